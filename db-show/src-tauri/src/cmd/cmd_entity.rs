@@ -1,6 +1,20 @@
 use serde::{Deserialize, Serialize};
 use std::collections::{BTreeMap, HashMap};
 
+use crate::op::es_entity::{
+    EsAliasAction, EsAnalyzeOptions, EsByQueryOptions, EsResizeOptions, EsRestoreOptions,
+    EsSearchOptions, EsSnapshotOptions, EsWriteOptions,
+};
+use crate::op::mongo_entity::{
+    MongoAggregateOptions, MongoCurrentOpFilter, MongoExportOptions, MongoFindOptions,
+    MongoImportOptions, MongoIndexOptions, MongoProfileQuery, MongoRoleOptions, MongoRoleRef,
+    MongoUserOptions, MongoWatchOptions,
+};
+use crate::op::redis_entity::{
+    AclUser, ImportConflictPolicy, MonitorCapture, MonitorFilter, RedisFailoverOptions,
+    RedisMigrateOptions, RedisTransferFormat,
+};
+
 #[derive(Deserialize, Serialize, Debug)]
 pub struct GetMysqlTablesParam {
     pub id: String,
@@ -98,11 +112,486 @@ pub struct ChangeRedisZSetValueParam {
     pub members_scores: BTreeMap<String, f64>,
 }
 
+#[derive(Deserialize, Serialize, Debug)]
+pub struct GetEsInfoParam {
+    pub id: String,
+}
+
+#[derive(Deserialize, Serialize, Debug)]
+pub struct EsListIndicesParam {
+    pub id: String,
+    /// 索引名或通配符，为空时列出全部
+    #[serde(default)]
+    pub pattern: String,
+}
+
+#[derive(Deserialize, Serialize, Debug)]
+pub struct EsIndexParam {
+    pub id: String,
+    pub index: String,
+}
+
+#[derive(Deserialize, Serialize, Debug)]
+pub struct EsCreateIndexParam {
+    pub id: String,
+    pub index: String,
+    /// settings/mappings/aliases（JSON）
+    #[serde(default)]
+    pub body: String,
+}
+
+#[derive(Deserialize, Serialize, Debug)]
+pub struct EsForceMergeParam {
+    pub id: String,
+    pub index: String,
+    #[serde(default)]
+    pub max_num_segments: Option<u32>,
+    #[serde(default)]
+    pub only_expunge_deletes: bool,
+}
+
+#[derive(Deserialize, Serialize, Debug)]
+pub struct EsResizeIndexParam {
+    pub id: String,
+    pub source: String,
+    pub options: EsResizeOptions,
+}
+
+#[derive(Deserialize, Serialize, Debug)]
+pub struct EsUpdateSettingsParam {
+    pub id: String,
+    pub index: String,
+    /// 动态配置（JSON）
+    pub settings: String,
+}
+
+#[derive(Deserialize, Serialize, Debug)]
+pub struct EsUpdateAliasesParam {
+    pub id: String,
+    pub actions: Vec<EsAliasAction>,
+}
+
+#[derive(Deserialize, Serialize, Debug)]
+pub struct EsSearchParam {
+    pub id: String,
+    pub options: EsSearchOptions,
+}
+
+#[derive(Deserialize, Serialize, Debug)]
+pub struct EsOpenPitParam {
+    pub id: String,
+    pub indices: Vec<String>,
+    /// 保持时间，如 `1m`
+    #[serde(default)]
+    pub keep_alive: String,
+}
+
+#[derive(Deserialize, Serialize, Debug)]
+pub struct EsClosePitParam {
+    pub id: String,
+    pub pit_id: String,
+}
+
+#[derive(Deserialize, Serialize, Debug)]
+pub struct EsQueryParam {
+    pub id: String,
+    #[serde(default)]
+    pub indices: Vec<String>,
+    /// Query DSL 请求体（JSON），只使用其中的 query
+    #[serde(default)]
+    pub body: String,
+}
+
+#[derive(Deserialize, Serialize, Debug)]
+pub struct EsDocumentParam {
+    pub id: String,
+    pub index: String,
+    pub doc_id: String,
+}
+
+#[derive(Deserialize, Serialize, Debug)]
+pub struct EsIndexDocumentParam {
+    pub id: String,
+    pub index: String,
+    /// 为空时自动生成
+    #[serde(default)]
+    pub doc_id: String,
+    pub body: String,
+    /// 文档已存在时失败
+    #[serde(default)]
+    pub create: bool,
+    #[serde(default)]
+    pub options: EsWriteOptions,
+}
+
+#[derive(Deserialize, Serialize, Debug)]
+pub struct EsUpdateDocumentParam {
+    pub id: String,
+    pub index: String,
+    pub doc_id: String,
+    /// 需要修改的字段（JSON）
+    pub doc: String,
+    #[serde(default)]
+    pub doc_as_upsert: bool,
+    #[serde(default)]
+    pub options: EsWriteOptions,
+}
+
+#[derive(Deserialize, Serialize, Debug)]
+pub struct EsDeleteDocumentParam {
+    pub id: String,
+    pub index: String,
+    pub doc_id: String,
+    #[serde(default)]
+    pub options: EsWriteOptions,
+}
+
+#[derive(Deserialize, Serialize, Debug)]
+pub struct EsByQueryParam {
+    pub id: String,
+    pub options: EsByQueryOptions,
+}
+
+#[derive(Deserialize, Serialize, Debug)]
+pub struct EsTaskParam {
+    pub id: String,
+    pub task_id: String,
+}
+
+#[derive(Deserialize, Serialize, Debug)]
+pub struct EsBulkImportParam {
+    pub id: String,
+    /// 动作行未指定 _index 时使用的索引
+    #[serde(default)]
+    pub index: String,
+    pub file_path: String,
+    #[serde(default)]
+    pub refresh: bool,
+}
+
+#[derive(Deserialize, Serialize, Debug)]
+pub struct EsFieldCapsParam {
+    pub id: String,
+    /// 索引名或通配符，为空时查看全部索引
+    #[serde(default)]
+    pub pattern: String,
+    /// 逗号分隔的字段名，支持通配符，为空时查看全部字段
+    #[serde(default)]
+    pub fields: String,
+    #[serde(default)]
+    pub conflicts_only: bool,
+}
+
+#[derive(Deserialize, Serialize, Debug)]
+pub struct EsAnalyzeParam {
+    pub id: String,
+    pub options: EsAnalyzeOptions,
+}
+
+#[derive(Deserialize, Serialize, Debug)]
+pub struct EsShardsParam {
+    pub id: String,
+    /// 索引名或通配符，为空时查看全部索引
+    #[serde(default)]
+    pub pattern: String,
+    #[serde(default)]
+    pub unassigned_only: bool,
+}
+
+#[derive(Deserialize, Serialize, Debug)]
+pub struct EsAllocationExplainParam {
+    pub id: String,
+    /// 为空时解释第一个未分配的分片
+    #[serde(default)]
+    pub index: String,
+    #[serde(default)]
+    pub shard: u32,
+    #[serde(default)]
+    pub primary: bool,
+}
+
+#[derive(Deserialize, Serialize, Debug)]
+pub struct EsTasksParam {
+    pub id: String,
+    /// 逗号分隔的动作过滤，如 `*byquery`
+    #[serde(default)]
+    pub actions: String,
+}
+
+#[derive(Deserialize, Serialize, Debug)]
+pub struct EsClusterSettingsParam {
+    pub id: String,
+    #[serde(default)]
+    pub include_defaults: bool,
+}
+
+#[derive(Deserialize, Serialize, Debug)]
+pub struct EsUpdateClusterSettingsParam {
+    pub id: String,
+    /// 扁平配置项（JSON），值为 null 时恢复默认
+    pub settings: String,
+    /// true 为 persistent，false 为 transient
+    #[serde(default)]
+    pub persistent: bool,
+}
+
+#[derive(Deserialize, Serialize, Debug)]
+pub struct EsCreateRepositoryParam {
+    pub id: String,
+    pub name: String,
+    pub repository_type: String,
+    /// 仓库配置（JSON）
+    #[serde(default)]
+    pub settings: String,
+    #[serde(default)]
+    pub verify: bool,
+}
+
+#[derive(Deserialize, Serialize, Debug)]
+pub struct EsRepositoryParam {
+    pub id: String,
+    pub repository: String,
+}
+
+#[derive(Deserialize, Serialize, Debug)]
+pub struct EsSnapshotParam {
+    pub id: String,
+    pub repository: String,
+    pub snapshot: String,
+    #[serde(default)]
+    pub options: EsSnapshotOptions,
+}
+
+#[derive(Deserialize, Serialize, Debug)]
+pub struct EsRestoreParam {
+    pub id: String,
+    pub repository: String,
+    pub snapshot: String,
+    #[serde(default)]
+    pub options: EsRestoreOptions,
+}
+
 #[derive(Deserialize, Serialize, Debug)]
 pub struct GetMongoInfoParam {
     pub id: String,
 }
 
+#[derive(Deserialize, Serialize, Debug)]
+pub struct MongoFindParam {
+    pub id: String,
+    pub database: String,
+    pub collection: String,
+    #[serde(default)]
+    pub options: MongoFindOptions,
+}
+
+#[derive(Deserialize, Serialize, Debug)]
+pub struct MongoAggregateParam {
+    pub id: String,
+    pub database: String,
+    pub collection: String,
+    pub options: MongoAggregateOptions,
+    /// 仅 explain 使用，默认 queryPlanner
+    #[serde(default)]
+    pub verbosity: String,
+}
+
+#[derive(Deserialize, Serialize, Debug)]
+pub struct MongoSchemaParam {
+    pub id: String,
+    pub database: String,
+    pub collection: String,
+    /// 抽样文档数，0 时使用默认值
+    #[serde(default)]
+    pub sample_size: u64,
+}
+
+#[derive(Deserialize, Serialize, Debug)]
+pub struct MongoCreateIndexParam {
+    pub id: String,
+    pub database: String,
+    pub collection: String,
+    pub options: MongoIndexOptions,
+}
+
+#[derive(Deserialize, Serialize, Debug)]
+pub struct MongoDropIndexParam {
+    pub id: String,
+    pub database: String,
+    pub collection: String,
+    pub index_name: String,
+}
+
+#[derive(Deserialize, Serialize, Debug)]
+pub struct MongoExportParam {
+    pub id: String,
+    pub database: String,
+    pub collection: String,
+    pub file_path: String,
+    pub options: MongoExportOptions,
+}
+
+#[derive(Deserialize, Serialize, Debug)]
+pub struct MongoImportParam {
+    pub id: String,
+    pub database: String,
+    pub collection: String,
+    pub file_path: String,
+    pub options: MongoImportOptions,
+}
+
+#[derive(Deserialize, Serialize, Debug)]
+pub struct MongoCurrentOpParam {
+    pub id: String,
+    #[serde(default)]
+    pub filter: MongoCurrentOpFilter,
+}
+
+#[derive(Deserialize, Serialize, Debug)]
+pub struct MongoKillOpParam {
+    pub id: String,
+    pub opid: String,
+}
+
+#[derive(Deserialize, Serialize, Debug)]
+pub struct MongoProfilingParam {
+    pub id: String,
+    pub database: String,
+    /// 0 关闭、1 仅记录慢操作、2 记录所有操作
+    pub level: i32,
+    #[serde(default)]
+    pub slowms: Option<i64>,
+}
+
+#[derive(Deserialize, Serialize, Debug)]
+pub struct MongoProfileParam {
+    pub id: String,
+    pub database: String,
+    #[serde(default)]
+    pub query: MongoProfileQuery,
+}
+
+#[derive(Deserialize, Serialize, Debug)]
+pub struct MongoListUsersParam {
+    pub id: String,
+    /// 为空时列出所有数据库的用户
+    #[serde(default)]
+    pub database: String,
+    /// 只返回拥有该角色的用户
+    #[serde(default)]
+    pub role: Option<MongoRoleRef>,
+}
+
+#[derive(Deserialize, Serialize, Debug)]
+pub struct MongoUserParam {
+    pub id: String,
+    pub database: String,
+    pub options: MongoUserOptions,
+}
+
+#[derive(Deserialize, Serialize, Debug)]
+pub struct MongoDropUserParam {
+    pub id: String,
+    pub database: String,
+    pub user: String,
+}
+
+#[derive(Deserialize, Serialize, Debug)]
+pub struct MongoUserRolesParam {
+    pub id: String,
+    pub database: String,
+    pub user: String,
+    pub roles: Vec<MongoRoleRef>,
+}
+
+#[derive(Deserialize, Serialize, Debug)]
+pub struct MongoListRolesParam {
+    pub id: String,
+    pub database: String,
+    #[serde(default)]
+    pub show_builtin: bool,
+}
+
+#[derive(Deserialize, Serialize, Debug)]
+pub struct MongoRoleParam {
+    pub id: String,
+    pub database: String,
+    pub options: MongoRoleOptions,
+}
+
+#[derive(Deserialize, Serialize, Debug)]
+pub struct MongoDropRoleParam {
+    pub id: String,
+    pub database: String,
+    pub role: String,
+}
+
+#[derive(Deserialize, Serialize, Debug)]
+pub struct MongoWatchParam {
+    pub id: String,
+    pub options: MongoWatchOptions,
+}
+
+#[derive(Deserialize, Serialize, Debug)]
+pub struct MongoWatchStopParam {
+    pub session_id: String,
+}
+
+#[derive(Deserialize, Serialize, Debug)]
+pub struct MongoDatabaseParam {
+    pub id: String,
+    pub database: String,
+}
+
+#[derive(Deserialize, Serialize, Debug)]
+pub struct MongoCollectionParam {
+    pub id: String,
+    pub database: String,
+    pub collection: String,
+}
+
+#[derive(Deserialize, Serialize, Debug)]
+pub struct MongoInsertParam {
+    pub id: String,
+    pub database: String,
+    pub collection: String,
+    /// 扩展 JSON 对象或对象数组
+    pub documents: String,
+}
+
+#[derive(Deserialize, Serialize, Debug)]
+pub struct MongoUpdateParam {
+    pub id: String,
+    pub database: String,
+    pub collection: String,
+    pub filter: String,
+    pub update: String,
+    #[serde(default)]
+    pub many: bool,
+    #[serde(default)]
+    pub upsert: bool,
+}
+
+#[derive(Deserialize, Serialize, Debug)]
+pub struct MongoReplaceParam {
+    pub id: String,
+    pub database: String,
+    pub collection: String,
+    pub document: String,
+}
+
+#[derive(Deserialize, Serialize, Debug)]
+pub struct MongoDeleteParam {
+    pub id: String,
+    pub database: String,
+    pub collection: String,
+    pub filter: String,
+    #[serde(default)]
+    pub many: bool,
+    #[serde(default)]
+    pub dry_run: bool,
+}
+
 #[derive(Deserialize, Serialize, Debug)]
 pub struct SetZookeeperDataParam {
     pub id: String,
@@ -161,3 +650,226 @@ pub struct GetListMeasurementTagInfluxdb {
     pub bucket: String,
     pub measurement: String,
 }
+
+#[derive(Deserialize, Serialize, Debug)]
+pub struct RedisSubscribeParam {
+    pub id: String,
+    #[serde(default)]
+    pub channels: Vec<String>,
+    #[serde(default)]
+    pub patterns: Vec<String>,
+}
+
+#[derive(Deserialize, Serialize, Debug)]
+pub struct RedisSessionParam {
+    pub session_id: String,
+}
+
+#[derive(Deserialize, Serialize, Debug)]
+pub struct RedisPublishParam {
+    pub id: String,
+    pub channel: String,
+    pub message: String,
+}
+
+#[derive(Deserialize, Serialize, Debug)]
+pub struct RedisPubSubChannelsParam {
+    pub id: String,
+    #[serde(default)]
+    pub pattern: String,
+    #[serde(default)]
+    pub channels: Vec<String>,
+}
+
+#[derive(Deserialize, Serialize, Debug)]
+pub struct RedisMonitorParam {
+    pub id: String,
+    #[serde(default)]
+    pub filter: MonitorFilter,
+    #[serde(default)]
+    pub capture: Option<MonitorCapture>,
+    /// 自动停止时间（秒），0 表示不自动停止
+    #[serde(default)]
+    pub max_duration_secs: u64,
+}
+
+#[derive(Deserialize, Serialize, Debug)]
+pub struct RedisMonitorCaptureParam {
+    pub file_path: String,
+}
+
+#[derive(Deserialize, Serialize, Debug)]
+pub struct RedisExportParam {
+    pub id: String,
+    pub db_index: i32,
+    pub pattern: String,
+    pub format: RedisTransferFormat,
+    pub file_path: String,
+}
+
+#[derive(Deserialize, Serialize, Debug)]
+pub struct RedisImportParam {
+    pub id: String,
+    pub db_index: i32,
+    pub format: RedisTransferFormat,
+    pub file_path: String,
+    pub policy: ImportConflictPolicy,
+    #[serde(default)]
+    pub rename_suffix: String,
+}
+
+#[derive(Deserialize, Serialize, Debug)]
+pub struct RedisMigrateParam {
+    pub source_id: String,
+    pub target_id: String,
+    pub source_db: i32,
+    pub target_db: i32,
+    pub pattern: String,
+    #[serde(default)]
+    pub options: RedisMigrateOptions,
+}
+
+#[derive(Deserialize, Serialize, Debug)]
+pub struct RedisEvalParam {
+    pub id: String,
+    pub db_index: i32,
+    pub script: String,
+    #[serde(default)]
+    pub keys: Vec<String>,
+    #[serde(default)]
+    pub args: Vec<String>,
+}
+
+#[derive(Deserialize, Serialize, Debug)]
+pub struct RedisEvalShaParam {
+    pub id: String,
+    pub db_index: i32,
+    pub sha: String,
+    #[serde(default)]
+    pub keys: Vec<String>,
+    #[serde(default)]
+    pub args: Vec<String>,
+}
+
+#[derive(Deserialize, Serialize, Debug)]
+pub struct RedisScriptLoadParam {
+    pub id: String,
+    pub script: String,
+}
+
+#[derive(Deserialize, Serialize, Debug)]
+pub struct RedisScriptExistsParam {
+    pub id: String,
+    pub shas: Vec<String>,
+}
+
+#[derive(Deserialize, Serialize, Debug)]
+pub struct RedisScriptIdParam {
+    pub id: String,
+}
+
+#[derive(Deserialize, Serialize, Debug)]
+pub struct RedisFunctionListParam {
+    pub id: String,
+    #[serde(default)]
+    pub library_pattern: String,
+    #[serde(default)]
+    pub with_code: bool,
+}
+
+#[derive(Deserialize, Serialize, Debug)]
+pub struct RedisFunctionLoadParam {
+    pub id: String,
+    pub code: String,
+    #[serde(default)]
+    pub replace: bool,
+}
+
+#[derive(Deserialize, Serialize, Debug)]
+pub struct RedisFunctionDeleteParam {
+    pub id: String,
+    pub library_name: String,
+}
+
+#[derive(Deserialize, Serialize, Debug)]
+pub struct RedisFcallParam {
+    pub id: String,
+    pub db_index: i32,
+    pub function: String,
+    #[serde(default)]
+    pub keys: Vec<String>,
+    #[serde(default)]
+    pub args: Vec<String>,
+    #[serde(default)]
+    pub read_only: bool,
+}
+
+#[derive(Deserialize, Serialize, Debug)]
+pub struct RedisConfigGetParam {
+    pub id: String,
+    pub pattern: String,
+}
+
+#[derive(Deserialize, Serialize, Debug)]
+pub struct RedisConfigSetParam {
+    pub id: String,
+    pub name: String,
+    pub value: String,
+}
+
+#[derive(Deserialize, Serialize, Debug)]
+pub struct RedisAclUserParam {
+    pub id: String,
+    pub username: String,
+}
+
+#[derive(Deserialize, Serialize, Debug)]
+pub struct RedisAclSaveUserParam {
+    pub id: String,
+    pub user: AclUser,
+    #[serde(default)]
+    pub new_passwords: Vec<String>,
+}
+
+#[derive(Deserialize, Serialize, Debug)]
+pub struct RedisAclDeleteParam {
+    pub id: String,
+    pub usernames: Vec<String>,
+}
+
+#[derive(Deserialize, Serialize, Debug)]
+pub struct RedisAclLogParam {
+    pub id: String,
+    pub count: usize,
+}
+
+#[derive(Deserialize, Serialize, Debug)]
+pub struct RedisReplicationParam {
+    pub id: String,
+    /// 副本落后字节数告警阈值
+    pub lag_threshold: i64,
+}
+
+#[derive(Deserialize, Serialize, Debug)]
+pub struct RedisBgsaveParam {
+    pub id: String,
+    #[serde(default)]
+    pub schedule: bool,
+}
+
+#[derive(Deserialize, Serialize, Debug)]
+pub struct RedisReplicaOfParam {
+    pub id: String,
+    /// 为空时执行 REPLICAOF NO ONE
+    #[serde(default)]
+    pub host: String,
+    #[serde(default)]
+    pub port: u16,
+}
+
+#[derive(Deserialize, Serialize, Debug)]
+pub struct RedisFailoverParam {
+    pub id: String,
+    #[serde(default)]
+    pub options: RedisFailoverOptions,
+}
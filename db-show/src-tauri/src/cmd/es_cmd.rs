@@ -1,5 +1,25 @@
+use std::collections::BTreeMap;
+
+use serde_json::Value;
+
+use crate::cmd::cmd_entity::{
+    EsAllocationExplainParam, EsAnalyzeParam, EsBulkImportParam, EsByQueryParam, EsClosePitParam,
+    EsClusterSettingsParam, EsCreateIndexParam, EsCreateRepositoryParam, EsDeleteDocumentParam,
+    EsDocumentParam, EsFieldCapsParam, EsForceMergeParam, EsIndexDocumentParam, EsIndexParam,
+    EsListIndicesParam, EsOpenPitParam, EsQueryParam, EsRepositoryParam, EsResizeIndexParam,
+    EsRestoreParam, EsSearchParam, EsShardsParam, EsSnapshotParam, EsTaskParam, EsTasksParam,
+    EsUpdateAliasesParam, EsUpdateClusterSettingsParam, EsUpdateDocumentParam,
+    EsUpdateSettingsParam, GetEsInfoParam,
+};
 use crate::config::es_config::ESUserPassword;
 use crate::config::es_config_storage::{ESStorageEntity, ESStorageManager, ESStorageService};
+use crate::op::es_entity::{
+    ClusterHealth, EsAcknowledged, EsAliasInfo, EsAllocationExplain, EsAnalyzeToken, EsBulkResult,
+    EsByQueryResult, EsClusterSettings, EsDocument, EsFieldCaps, EsIndexInfo, EsIndexMapping,
+    EsSearchResult, EsShardInfo, EsShardsSummary, EsSnapshotInfo, EsSnapshotRepository, EsTaskInfo,
+    EsTaskStatus, EsValidateResult, EsWriteResult, NodesStats,
+};
+use crate::op::es_op::EsOperation;
 use crate::resp::resp::Response;
 
 #[tauri::command]
@@ -14,3 +34,486 @@ pub fn add_es_config(param: ESUserPassword) -> Response<bool> {
     manager.add_username_password_model(param);
     Response::ok()
 }
+
+#[tauri::command]
+pub async fn es_cluster_health(param: GetEsInfoParam) -> Result<Response<ClusterHealth>, ()> {
+    let operation = match es_operation(param.id.as_str()) {
+        Ok(operation) => operation,
+        Err(err) => return Ok(Response::from_error(err)),
+    };
+    Ok(operation.get_cluster_health().await)
+}
+
+#[tauri::command]
+pub async fn es_nodes_stats(param: GetEsInfoParam) -> Result<Response<NodesStats>, ()> {
+    let operation = match es_operation(param.id.as_str()) {
+        Ok(operation) => operation,
+        Err(err) => return Ok(Response::from_error(err)),
+    };
+    Ok(operation.get_nodes_stats().await)
+}
+
+#[tauri::command]
+pub async fn es_list_indices(param: EsListIndicesParam) -> Result<Response<Vec<EsIndexInfo>>, ()> {
+    let operation = match es_operation(param.id.as_str()) {
+        Ok(operation) => operation,
+        Err(err) => return Ok(Response::from_error(err)),
+    };
+    Ok(operation.list_indices(param.pattern.as_str()).await)
+}
+
+#[tauri::command]
+pub async fn es_create_index(param: EsCreateIndexParam) -> Result<Response<EsAcknowledged>, ()> {
+    let operation = match es_operation(param.id.as_str()) {
+        Ok(operation) => operation,
+        Err(err) => return Ok(Response::from_error(err)),
+    };
+    Ok(operation
+        .create_index(param.index.as_str(), param.body.as_str())
+        .await)
+}
+
+#[tauri::command]
+pub async fn es_delete_index(param: EsIndexParam) -> Result<Response<EsAcknowledged>, ()> {
+    let operation = match es_operation(param.id.as_str()) {
+        Ok(operation) => operation,
+        Err(err) => return Ok(Response::from_error(err)),
+    };
+    Ok(operation.delete_index(param.index.as_str()).await)
+}
+
+#[tauri::command]
+pub async fn es_open_index(param: EsIndexParam) -> Result<Response<EsAcknowledged>, ()> {
+    let operation = match es_operation(param.id.as_str()) {
+        Ok(operation) => operation,
+        Err(err) => return Ok(Response::from_error(err)),
+    };
+    Ok(operation.open_index(param.index.as_str()).await)
+}
+
+#[tauri::command]
+pub async fn es_close_index(param: EsIndexParam) -> Result<Response<EsAcknowledged>, ()> {
+    let operation = match es_operation(param.id.as_str()) {
+        Ok(operation) => operation,
+        Err(err) => return Ok(Response::from_error(err)),
+    };
+    Ok(operation.close_index(param.index.as_str()).await)
+}
+
+#[tauri::command]
+pub async fn es_refresh_index(param: EsIndexParam) -> Result<Response<EsShardsSummary>, ()> {
+    let operation = match es_operation(param.id.as_str()) {
+        Ok(operation) => operation,
+        Err(err) => return Ok(Response::from_error(err)),
+    };
+    Ok(operation.refresh_index(param.index.as_str()).await)
+}
+
+#[tauri::command]
+pub async fn es_flush_index(param: EsIndexParam) -> Result<Response<EsShardsSummary>, ()> {
+    let operation = match es_operation(param.id.as_str()) {
+        Ok(operation) => operation,
+        Err(err) => return Ok(Response::from_error(err)),
+    };
+    Ok(operation.flush_index(param.index.as_str()).await)
+}
+
+#[tauri::command]
+pub async fn es_force_merge(param: EsForceMergeParam) -> Result<Response<EsShardsSummary>, ()> {
+    let operation = match es_operation(param.id.as_str()) {
+        Ok(operation) => operation,
+        Err(err) => return Ok(Response::from_error(err)),
+    };
+    Ok(operation
+        .force_merge(
+            param.index.as_str(),
+            param.max_num_segments,
+            param.only_expunge_deletes,
+        )
+        .await)
+}
+
+#[tauri::command]
+pub async fn es_resize_index(param: EsResizeIndexParam) -> Result<Response<EsAcknowledged>, ()> {
+    let operation = match es_operation(param.id.as_str()) {
+        Ok(operation) => operation,
+        Err(err) => return Ok(Response::from_error(err)),
+    };
+    Ok(operation
+        .resize_index(param.source.as_str(), &param.options)
+        .await)
+}
+
+#[tauri::command]
+pub async fn es_get_index_settings(
+    param: EsIndexParam,
+) -> Result<Response<BTreeMap<String, BTreeMap<String, Value>>>, ()> {
+    let operation = match es_operation(param.id.as_str()) {
+        Ok(operation) => operation,
+        Err(err) => return Ok(Response::from_error(err)),
+    };
+    Ok(operation.get_index_settings(param.index.as_str()).await)
+}
+
+#[tauri::command]
+pub async fn es_update_index_settings(
+    param: EsUpdateSettingsParam,
+) -> Result<Response<EsAcknowledged>, ()> {
+    let operation = match es_operation(param.id.as_str()) {
+        Ok(operation) => operation,
+        Err(err) => return Ok(Response::from_error(err)),
+    };
+    Ok(operation
+        .update_index_settings(param.index.as_str(), param.settings.as_str())
+        .await)
+}
+
+#[tauri::command]
+pub async fn es_list_aliases(param: GetEsInfoParam) -> Result<Response<Vec<EsAliasInfo>>, ()> {
+    let operation = match es_operation(param.id.as_str()) {
+        Ok(operation) => operation,
+        Err(err) => return Ok(Response::from_error(err)),
+    };
+    Ok(operation.list_aliases().await)
+}
+
+#[tauri::command]
+pub async fn es_update_aliases(
+    param: EsUpdateAliasesParam,
+) -> Result<Response<EsAcknowledged>, ()> {
+    let operation = match es_operation(param.id.as_str()) {
+        Ok(operation) => operation,
+        Err(err) => return Ok(Response::from_error(err)),
+    };
+    Ok(operation.update_aliases(&param.actions).await)
+}
+
+#[tauri::command]
+pub async fn es_search(param: EsSearchParam) -> Result<Response<EsSearchResult>, ()> {
+    let operation = match es_operation(param.id.as_str()) {
+        Ok(operation) => operation,
+        Err(err) => return Ok(Response::from_error(err)),
+    };
+    Ok(operation.search(&param.options).await)
+}
+
+#[tauri::command]
+pub async fn es_open_pit(param: EsOpenPitParam) -> Result<Response<String>, ()> {
+    let operation = match es_operation(param.id.as_str()) {
+        Ok(operation) => operation,
+        Err(err) => return Ok(Response::from_error(err)),
+    };
+    Ok(operation
+        .open_pit(&param.indices, param.keep_alive.as_str())
+        .await)
+}
+
+#[tauri::command]
+pub async fn es_close_pit(param: EsClosePitParam) -> Result<Response<bool>, ()> {
+    let operation = match es_operation(param.id.as_str()) {
+        Ok(operation) => operation,
+        Err(err) => return Ok(Response::from_error(err)),
+    };
+    Ok(operation.close_pit(param.pit_id.as_str()).await)
+}
+
+#[tauri::command]
+pub async fn es_count(param: EsQueryParam) -> Result<Response<u64>, ()> {
+    let operation = match es_operation(param.id.as_str()) {
+        Ok(operation) => operation,
+        Err(err) => return Ok(Response::from_error(err)),
+    };
+    Ok(operation.count(&param.indices, param.body.as_str()).await)
+}
+
+#[tauri::command]
+pub async fn es_validate_query(param: EsQueryParam) -> Result<Response<EsValidateResult>, ()> {
+    let operation = match es_operation(param.id.as_str()) {
+        Ok(operation) => operation,
+        Err(err) => return Ok(Response::from_error(err)),
+    };
+    Ok(operation
+        .validate_query(&param.indices, param.body.as_str())
+        .await)
+}
+
+#[tauri::command]
+pub async fn es_get_document(param: EsDocumentParam) -> Result<Response<EsDocument>, ()> {
+    let operation = match es_operation(param.id.as_str()) {
+        Ok(operation) => operation,
+        Err(err) => return Ok(Response::from_error(err)),
+    };
+    Ok(operation
+        .get_document(param.index.as_str(), param.doc_id.as_str())
+        .await)
+}
+
+#[tauri::command]
+pub async fn es_index_document(param: EsIndexDocumentParam) -> Result<Response<EsWriteResult>, ()> {
+    let operation = match es_operation(param.id.as_str()) {
+        Ok(operation) => operation,
+        Err(err) => return Ok(Response::from_error(err)),
+    };
+    Ok(operation
+        .index_document(
+            param.index.as_str(),
+            param.doc_id.as_str(),
+            param.body.as_str(),
+            param.create,
+            &param.options,
+        )
+        .await)
+}
+
+#[tauri::command]
+pub async fn es_update_document(
+    param: EsUpdateDocumentParam,
+) -> Result<Response<EsWriteResult>, ()> {
+    let operation = match es_operation(param.id.as_str()) {
+        Ok(operation) => operation,
+        Err(err) => return Ok(Response::from_error(err)),
+    };
+    Ok(operation
+        .update_document(
+            param.index.as_str(),
+            param.doc_id.as_str(),
+            param.doc.as_str(),
+            param.doc_as_upsert,
+            &param.options,
+        )
+        .await)
+}
+
+#[tauri::command]
+pub async fn es_delete_document(
+    param: EsDeleteDocumentParam,
+) -> Result<Response<EsWriteResult>, ()> {
+    let operation = match es_operation(param.id.as_str()) {
+        Ok(operation) => operation,
+        Err(err) => return Ok(Response::from_error(err)),
+    };
+    Ok(operation
+        .delete_document(param.index.as_str(), param.doc_id.as_str(), &param.options)
+        .await)
+}
+
+#[tauri::command]
+pub async fn es_update_by_query(param: EsByQueryParam) -> Result<Response<EsByQueryResult>, ()> {
+    let operation = match es_operation(param.id.as_str()) {
+        Ok(operation) => operation,
+        Err(err) => return Ok(Response::from_error(err)),
+    };
+    Ok(operation.update_by_query(&param.options).await)
+}
+
+#[tauri::command]
+pub async fn es_delete_by_query(param: EsByQueryParam) -> Result<Response<EsByQueryResult>, ()> {
+    let operation = match es_operation(param.id.as_str()) {
+        Ok(operation) => operation,
+        Err(err) => return Ok(Response::from_error(err)),
+    };
+    Ok(operation.delete_by_query(&param.options).await)
+}
+
+#[tauri::command]
+pub async fn es_get_task(param: EsTaskParam) -> Result<Response<EsTaskStatus>, ()> {
+    let operation = match es_operation(param.id.as_str()) {
+        Ok(operation) => operation,
+        Err(err) => return Ok(Response::from_error(err)),
+    };
+    Ok(operation.get_task(param.task_id.as_str()).await)
+}
+
+#[tauri::command]
+pub async fn es_bulk_import(param: EsBulkImportParam) -> Result<Response<EsBulkResult>, ()> {
+    let operation = match es_operation(param.id.as_str()) {
+        Ok(operation) => operation,
+        Err(err) => return Ok(Response::from_error(err)),
+    };
+    Ok(operation
+        .bulk_import(
+            param.index.as_str(),
+            param.file_path.as_str(),
+            param.refresh,
+        )
+        .await)
+}
+
+#[tauri::command]
+pub async fn es_get_mapping(param: EsIndexParam) -> Result<Response<Vec<EsIndexMapping>>, ()> {
+    let operation = match es_operation(param.id.as_str()) {
+        Ok(operation) => operation,
+        Err(err) => return Ok(Response::from_error(err)),
+    };
+    Ok(operation.get_mapping(param.index.as_str()).await)
+}
+
+#[tauri::command]
+pub async fn es_field_caps(param: EsFieldCapsParam) -> Result<Response<Vec<EsFieldCaps>>, ()> {
+    let operation = match es_operation(param.id.as_str()) {
+        Ok(operation) => operation,
+        Err(err) => return Ok(Response::from_error(err)),
+    };
+    Ok(operation
+        .field_caps(
+            param.pattern.as_str(),
+            param.fields.as_str(),
+            param.conflicts_only,
+        )
+        .await)
+}
+
+#[tauri::command]
+pub async fn es_analyze(param: EsAnalyzeParam) -> Result<Response<Vec<EsAnalyzeToken>>, ()> {
+    let operation = match es_operation(param.id.as_str()) {
+        Ok(operation) => operation,
+        Err(err) => return Ok(Response::from_error(err)),
+    };
+    Ok(operation.analyze(&param.options).await)
+}
+
+#[tauri::command]
+pub async fn es_list_shards(param: EsShardsParam) -> Result<Response<Vec<EsShardInfo>>, ()> {
+    let operation = match es_operation(param.id.as_str()) {
+        Ok(operation) => operation,
+        Err(err) => return Ok(Response::from_error(err)),
+    };
+    Ok(operation
+        .list_shards(param.pattern.as_str(), param.unassigned_only)
+        .await)
+}
+
+#[tauri::command]
+pub async fn es_allocation_explain(
+    param: EsAllocationExplainParam,
+) -> Result<Response<EsAllocationExplain>, ()> {
+    let operation = match es_operation(param.id.as_str()) {
+        Ok(operation) => operation,
+        Err(err) => return Ok(Response::from_error(err)),
+    };
+    Ok(operation
+        .allocation_explain(param.index.as_str(), param.shard, param.primary)
+        .await)
+}
+
+#[tauri::command]
+pub async fn es_list_tasks(param: EsTasksParam) -> Result<Response<Vec<EsTaskInfo>>, ()> {
+    let operation = match es_operation(param.id.as_str()) {
+        Ok(operation) => operation,
+        Err(err) => return Ok(Response::from_error(err)),
+    };
+    Ok(operation.list_tasks(param.actions.as_str()).await)
+}
+
+#[tauri::command]
+pub async fn es_cancel_task(param: EsTaskParam) -> Result<Response<bool>, ()> {
+    let operation = match es_operation(param.id.as_str()) {
+        Ok(operation) => operation,
+        Err(err) => return Ok(Response::from_error(err)),
+    };
+    Ok(operation.cancel_task(param.task_id.as_str()).await)
+}
+
+#[tauri::command]
+pub async fn es_get_cluster_settings(
+    param: EsClusterSettingsParam,
+) -> Result<Response<EsClusterSettings>, ()> {
+    let operation = match es_operation(param.id.as_str()) {
+        Ok(operation) => operation,
+        Err(err) => return Ok(Response::from_error(err)),
+    };
+    Ok(operation.get_cluster_settings(param.include_defaults).await)
+}
+
+#[tauri::command]
+pub async fn es_update_cluster_settings(
+    param: EsUpdateClusterSettingsParam,
+) -> Result<Response<EsClusterSettings>, ()> {
+    let operation = match es_operation(param.id.as_str()) {
+        Ok(operation) => operation,
+        Err(err) => return Ok(Response::from_error(err)),
+    };
+    Ok(operation
+        .update_cluster_settings(param.settings.as_str(), param.persistent)
+        .await)
+}
+
+#[tauri::command]
+pub async fn es_list_repositories(
+    param: GetEsInfoParam,
+) -> Result<Response<Vec<EsSnapshotRepository>>, ()> {
+    let operation = match es_operation(param.id.as_str()) {
+        Ok(operation) => operation,
+        Err(err) => return Ok(Response::from_error(err)),
+    };
+    Ok(operation.list_repositories().await)
+}
+
+#[tauri::command]
+pub async fn es_create_repository(
+    param: EsCreateRepositoryParam,
+) -> Result<Response<EsAcknowledged>, ()> {
+    let operation = match es_operation(param.id.as_str()) {
+        Ok(operation) => operation,
+        Err(err) => return Ok(Response::from_error(err)),
+    };
+    Ok(operation
+        .create_repository(
+            param.name.as_str(),
+            param.repository_type.as_str(),
+            param.settings.as_str(),
+            param.verify,
+        )
+        .await)
+}
+
+#[tauri::command]
+pub async fn es_list_snapshots(
+    param: EsRepositoryParam,
+) -> Result<Response<Vec<EsSnapshotInfo>>, ()> {
+    let operation = match es_operation(param.id.as_str()) {
+        Ok(operation) => operation,
+        Err(err) => return Ok(Response::from_error(err)),
+    };
+    Ok(operation.list_snapshots(param.repository.as_str()).await)
+}
+
+#[tauri::command]
+pub async fn es_create_snapshot(param: EsSnapshotParam) -> Result<Response<bool>, ()> {
+    let operation = match es_operation(param.id.as_str()) {
+        Ok(operation) => operation,
+        Err(err) => return Ok(Response::from_error(err)),
+    };
+    Ok(operation
+        .create_snapshot(
+            param.repository.as_str(),
+            param.snapshot.as_str(),
+            &param.options,
+        )
+        .await)
+}
+
+#[tauri::command]
+pub async fn es_restore_snapshot(param: EsRestoreParam) -> Result<Response<bool>, ()> {
+    let operation = match es_operation(param.id.as_str()) {
+        Ok(operation) => operation,
+        Err(err) => return Ok(Response::from_error(err)),
+    };
+    Ok(operation
+        .restore_snapshot(
+            param.repository.as_str(),
+            param.snapshot.as_str(),
+            &param.options,
+        )
+        .await)
+}
+
+/// 根据保存的连接配置创建 EsOperation
+fn es_operation(id: &str) -> Result<EsOperation, String> {
+    let manager = ESStorageManager::new();
+    let item = match manager.by_id(id) {
+        None => return Err("没有数据".to_string()),
+        Some(item) => item,
+    };
+    EsOperation::new(&item.to_user_password())
+}
@@ -1,58 +1,647 @@
-use crate::cmd::cmd_entity::GetMongoInfoParam;
+use std::collections::HashMap;
+use std::time::{Duration, Instant};
+
+use chrono::Local;
+use tauri::State;
+use tokio::sync::Mutex as AsyncMutex;
+use uuid::Uuid;
+
+use crate::cmd::cmd_entity::{
+    GetMongoInfoParam, MongoAggregateParam, MongoCollectionParam, MongoCreateIndexParam,
+    MongoCurrentOpParam, MongoDatabaseParam, MongoDeleteParam, MongoDropIndexParam,
+    MongoDropRoleParam, MongoDropUserParam, MongoExportParam, MongoFindParam, MongoImportParam,
+    MongoInsertParam, MongoKillOpParam, MongoListRolesParam, MongoListUsersParam,
+    MongoProfileParam, MongoProfilingParam, MongoReplaceParam, MongoRoleParam, MongoSchemaParam,
+    MongoUpdateParam, MongoUserParam, MongoUserRolesParam, MongoWatchParam, MongoWatchStopParam,
+};
 use crate::config::mongo_config::MongoUserPassword;
 use crate::config::mongo_config_storage::{
     MongoStorageEntity, MongoStorageManager, MongoStorageService,
 };
-use crate::op::mongo_entity::MongoServerInfoCol;
+use crate::config::mongo_resume_token_storage::{
+    MongoResumeTokenEntity, MongoResumeTokenStorageManager, MongoResumeTokenStorageService,
+};
+use crate::op::mongo_entity::{
+    MongoAggregateResult, MongoCurrentOp, MongoExplainSummary, MongoExportResult, MongoFindResult,
+    MongoImportResult, MongoIndexStats, MongoProfileEntry, MongoProfilingStatus,
+    MongoReplicaSetStatus, MongoRoleInfo, MongoSchemaReport, MongoServerInfoCol,
+    MongoShardingStatus, MongoUserInfo, MongoWatchHandle, MongoWatchInfo, MongoWriteResult,
+};
 use crate::op::mongo_op::MongoOperation;
 use crate::resp::resp::Response;
 
+/// change stream 事件名，出错结束时推送 operation_type 为 error 的事件
+pub static MONGO_CHANGE_EVENT: &str = "mongo-change-event";
+
+/// 监听过程中保存 resume token 的最小间隔，停止监听时总会保存
+const RESUME_TOKEN_SAVE_INTERVAL: Duration = Duration::from_secs(5);
+
+/// change stream 监听会话，由 tauri 托管
+#[derive(Default)]
+pub struct MongoWatchState {
+    pub sessions: AsyncMutex<HashMap<String, (MongoWatchInfo, MongoWatchHandle)>>,
+}
+
 #[tauri::command]
 pub async fn mongo_info(param: GetMongoInfoParam) -> Result<Response<MongoServerInfoCol>, ()> {
-    let manager = MongoStorageManager::new();
-
-    let option = manager.by_id(param.id.as_str());
-
-    let b = match option {
-        None => Ok(Response::from_error("没有数据")),
-        Some(&ref item) => {
-            let mongo_user_password = MongoUserPassword {
-                username: format!("{}", item.username),
-                password: format!("{}", item.password),
-                host: format!("{}", item.host),
-                port: item.port,
-                name: format!("{}", item.name),
-            };
-            let operation = MongoOperation::new(&mongo_user_password).await.unwrap();
-            let result = operation.get_server_info().await;
-            Ok(result)
-        }
+    let operation = match mongo_operation(param.id.as_str()).await {
+        Ok(operation) => operation,
+        Err(err) => return Ok(Response::from_error(err)),
     };
-    b
+    Ok(operation.get_server_info().await)
 }
 
 #[tauri::command]
 pub async fn mongo_db_names(param: GetMongoInfoParam) -> Result<Response<Vec<String>>, ()> {
-    let manager = MongoStorageManager::new();
+    let operation = match mongo_operation(param.id.as_str()).await {
+        Ok(operation) => operation,
+        Err(err) => return Ok(Response::from_error(err)),
+    };
+    Ok(operation.db_names().await)
+}
+
+#[tauri::command]
+pub async fn mongo_replica_set_status(
+    param: GetMongoInfoParam,
+) -> Result<Response<MongoReplicaSetStatus>, ()> {
+    let operation = match mongo_operation(param.id.as_str()).await {
+        Ok(operation) => operation,
+        Err(err) => return Ok(Response::from_error(err)),
+    };
+    Ok(operation.replica_set_status().await)
+}
+
+#[tauri::command]
+pub async fn mongo_sharding_status(
+    param: GetMongoInfoParam,
+) -> Result<Response<MongoShardingStatus>, ()> {
+    let operation = match mongo_operation(param.id.as_str()).await {
+        Ok(operation) => operation,
+        Err(err) => return Ok(Response::from_error(err)),
+    };
+    Ok(operation.sharding_status().await)
+}
+
+#[tauri::command]
+pub async fn mongo_find_documents(param: MongoFindParam) -> Result<Response<MongoFindResult>, ()> {
+    let operation = match mongo_operation(param.id.as_str()).await {
+        Ok(operation) => operation,
+        Err(err) => return Ok(Response::from_error(err)),
+    };
+    Ok(operation
+        .find_documents(
+            param.database.as_str(),
+            param.collection.as_str(),
+            &param.options,
+        )
+        .await)
+}
+
+#[tauri::command]
+pub async fn mongo_insert_documents(
+    param: MongoInsertParam,
+) -> Result<Response<MongoWriteResult>, ()> {
+    let operation = match mongo_operation(param.id.as_str()).await {
+        Ok(operation) => operation,
+        Err(err) => return Ok(Response::from_error(err)),
+    };
+    Ok(operation
+        .insert_documents(
+            param.database.as_str(),
+            param.collection.as_str(),
+            param.documents.as_str(),
+        )
+        .await)
+}
+
+#[tauri::command]
+pub async fn mongo_update_documents(
+    param: MongoUpdateParam,
+) -> Result<Response<MongoWriteResult>, ()> {
+    let operation = match mongo_operation(param.id.as_str()).await {
+        Ok(operation) => operation,
+        Err(err) => return Ok(Response::from_error(err)),
+    };
+    Ok(operation
+        .update_documents(
+            param.database.as_str(),
+            param.collection.as_str(),
+            param.filter.as_str(),
+            param.update.as_str(),
+            param.many,
+            param.upsert,
+        )
+        .await)
+}
+
+#[tauri::command]
+pub async fn mongo_replace_document(
+    param: MongoReplaceParam,
+) -> Result<Response<MongoWriteResult>, ()> {
+    let operation = match mongo_operation(param.id.as_str()).await {
+        Ok(operation) => operation,
+        Err(err) => return Ok(Response::from_error(err)),
+    };
+    Ok(operation
+        .replace_document(
+            param.database.as_str(),
+            param.collection.as_str(),
+            param.document.as_str(),
+        )
+        .await)
+}
+
+#[tauri::command]
+pub async fn mongo_delete_documents(
+    param: MongoDeleteParam,
+) -> Result<Response<MongoWriteResult>, ()> {
+    let operation = match mongo_operation(param.id.as_str()).await {
+        Ok(operation) => operation,
+        Err(err) => return Ok(Response::from_error(err)),
+    };
+    Ok(operation
+        .delete_documents(
+            param.database.as_str(),
+            param.collection.as_str(),
+            param.filter.as_str(),
+            param.many,
+            param.dry_run,
+        )
+        .await)
+}
+
+#[tauri::command]
+pub async fn mongo_aggregate(
+    param: MongoAggregateParam,
+) -> Result<Response<MongoAggregateResult>, ()> {
+    let operation = match mongo_operation(param.id.as_str()).await {
+        Ok(operation) => operation,
+        Err(err) => return Ok(Response::from_error(err)),
+    };
+    Ok(operation
+        .aggregate(
+            param.database.as_str(),
+            param.collection.as_str(),
+            &param.options,
+        )
+        .await)
+}
+
+#[tauri::command]
+pub async fn mongo_explain_aggregate(
+    param: MongoAggregateParam,
+) -> Result<Response<serde_json::Value>, ()> {
+    let operation = match mongo_operation(param.id.as_str()).await {
+        Ok(operation) => operation,
+        Err(err) => return Ok(Response::from_error(err)),
+    };
+    Ok(operation
+        .explain_aggregate(
+            param.database.as_str(),
+            param.collection.as_str(),
+            &param.options,
+            param.verbosity.as_str(),
+        )
+        .await)
+}
+
+#[tauri::command]
+pub async fn mongo_infer_schema(
+    param: MongoSchemaParam,
+) -> Result<Response<MongoSchemaReport>, ()> {
+    let operation = match mongo_operation(param.id.as_str()).await {
+        Ok(operation) => operation,
+        Err(err) => return Ok(Response::from_error(err)),
+    };
+    Ok(operation
+        .infer_schema(
+            param.database.as_str(),
+            param.collection.as_str(),
+            param.sample_size,
+        )
+        .await)
+}
+
+#[tauri::command]
+pub async fn mongo_create_index(param: MongoCreateIndexParam) -> Result<Response<String>, ()> {
+    let operation = match mongo_operation(param.id.as_str()).await {
+        Ok(operation) => operation,
+        Err(err) => return Ok(Response::from_error(err)),
+    };
+    Ok(operation
+        .create_index(
+            param.database.as_str(),
+            param.collection.as_str(),
+            &param.options,
+        )
+        .await)
+}
+
+#[tauri::command]
+pub async fn mongo_drop_index(param: MongoDropIndexParam) -> Result<Response<bool>, ()> {
+    let operation = match mongo_operation(param.id.as_str()).await {
+        Ok(operation) => operation,
+        Err(err) => return Ok(Response::from_error(err)),
+    };
+    Ok(operation
+        .drop_index(
+            param.database.as_str(),
+            param.collection.as_str(),
+            param.index_name.as_str(),
+        )
+        .await)
+}
+
+#[tauri::command]
+pub async fn mongo_index_stats(
+    param: MongoCollectionParam,
+) -> Result<Response<Vec<MongoIndexStats>>, ()> {
+    let operation = match mongo_operation(param.id.as_str()).await {
+        Ok(operation) => operation,
+        Err(err) => return Ok(Response::from_error(err)),
+    };
+    Ok(operation
+        .index_stats(param.database.as_str(), param.collection.as_str())
+        .await)
+}
+
+#[tauri::command]
+pub async fn mongo_explain_find(
+    param: MongoFindParam,
+) -> Result<Response<MongoExplainSummary>, ()> {
+    let operation = match mongo_operation(param.id.as_str()).await {
+        Ok(operation) => operation,
+        Err(err) => return Ok(Response::from_error(err)),
+    };
+    Ok(operation
+        .explain_find(
+            param.database.as_str(),
+            param.collection.as_str(),
+            &param.options,
+        )
+        .await)
+}
+
+#[tauri::command]
+pub async fn mongo_export_collection(
+    param: MongoExportParam,
+) -> Result<Response<MongoExportResult>, ()> {
+    let operation = match mongo_operation(param.id.as_str()).await {
+        Ok(operation) => operation,
+        Err(err) => return Ok(Response::from_error(err)),
+    };
+    Ok(operation
+        .export_collection(
+            param.database.as_str(),
+            param.collection.as_str(),
+            &param.options,
+            param.file_path.as_str(),
+        )
+        .await)
+}
+
+#[tauri::command]
+pub async fn mongo_import_collection(
+    param: MongoImportParam,
+) -> Result<Response<MongoImportResult>, ()> {
+    let operation = match mongo_operation(param.id.as_str()).await {
+        Ok(operation) => operation,
+        Err(err) => return Ok(Response::from_error(err)),
+    };
+    Ok(operation
+        .import_collection(
+            param.database.as_str(),
+            param.collection.as_str(),
+            &param.options,
+            param.file_path.as_str(),
+        )
+        .await)
+}
+
+#[tauri::command]
+pub async fn mongo_current_op(
+    param: MongoCurrentOpParam,
+) -> Result<Response<Vec<MongoCurrentOp>>, ()> {
+    let operation = match mongo_operation(param.id.as_str()).await {
+        Ok(operation) => operation,
+        Err(err) => return Ok(Response::from_error(err)),
+    };
+    Ok(operation.current_op(&param.filter).await)
+}
+
+#[tauri::command]
+pub async fn mongo_kill_op(param: MongoKillOpParam) -> Result<Response<bool>, ()> {
+    let operation = match mongo_operation(param.id.as_str()).await {
+        Ok(operation) => operation,
+        Err(err) => return Ok(Response::from_error(err)),
+    };
+    Ok(operation.kill_op(param.opid.as_str()).await)
+}
+
+#[tauri::command]
+pub async fn mongo_profiling_status(
+    param: MongoDatabaseParam,
+) -> Result<Response<MongoProfilingStatus>, ()> {
+    let operation = match mongo_operation(param.id.as_str()).await {
+        Ok(operation) => operation,
+        Err(err) => return Ok(Response::from_error(err)),
+    };
+    Ok(operation.profiling_status(param.database.as_str()).await)
+}
+
+#[tauri::command]
+pub async fn mongo_set_profiling_level(
+    param: MongoProfilingParam,
+) -> Result<Response<MongoProfilingStatus>, ()> {
+    let operation = match mongo_operation(param.id.as_str()).await {
+        Ok(operation) => operation,
+        Err(err) => return Ok(Response::from_error(err)),
+    };
+    Ok(operation
+        .set_profiling_level(param.database.as_str(), param.level, param.slowms)
+        .await)
+}
+
+#[tauri::command]
+pub async fn mongo_profile_entries(
+    param: MongoProfileParam,
+) -> Result<Response<Vec<MongoProfileEntry>>, ()> {
+    let operation = match mongo_operation(param.id.as_str()).await {
+        Ok(operation) => operation,
+        Err(err) => return Ok(Response::from_error(err)),
+    };
+    Ok(operation
+        .profile_entries(param.database.as_str(), &param.query)
+        .await)
+}
+
+#[tauri::command]
+pub async fn mongo_list_users(
+    param: MongoListUsersParam,
+) -> Result<Response<Vec<MongoUserInfo>>, ()> {
+    let operation = match mongo_operation(param.id.as_str()).await {
+        Ok(operation) => operation,
+        Err(err) => return Ok(Response::from_error(err)),
+    };
+    Ok(operation
+        .list_users(param.database.as_str(), param.role.as_ref())
+        .await)
+}
+
+#[tauri::command]
+pub async fn mongo_create_user(param: MongoUserParam) -> Result<Response<bool>, ()> {
+    let operation = match mongo_operation(param.id.as_str()).await {
+        Ok(operation) => operation,
+        Err(err) => return Ok(Response::from_error(err)),
+    };
+    Ok(operation
+        .create_user(param.database.as_str(), &param.options)
+        .await)
+}
+
+#[tauri::command]
+pub async fn mongo_update_user(param: MongoUserParam) -> Result<Response<bool>, ()> {
+    let operation = match mongo_operation(param.id.as_str()).await {
+        Ok(operation) => operation,
+        Err(err) => return Ok(Response::from_error(err)),
+    };
+    Ok(operation
+        .update_user(param.database.as_str(), &param.options)
+        .await)
+}
+
+#[tauri::command]
+pub async fn mongo_drop_user(param: MongoDropUserParam) -> Result<Response<bool>, ()> {
+    let operation = match mongo_operation(param.id.as_str()).await {
+        Ok(operation) => operation,
+        Err(err) => return Ok(Response::from_error(err)),
+    };
+    Ok(operation
+        .drop_user(param.database.as_str(), param.user.as_str())
+        .await)
+}
+
+#[tauri::command]
+pub async fn mongo_grant_roles(param: MongoUserRolesParam) -> Result<Response<bool>, ()> {
+    let operation = match mongo_operation(param.id.as_str()).await {
+        Ok(operation) => operation,
+        Err(err) => return Ok(Response::from_error(err)),
+    };
+    Ok(operation
+        .grant_roles(param.database.as_str(), param.user.as_str(), &param.roles)
+        .await)
+}
+
+#[tauri::command]
+pub async fn mongo_revoke_roles(param: MongoUserRolesParam) -> Result<Response<bool>, ()> {
+    let operation = match mongo_operation(param.id.as_str()).await {
+        Ok(operation) => operation,
+        Err(err) => return Ok(Response::from_error(err)),
+    };
+    Ok(operation
+        .revoke_roles(param.database.as_str(), param.user.as_str(), &param.roles)
+        .await)
+}
+
+#[tauri::command]
+pub async fn mongo_list_roles(
+    param: MongoListRolesParam,
+) -> Result<Response<Vec<MongoRoleInfo>>, ()> {
+    let operation = match mongo_operation(param.id.as_str()).await {
+        Ok(operation) => operation,
+        Err(err) => return Ok(Response::from_error(err)),
+    };
+    Ok(operation
+        .list_roles(param.database.as_str(), param.show_builtin)
+        .await)
+}
+
+#[tauri::command]
+pub async fn mongo_create_role(param: MongoRoleParam) -> Result<Response<bool>, ()> {
+    let operation = match mongo_operation(param.id.as_str()).await {
+        Ok(operation) => operation,
+        Err(err) => return Ok(Response::from_error(err)),
+    };
+    Ok(operation
+        .create_role(param.database.as_str(), &param.options)
+        .await)
+}
+
+#[tauri::command]
+pub async fn mongo_update_role(param: MongoRoleParam) -> Result<Response<bool>, ()> {
+    let operation = match mongo_operation(param.id.as_str()).await {
+        Ok(operation) => operation,
+        Err(err) => return Ok(Response::from_error(err)),
+    };
+    Ok(operation
+        .update_role(param.database.as_str(), &param.options)
+        .await)
+}
+
+#[tauri::command]
+pub async fn mongo_drop_role(param: MongoDropRoleParam) -> Result<Response<bool>, ()> {
+    let operation = match mongo_operation(param.id.as_str()).await {
+        Ok(operation) => operation,
+        Err(err) => return Ok(Response::from_error(err)),
+    };
+    Ok(operation
+        .drop_role(param.database.as_str(), param.role.as_str())
+        .await)
+}
+
+#[tauri::command]
+pub async fn mongo_create_collection(param: MongoCollectionParam) -> Result<Response<bool>, ()> {
+    let operation = match mongo_operation(param.id.as_str()).await {
+        Ok(operation) => operation,
+        Err(err) => return Ok(Response::from_error(err)),
+    };
+    Ok(operation
+        .create_collection(param.database.as_str(), param.collection.as_str())
+        .await)
+}
+
+#[tauri::command]
+pub async fn mongo_drop_collection(param: MongoCollectionParam) -> Result<Response<bool>, ()> {
+    let operation = match mongo_operation(param.id.as_str()).await {
+        Ok(operation) => operation,
+        Err(err) => return Ok(Response::from_error(err)),
+    };
+    Ok(operation
+        .drop_collection(param.database.as_str(), param.collection.as_str())
+        .await)
+}
 
-    let option = manager.by_id(param.id.as_str());
-
-    let b = match option {
-        None => Ok(Response::from_error("没有数据")),
-        Some(&ref item) => {
-            let mongo_user_password = MongoUserPassword {
-                username: format!("{}", item.username),
-                password: format!("{}", item.password),
-                host: format!("{}", item.host),
-                port: item.port,
-                name: format!("{}", item.name),
-            };
-            let operation = MongoOperation::new(&mongo_user_password).await.unwrap();
-            let result = operation.db_names().await;
-            Ok(result)
+#[tauri::command]
+pub async fn mongo_drop_database(param: MongoDatabaseParam) -> Result<Response<bool>, ()> {
+    let operation = match mongo_operation(param.id.as_str()).await {
+        Ok(operation) => operation,
+        Err(err) => return Ok(Response::from_error(err)),
+    };
+    Ok(operation.drop_database(param.database.as_str()).await)
+}
+
+#[tauri::command]
+pub async fn mongo_watch_start(
+    window: tauri::Window,
+    state: State<'_, MongoWatchState>,
+    param: MongoWatchParam,
+) -> Result<Response<MongoWatchInfo>, ()> {
+    let operation = match mongo_operation(param.id.as_str()).await {
+        Ok(operation) => operation,
+        Err(err) => return Ok(Response::from_error(err)),
+    };
+    let options = param.options;
+    let resume_token = if options.resume {
+        MongoResumeTokenStorageManager::new()
+            .find(&param.id, &options.database, &options.collection)
+            .map(|entity| entity.token.clone())
+    } else {
+        None
+    };
+    let info = MongoWatchInfo {
+        session_id: Uuid::new_v4().to_string(),
+        config_id: param.id,
+        database: options.database.clone(),
+        collection: options.collection.clone(),
+        full_document: options.full_document,
+        resumed: resume_token.is_some(),
+        started_at: Local::now().timestamp_millis(),
+    };
+
+    let (config_id, database, collection) = (
+        info.config_id.clone(),
+        info.database.clone(),
+        info.collection.clone(),
+    );
+    let mut last_saved = Instant::now();
+    let result = operation
+        .watch(
+            info.session_id.clone(),
+            &options,
+            resume_token,
+            move |event| {
+                if let Some(token) = &event.resume_token {
+                    // 出错结束时总是保存，便于之后从断点继续
+                    if event.error.is_some() || last_saved.elapsed() >= RESUME_TOKEN_SAVE_INTERVAL {
+                        save_resume_token(&config_id, &database, &collection, token.clone());
+                        last_saved = Instant::now();
+                    }
+                }
+                let _ = window.emit(MONGO_CHANGE_EVENT, event);
+            },
+        )
+        .await;
+    match result {
+        Ok(handle) => {
+            state
+                .sessions
+                .lock()
+                .await
+                .insert(info.session_id.clone(), (info.clone(), handle));
+            Ok(Response::new("监听成功", Some(info)))
         }
+        Err(err) => Ok(Response::from_error(err)),
+    }
+}
+
+#[tauri::command]
+pub async fn mongo_watch_stop(
+    state: State<'_, MongoWatchState>,
+    param: MongoWatchStopParam,
+) -> Result<Response<bool>, ()> {
+    let removed = state
+        .sessions
+        .lock()
+        .await
+        .remove(param.session_id.as_str());
+    match removed {
+        None => Ok(Response::from_error("会话不存在")),
+        Some((info, mut handle)) => {
+            if let Some(token) = handle.stop().await {
+                save_resume_token(&info.config_id, &info.database, &info.collection, token);
+            }
+            Ok(Response::new("已停止", Some(true)))
+        }
+    }
+}
+
+#[tauri::command]
+pub async fn mongo_watch_list(
+    state: State<'_, MongoWatchState>,
+    param: GetMongoInfoParam,
+) -> Result<Response<Vec<MongoWatchInfo>>, ()> {
+    let mut sessions = state.sessions.lock().await;
+    // 清理已经异常退出的会话
+    sessions.retain(|_, (_, handle)| handle.is_running());
+    let infos = sessions
+        .values()
+        .filter(|(info, _)| info.config_id == param.id)
+        .map(|(info, _)| info.clone())
+        .collect();
+    Ok(Response::new("success", Some(infos)))
+}
+
+fn save_resume_token(config_id: &str, database: &str, collection: &str, token: serde_json::Value) {
+    MongoResumeTokenStorageManager::new().save(MongoResumeTokenEntity {
+        config_id: config_id.to_string(),
+        database: database.to_string(),
+        collection: collection.to_string(),
+        token,
+        updated_at: 0,
+    });
+}
+
+/// 根据配置 id 创建 MongoOperation
+async fn mongo_operation(id: &str) -> Result<MongoOperation, String> {
+    let manager = MongoStorageManager::new();
+    let item = match manager.by_id(id) {
+        None => return Err("没有数据".to_string()),
+        Some(item) => item,
     };
-    b
+    match MongoOperation::new(&item.to_user_password()).await {
+        Ok(operation) => Ok(operation),
+        Err(err) => Err(format!("Mongo 链接异常: {}", err)),
+    }
 }
 
 #[tauri::command]
@@ -1,17 +1,91 @@
+use std::collections::HashMap;
+use std::sync::Mutex;
+
+use chrono::Local;
+use tauri::State;
+use tokio::sync::Mutex as AsyncMutex;
+use uuid::Uuid;
+
 use crate::cmd::cmd_entity::{
     ChangeRedisListValueParam, ChangeRedisSetValueParam, ChangeRedisZSetValueParam,
-    GetRedisInfoParam, GetRedisKeysParam, GetRedisValueParam, SetRedisHashParam,
-    SetRedisValueParam,
+    GetRedisInfoParam, GetRedisKeysParam, GetRedisValueParam, RedisAclDeleteParam,
+    RedisAclLogParam, RedisAclSaveUserParam, RedisAclUserParam, RedisBgsaveParam,
+    RedisConfigGetParam, RedisConfigSetParam, RedisEvalParam, RedisEvalShaParam, RedisExportParam,
+    RedisFailoverParam, RedisFcallParam, RedisFunctionDeleteParam, RedisFunctionListParam,
+    RedisFunctionLoadParam, RedisImportParam, RedisMigrateParam, RedisMonitorCaptureParam,
+    RedisMonitorParam, RedisPubSubChannelsParam, RedisPublishParam, RedisReplicaOfParam,
+    RedisReplicationParam, RedisScriptExistsParam, RedisScriptIdParam, RedisScriptLoadParam,
+    RedisSessionParam, RedisSubscribeParam, SetRedisHashParam, SetRedisValueParam,
 };
 use crate::config::redis_config::RedisUserPassword;
 use crate::config::redis_config_storage::{
     RedisStorageEntity, RedisStorageManager, RedisStorageService,
 };
+use crate::config::redis_script_storage::{
+    RedisScriptEntity, RedisScriptStorageManager, RedisScriptStorageService,
+};
+use crate::op::redis_entity::{
+    AclLogEntry, AclUser, MonitorEntry, PubSubChannelCount, RedisConfigGroup, RedisConfigItem,
+    RedisExportResult, RedisFunctionLibrary, RedisImportResult, RedisListenHandle,
+    RedisMigrateResult, RedisPersistenceStatus, RedisReplicationStatus, RedisReply,
+    RedisSessionInfo, ScriptExistence,
+};
 use crate::op::redis_op::{
     HashData, ListData, RedisInfo, RedisOperation, ScanKeyResult, SetData, ZSetData,
 };
 use crate::resp::resp::Response;
 
+/// Pub/Sub 消息事件名，订阅中断时推送 kind 为 closed 的事件
+pub static REDIS_PUBSUB_EVENT: &str = "redis-pubsub-message";
+/// MONITOR 命令事件名，连接中断时推送 kind 为 closed 的事件
+pub static REDIS_MONITOR_EVENT: &str = "redis-monitor-event";
+
+/// 后台监听会话，由 tauri 托管
+#[derive(Default)]
+pub struct RedisSessionState {
+    pub sessions: Mutex<HashMap<String, (RedisSessionInfo, RedisListenHandle)>>,
+}
+
+/// 按连接配置 id 缓存的 RedisOperation，由 tauri 托管
+///
+/// 同一个连接的所有请求共享 RedisOperation 内的多路复用连接，缓存同时记录创建时的连接参数，
+/// 连接配置被修改或删除后不再复用旧连接
+#[derive(Default)]
+pub struct RedisConnectionState {
+    operations: AsyncMutex<HashMap<String, (RedisUserPassword, RedisOperation)>>,
+}
+
+impl RedisConnectionState {
+    pub async fn operation(&self, id: &str) -> Result<RedisOperation, String> {
+        let manager = RedisStorageManager::new();
+        let mut operations = self.operations.lock().await;
+        let entity = match manager.by_id(id) {
+            Some(entity) => entity,
+            None => {
+                operations.remove(id);
+                return Err("没有数据".to_string());
+            }
+        };
+        let redis_config = RedisUserPassword {
+            username: format!("{}", entity.username),
+            password: format!("{}", entity.password),
+            host: format!("{}", entity.host),
+            port: entity.port,
+            name: format!("{}", entity.name),
+        };
+        if let Some((config, operation)) = operations.get(id) {
+            if *config == redis_config {
+                return Ok(operation.clone());
+            }
+        }
+
+        let operation =
+            RedisOperation::new(&redis_config).map_err(|e| format!("Redis 链接异常: {}", e))?;
+        operations.insert(id.to_string(), (redis_config, operation.clone()));
+        Ok(operation)
+    }
+}
+
 #[tauri::command]
 pub fn query_all_redis() -> Response<Vec<RedisStorageEntity>> {
     let manager = RedisStorageManager::new();
@@ -26,167 +100,335 @@ pub fn add_redis_config(param: RedisUserPassword) -> Response<bool> {
 }
 
 #[tauri::command]
-pub fn redis_info(param: GetRedisInfoParam) -> Response<RedisInfo> {
-    let manager = RedisStorageManager::new();
-    let option = manager.by_id(param.id.as_str());
+pub async fn redis_info(
+    state: State<'_, RedisConnectionState>,
+    param: GetRedisInfoParam,
+) -> Result<Response<RedisInfo>, ()> {
+    let operation = match state.operation(param.id.as_str()).await {
+        Ok(operation) => operation,
+        Err(err) => return Ok(Response::from_error(err)),
+    };
+    Ok(operation.get_server_info().await)
+}
 
-    match option {
-        None => Response::from_error("没有数据"),
-        Some(&ref entity) => {
-            let redis_config = RedisUserPassword {
-                username: format!("{}", entity.username),
-                password: format!("{}", entity.password),
-                host: format!("{}", entity.host),
-                port: entity.port,
-                name: format!("{}", entity.name),
-            };
-            let redis_operation = RedisOperation::new(&redis_config).unwrap();
-            redis_operation.get_server_info()
-        }
-    }
+#[tauri::command]
+pub async fn redis_db_count(
+    state: State<'_, RedisConnectionState>,
+    param: GetRedisInfoParam,
+) -> Result<Response<i32>, ()> {
+    let operation = match state.operation(param.id.as_str()).await {
+        Ok(operation) => operation,
+        Err(err) => return Ok(Response::from_error(err)),
+    };
+    Ok(operation.get_db_size().await)
 }
 
 #[tauri::command]
-pub fn redis_db_count(param: GetRedisInfoParam) -> Response<i32> {
-    let manager = RedisStorageManager::new();
-    let option = manager.by_id(param.id.as_str());
+pub async fn redis_keys_page(
+    state: State<'_, RedisConnectionState>,
+    param: GetRedisKeysParam,
+) -> Result<Response<ScanKeyResult>, ()> {
+    let operation = match state.operation(param.id.as_str()).await {
+        Ok(operation) => operation,
+        Err(err) => return Ok(Response::from_error(err)),
+    };
+    let result = operation
+        .get_keys_page(param.db_index, param.page, param.page_size)
+        .await;
 
-    match option {
-        None => Response::from_error("没有数据"),
-        Some(&ref entity) => {
-            let redis_config = RedisUserPassword {
-                username: format!("{}", entity.username),
-                password: format!("{}", entity.password),
-                host: format!("{}", entity.host),
-                port: entity.port,
-                name: format!("{}", entity.name),
-            };
-            let redis_operation = RedisOperation::new(&redis_config).unwrap();
-            redis_operation.get_db_size()
-        }
-    }
+    return Ok(result);
 }
 
 #[tauri::command]
-pub fn redis_keys_page(param: GetRedisKeysParam) -> Response<ScanKeyResult> {
-    let manager = RedisStorageManager::new();
-    let option = manager.by_id(param.id.as_str());
+pub async fn redis_get_string_data(
+    state: State<'_, RedisConnectionState>,
+    param: GetRedisValueParam,
+) -> Result<Response<String>, ()> {
+    let operation = match state.operation(param.db_config_id.as_str()).await {
+        Ok(operation) => operation,
+        Err(err) => return Ok(Response::from_error(err)),
+    };
+    let result = operation
+        .get_string_data(param.db_index, param.key_name.to_string())
+        .await;
 
-    match option {
-        None => Response::from_error("没有数据"),
-        Some(&ref entity) => {
-            let redis_config = RedisUserPassword {
-                username: format!("{}", entity.username),
-                password: format!("{}", entity.password),
-                host: format!("{}", entity.host),
-                port: entity.port,
-                name: format!("{}", entity.name),
-            };
-            let operation = RedisOperation::new(&redis_config).unwrap();
-            let result = operation.get_keys_page(param.db_index, param.page, param.page_size);
+    return Ok(result);
+}
 
-            return result;
-        }
-    }
+#[tauri::command]
+pub async fn redis_set_string_data(
+    state: State<'_, RedisConnectionState>,
+    param: SetRedisValueParam,
+) -> Result<Response<bool>, ()> {
+    let operation = match state.operation(param.db_config_id.as_str()).await {
+        Ok(operation) => operation,
+        Err(err) => return Ok(Response::from_error(err)),
+    };
+    let result = operation
+        .set_string_data(param.db_index, param.key_name.to_string(), param.value)
+        .await;
+
+    return Ok(result);
 }
 
 #[tauri::command]
-pub fn redis_get_string_data(param: GetRedisValueParam) -> Response<String> {
-    let manager = RedisStorageManager::new();
-    let option = manager.by_id(param.db_config_id.as_str());
+pub async fn redis_get_list_data(
+    state: State<'_, RedisConnectionState>,
+    param: GetRedisValueParam,
+) -> Result<Response<ListData>, ()> {
+    let operation = match state.operation(param.db_config_id.as_str()).await {
+        Ok(operation) => operation,
+        Err(err) => return Ok(Response::from_error(err)),
+    };
+    let result = operation
+        .get_list_data(param.db_index, param.key_name.to_string(), 0, 0)
+        .await;
 
-    match option {
-        None => Response::from_error("没有数据"),
-        Some(&ref entity) => {
-            let redis_config = RedisUserPassword {
-                username: format!("{}", entity.username),
-                password: format!("{}", entity.password),
-                host: format!("{}", entity.host),
-                port: entity.port,
-                name: format!("{}", entity.name),
-            };
-            let operation = RedisOperation::new(&redis_config).unwrap();
-            let result = operation.get_string_data(param.db_index, param.key_name.to_string());
+    return Ok(result);
+}
 
-            return result;
-        }
-    }
+#[tauri::command]
+pub async fn redis_get_set_data(
+    state: State<'_, RedisConnectionState>,
+    param: GetRedisValueParam,
+) -> Result<Response<SetData>, ()> {
+    let operation = match state.operation(param.db_config_id.as_str()).await {
+        Ok(operation) => operation,
+        Err(err) => return Ok(Response::from_error(err)),
+    };
+    let result = operation
+        .get_set_data(param.db_index, param.key_name.to_string())
+        .await;
+
+    return Ok(result);
 }
 
 #[tauri::command]
-pub fn redis_set_string_data(param: SetRedisValueParam) -> Response<bool> {
-    let manager = RedisStorageManager::new();
-    let option = manager.by_id(param.db_config_id.as_str());
+pub async fn redis_get_hash_data(
+    state: State<'_, RedisConnectionState>,
+    param: GetRedisValueParam,
+) -> Result<Response<HashData>, ()> {
+    let operation = match state.operation(param.db_config_id.as_str()).await {
+        Ok(operation) => operation,
+        Err(err) => return Ok(Response::from_error(err)),
+    };
+    let result = operation
+        .get_hash_data(param.db_index, param.key_name.to_string())
+        .await;
 
-    match option {
-        None => Response::from_error("没有数据"),
-        Some(&ref entity) => {
-            let redis_config = RedisUserPassword {
-                username: format!("{}", entity.username),
-                password: format!("{}", entity.password),
-                host: format!("{}", entity.host),
-                port: entity.port,
-                name: format!("{}", entity.name),
-            };
-            let operation = RedisOperation::new(&redis_config).unwrap();
-            let result =
-                operation.set_string_data(param.db_index, param.key_name.to_string(), param.value);
+    return Ok(result);
+}
 
-            return result;
-        }
+#[tauri::command]
+pub async fn redis_change_hash(
+    state: State<'_, RedisConnectionState>,
+    param: SetRedisHashParam,
+) -> Result<Response<String>, ()> {
+    let operation = match state.operation(param.db_config_id.as_str()).await {
+        Ok(operation) => operation,
+        Err(err) => return Ok(Response::from_error(err)),
+    };
+
+    for x in param.old_field_values {
+        operation
+            .remove_hash_hk_data(param.db_index, param.key_name.to_string(), x.0)
+            .await;
     }
+
+    let result = operation
+        .set_hash_data(
+            param.db_index,
+            param.key_name.to_string(),
+            param.new_field_values,
+        )
+        .await;
+
+    return Ok(result);
+}
+#[tauri::command]
+pub async fn redis_remove_hash_member(
+    state: State<'_, RedisConnectionState>,
+    param: SetRedisHashParam,
+) -> Result<Response<bool>, ()> {
+    let operation = match state.operation(param.db_config_id.as_str()).await {
+        Ok(operation) => operation,
+        Err(err) => return Ok(Response::from_error(err)),
+    };
+
+    for x in param.old_field_values {
+        operation
+            .remove_hash_hk_data(param.db_index, param.key_name.to_string(), x.0)
+            .await;
+    }
+
+    return Ok(Response::new("ok", Some(true)));
 }
 
 #[tauri::command]
-pub fn redis_get_list_data(param: GetRedisValueParam) -> Response<ListData> {
-    let manager = RedisStorageManager::new();
-    let option = manager.by_id(param.db_config_id.as_str());
+pub async fn redis_get_zset_data(
+    state: State<'_, RedisConnectionState>,
+    param: GetRedisValueParam,
+) -> Result<Response<ZSetData>, ()> {
+    let operation = match state.operation(param.db_config_id.as_str()).await {
+        Ok(operation) => operation,
+        Err(err) => return Ok(Response::from_error(err)),
+    };
+    let result = operation
+        .get_zset_data(param.db_index, param.key_name.to_string(), 0, 0)
+        .await;
 
-    match option {
-        None => Response::from_error("没有数据"),
-        Some(&ref entity) => {
-            let redis_config = RedisUserPassword {
-                username: format!("{}", entity.username),
-                password: format!("{}", entity.password),
-                host: format!("{}", entity.host),
-                port: entity.port,
-                name: format!("{}", entity.name),
-            };
-            let operation = RedisOperation::new(&redis_config).unwrap();
-            let result = operation.get_list_data(param.db_index, param.key_name.to_string(), 0, 0);
+    return Ok(result);
+}
 
-            return result;
-        }
+#[tauri::command]
+pub async fn redis_delete_redis_key(
+    state: State<'_, RedisConnectionState>,
+    param: GetRedisValueParam,
+) -> Result<Response<bool>, ()> {
+    let operation = match state.operation(param.db_config_id.as_str()).await {
+        Ok(operation) => operation,
+        Err(err) => return Ok(Response::from_error(err)),
+    };
+    let result = operation
+        .delete_redis_key(param.db_index, param.key_name.to_string())
+        .await;
+
+    return Ok(result);
+}
+
+#[tauri::command]
+pub async fn redis_set_redis_key_expire(
+    state: State<'_, RedisConnectionState>,
+    param: GetRedisValueParam,
+) -> Result<Response<bool>, ()> {
+    let operation = match state.operation(param.db_config_id.as_str()).await {
+        Ok(operation) => operation,
+        Err(err) => return Ok(Response::from_error(err)),
+    };
+    if param.expiration_seconds > 0 {
+        return Ok(operation
+            .set_redis_key_expire(
+                param.db_index,
+                param.key_name,
+                param.expiration_seconds as usize,
+            )
+            .await);
+    } else {
+        return Ok(operation
+            .persist_redis_key(param.db_index, param.key_name.as_str())
+            .await);
     }
 }
 
 #[tauri::command]
-pub fn redis_get_set_data(param: GetRedisValueParam) -> Response<SetData> {
-    let manager = RedisStorageManager::new();
-    let option = manager.by_id(param.db_config_id.as_str());
+pub async fn redis_change_set(
+    state: State<'_, RedisConnectionState>,
+    param: ChangeRedisSetValueParam,
+) -> Result<Response<bool>, ()> {
+    let operation = match state.operation(param.db_config_id.as_str()).await {
+        Ok(operation) => operation,
+        Err(err) => return Ok(Response::from_error(err)),
+    };
+    Ok(operation
+        .change_set(param.db_index, param.key_name, param.old, param.new_val)
+        .await)
+}
 
-    match option {
-        None => Response::from_error("没有数据"),
-        Some(&ref entity) => {
-            let redis_config = RedisUserPassword {
-                username: format!("{}", entity.username),
-                password: format!("{}", entity.password),
-                host: format!("{}", entity.host),
-                port: entity.port,
-                name: format!("{}", entity.name),
-            };
-            let operation = RedisOperation::new(&redis_config).unwrap();
-            let result = operation.get_set_data(param.db_index, param.key_name.to_string());
+#[tauri::command]
+pub async fn redis_add_set(
+    state: State<'_, RedisConnectionState>,
+    param: ChangeRedisSetValueParam,
+) -> Result<Response<usize>, ()> {
+    let operation = match state.operation(param.db_config_id.as_str()).await {
+        Ok(operation) => operation,
+        Err(err) => return Ok(Response::from_error(err)),
+    };
+    let alues = vec![param.new_val];
+    Ok(operation
+        .set_set_data(param.db_index, param.key_name, alues)
+        .await)
+}
 
-            return result;
-        }
+#[tauri::command]
+pub async fn redis_remove_set_member(
+    state: State<'_, RedisConnectionState>,
+    param: ChangeRedisSetValueParam,
+) -> Result<Response<bool>, ()> {
+    let operation = match state.operation(param.db_config_id.as_str()).await {
+        Ok(operation) => operation,
+        Err(err) => return Ok(Response::from_error(err)),
+    };
+    Ok(operation
+        .remove_set_value(param.db_index, param.key_name, param.old)
+        .await)
+}
+
+#[tauri::command]
+pub async fn redis_change_list(
+    state: State<'_, RedisConnectionState>,
+    param: ChangeRedisListValueParam,
+) -> Result<Response<bool>, ()> {
+    let operation = match state.operation(param.db_config_id.as_str()).await {
+        Ok(operation) => operation,
+        Err(err) => return Ok(Response::from_error(err)),
+    };
+    Ok(operation
+        .change_list(param.db_index, param.key_name, param.old, param.new_val)
+        .await)
+}
+
+#[tauri::command]
+pub async fn redis_remove_list_member(
+    state: State<'_, RedisConnectionState>,
+    param: ChangeRedisListValueParam,
+) -> Result<Response<bool>, ()> {
+    let operation = match state.operation(param.db_config_id.as_str()).await {
+        Ok(operation) => operation,
+        Err(err) => return Ok(Response::from_error(err)),
+    };
+    Ok(operation
+        .remove_list_value(param.db_index, param.key_name, param.old)
+        .await)
+}
+
+#[tauri::command]
+pub async fn redis_change_zset(
+    state: State<'_, RedisConnectionState>,
+    param: ChangeRedisZSetValueParam,
+) -> Result<Response<usize>, ()> {
+    let operation = match state.operation(param.db_config_id.as_str()).await {
+        Ok(operation) => operation,
+        Err(err) => return Ok(Response::from_error(err)),
+    };
+    Ok(operation
+        .set_zset_data(param.db_index, param.key_name, param.members_scores)
+        .await)
+}
+#[tauri::command]
+pub async fn redis_remove_zset_member(
+    state: State<'_, RedisConnectionState>,
+    param: ChangeRedisZSetValueParam,
+) -> Result<Response<bool>, ()> {
+    let operation = match state.operation(param.db_config_id.as_str()).await {
+        Ok(operation) => operation,
+        Err(err) => return Ok(Response::from_error(err)),
+    };
+    for x in param.members_scores {
+        operation
+            .remove_member_from_zset(param.db_index, param.key_name.to_string(), x.0)
+            .await;
     }
+    Ok(Response::new("ok", Some(true)))
 }
 
 #[tauri::command]
-pub fn redis_get_hash_data(param: GetRedisValueParam) -> Response<HashData> {
+pub fn redis_pubsub_subscribe(
+    window: tauri::Window,
+    state: tauri::State<'_, RedisSessionState>,
+    param: RedisSubscribeParam,
+) -> Response<RedisSessionInfo> {
     let manager = RedisStorageManager::new();
-    let option = manager.by_id(param.db_config_id.as_str());
+    let option = manager.by_id(param.id.as_str());
 
     match option {
         None => Response::from_error("没有数据"),
@@ -199,48 +441,128 @@ pub fn redis_get_hash_data(param: GetRedisValueParam) -> Response<HashData> {
                 name: format!("{}", entity.name),
             };
             let operation = RedisOperation::new(&redis_config).unwrap();
-            let result = operation.get_hash_data(param.db_index, param.key_name.to_string());
+            let info = RedisSessionInfo {
+                session_id: Uuid::new_v4().to_string(),
+                config_id: param.id,
+                kind: "pubsub".to_string(),
+                channels: param.channels,
+                patterns: param.patterns,
+                started_at: Local::now().timestamp_millis(),
+            };
+            let result = operation.subscribe(
+                info.session_id.clone(),
+                info.channels.clone(),
+                info.patterns.clone(),
+                move |message| {
+                    let _ = window.emit(REDIS_PUBSUB_EVENT, message);
+                },
+            );
+            match result {
+                Ok(handle) => {
+                    state
+                        .sessions
+                        .lock()
+                        .unwrap()
+                        .insert(info.session_id.clone(), (info.clone(), handle));
+                    Response::new("订阅成功", Some(info))
+                }
+                Err(err) => Response::from_error(err),
+            }
+        }
+    }
+}
 
-            return result;
+#[tauri::command]
+pub fn redis_stop_session(
+    state: tauri::State<'_, RedisSessionState>,
+    param: RedisSessionParam,
+) -> Response<bool> {
+    let removed = state
+        .sessions
+        .lock()
+        .unwrap()
+        .remove(param.session_id.as_str());
+    match removed {
+        None => Response::from_error("会话不存在"),
+        Some((_, mut handle)) => {
+            handle.stop();
+            Response::new("已停止", Some(true))
         }
     }
 }
 
 #[tauri::command]
-pub fn redis_change_hash(param: SetRedisHashParam) -> Response<String> {
-    let manager = RedisStorageManager::new();
-    let option = manager.by_id(param.db_config_id.as_str());
+pub fn redis_list_sessions(
+    state: tauri::State<'_, RedisSessionState>,
+    param: GetRedisInfoParam,
+) -> Response<Vec<RedisSessionInfo>> {
+    let mut sessions = state.sessions.lock().unwrap();
+    // 清理已经异常退出的会话
+    sessions.retain(|_, (_, handle)| handle.is_running());
+    let infos = sessions
+        .values()
+        .filter(|(info, _)| info.config_id == param.id)
+        .map(|(info, _)| info.clone())
+        .collect();
+    Response::new("success", Some(infos))
+}
 
-    match option {
-        None => Response::from_error("没有数据"),
-        Some(&ref entity) => {
-            let redis_config = RedisUserPassword {
-                username: format!("{}", entity.username),
-                password: format!("{}", entity.password),
-                host: format!("{}", entity.host),
-                port: entity.port,
-                name: format!("{}", entity.name),
-            };
-            let operation = RedisOperation::new(&redis_config).unwrap();
+#[tauri::command]
+pub async fn redis_publish(
+    state: State<'_, RedisConnectionState>,
+    param: RedisPublishParam,
+) -> Result<Response<i64>, ()> {
+    let operation = match state.operation(param.id.as_str()).await {
+        Ok(operation) => operation,
+        Err(err) => return Ok(Response::from_error(err)),
+    };
+    Ok(operation.publish(param.channel, param.message).await)
+}
 
-            for x in param.old_field_values {
-                operation.remove_hash_hk_data(param.db_index, param.key_name.to_string(), x.0);
-            }
+#[tauri::command]
+pub async fn redis_pubsub_channels(
+    state: State<'_, RedisConnectionState>,
+    param: RedisPubSubChannelsParam,
+) -> Result<Response<Vec<String>>, ()> {
+    let operation = match state.operation(param.id.as_str()).await {
+        Ok(operation) => operation,
+        Err(err) => return Ok(Response::from_error(err)),
+    };
+    Ok(operation.pubsub_channels(param.pattern.as_str()).await)
+}
 
-            let result = operation.set_hash_data(
-                param.db_index,
-                param.key_name.to_string(),
-                param.new_field_values,
-            );
+#[tauri::command]
+pub async fn redis_pubsub_numsub(
+    state: State<'_, RedisConnectionState>,
+    param: RedisPubSubChannelsParam,
+) -> Result<Response<Vec<PubSubChannelCount>>, ()> {
+    let operation = match state.operation(param.id.as_str()).await {
+        Ok(operation) => operation,
+        Err(err) => return Ok(Response::from_error(err)),
+    };
+    Ok(operation.pubsub_numsub(param.channels).await)
+}
 
-            return result;
-        }
-    }
+#[tauri::command]
+pub async fn redis_pubsub_numpat(
+    state: State<'_, RedisConnectionState>,
+    param: GetRedisInfoParam,
+) -> Result<Response<i64>, ()> {
+    let operation = match state.operation(param.id.as_str()).await {
+        Ok(operation) => operation,
+        Err(err) => return Ok(Response::from_error(err)),
+    };
+    Ok(operation.pubsub_numpat().await)
 }
+
 #[tauri::command]
-pub fn redis_remove_hash_member(param: SetRedisHashParam) -> Response<bool> {
+pub fn redis_monitor_start(
+    window: tauri::Window,
+    state: tauri::State<'_, RedisSessionState>,
+    param: RedisMonitorParam,
+) -> Response<RedisSessionInfo> {
     let manager = RedisStorageManager::new();
-    let option = manager.by_id(param.db_config_id.as_str());
+    let option = manager.by_id(param.id.as_str());
 
     match option {
         None => Response::from_error("没有数据"),
@@ -253,238 +575,454 @@ pub fn redis_remove_hash_member(param: SetRedisHashParam) -> Response<bool> {
                 name: format!("{}", entity.name),
             };
             let operation = RedisOperation::new(&redis_config).unwrap();
-
-            for x in param.old_field_values {
-                operation.remove_hash_hk_data(param.db_index, param.key_name.to_string(), x.0);
+            let info = RedisSessionInfo {
+                session_id: Uuid::new_v4().to_string(),
+                config_id: param.id,
+                kind: "monitor".to_string(),
+                channels: vec![],
+                patterns: vec![],
+                started_at: Local::now().timestamp_millis(),
+            };
+            let result = operation.monitor(
+                info.session_id.clone(),
+                param.filter,
+                param.capture,
+                param.max_duration_secs,
+                move |entry| {
+                    let _ = window.emit(REDIS_MONITOR_EVENT, entry);
+                },
+            );
+            match result {
+                Ok(handle) => {
+                    state
+                        .sessions
+                        .lock()
+                        .unwrap()
+                        .insert(info.session_id.clone(), (info.clone(), handle));
+                    Response::new("开始监控", Some(info))
+                }
+                Err(err) => Response::from_error(err),
             }
-
-            return Response::new("ok", Some(true));
         }
     }
 }
 
 #[tauri::command]
-pub fn redis_get_zset_data(param: GetRedisValueParam) -> Response<ZSetData> {
-    let manager = RedisStorageManager::new();
-    let option = manager.by_id(param.db_config_id.as_str());
+pub fn redis_read_monitor_capture(param: RedisMonitorCaptureParam) -> Response<Vec<MonitorEntry>> {
+    RedisOperation::read_monitor_capture(param.file_path.as_str())
+}
 
-    match option {
-        None => Response::from_error("没有数据"),
-        Some(&ref entity) => {
-            let redis_config = RedisUserPassword {
-                username: format!("{}", entity.username),
-                password: format!("{}", entity.password),
-                host: format!("{}", entity.host),
-                port: entity.port,
-                name: format!("{}", entity.name),
-            };
-            let operation = RedisOperation::new(&redis_config).unwrap();
-            let result = operation.get_zset_data(param.db_index, param.key_name.to_string(), 0, 0);
+#[tauri::command]
+pub async fn redis_export_keys(
+    state: State<'_, RedisConnectionState>,
+    param: RedisExportParam,
+) -> Result<Response<RedisExportResult>, ()> {
+    let operation = match state.operation(param.id.as_str()).await {
+        Ok(operation) => operation,
+        Err(err) => return Ok(Response::from_error(err)),
+    };
+    Ok(operation
+        .export_keys(
+            param.db_index,
+            param.pattern.as_str(),
+            param.format,
+            param.file_path.as_str(),
+        )
+        .await)
+}
 
-            return result;
-        }
-    }
+#[tauri::command]
+pub async fn redis_import_keys(
+    state: State<'_, RedisConnectionState>,
+    param: RedisImportParam,
+) -> Result<Response<RedisImportResult>, ()> {
+    let operation = match state.operation(param.id.as_str()).await {
+        Ok(operation) => operation,
+        Err(err) => return Ok(Response::from_error(err)),
+    };
+    Ok(operation
+        .import_keys(
+            param.db_index,
+            param.format,
+            param.file_path.as_str(),
+            param.policy,
+            param.rename_suffix.as_str(),
+        )
+        .await)
 }
 
 #[tauri::command]
-pub fn redis_delete_redis_key(param: GetRedisValueParam) -> Response<bool> {
-    let manager = RedisStorageManager::new();
-    let option = manager.by_id(param.db_config_id.as_str());
+pub async fn redis_migrate_keys(
+    state: State<'_, RedisConnectionState>,
+    param: RedisMigrateParam,
+) -> Result<Response<RedisMigrateResult>, ()> {
+    let source = match state.operation(param.source_id.as_str()).await {
+        Ok(operation) => operation,
+        Err(err) => return Ok(Response::from_error(err)),
+    };
+    let target = match state.operation(param.target_id.as_str()).await {
+        Ok(operation) => operation,
+        Err(err) => return Ok(Response::from_error(err)),
+    };
+    Ok(source
+        .migrate_keys(
+            &target,
+            param.source_db,
+            param.target_db,
+            param.pattern.as_str(),
+            param.options,
+        )
+        .await)
+}
 
-    match option {
-        None => Response::from_error("没有数据"),
-        Some(&ref entity) => {
-            let redis_config = RedisUserPassword {
-                username: format!("{}", entity.username),
-                password: format!("{}", entity.password),
-                host: format!("{}", entity.host),
-                port: entity.port,
-                name: format!("{}", entity.name),
-            };
-            let operation = RedisOperation::new(&redis_config).unwrap();
-            let result = operation.delete_redis_key(param.db_index, param.key_name.to_string());
+#[tauri::command]
+pub async fn redis_eval(
+    state: State<'_, RedisConnectionState>,
+    param: RedisEvalParam,
+) -> Result<Response<RedisReply>, ()> {
+    let operation = match state.operation(param.id.as_str()).await {
+        Ok(operation) => operation,
+        Err(err) => return Ok(Response::from_error(err)),
+    };
+    Ok(operation
+        .eval_script(
+            param.db_index,
+            param.script.as_str(),
+            param.keys,
+            param.args,
+        )
+        .await)
+}
 
-            return result;
-        }
-    }
+#[tauri::command]
+pub async fn redis_evalsha(
+    state: State<'_, RedisConnectionState>,
+    param: RedisEvalShaParam,
+) -> Result<Response<RedisReply>, ()> {
+    let operation = match state.operation(param.id.as_str()).await {
+        Ok(operation) => operation,
+        Err(err) => return Ok(Response::from_error(err)),
+    };
+    Ok(operation
+        .evalsha(param.db_index, param.sha.as_str(), param.keys, param.args)
+        .await)
 }
 
 #[tauri::command]
-pub fn redis_set_redis_key_expire(param: GetRedisValueParam) -> Response<bool> {
-    let manager = RedisStorageManager::new();
-    let option = manager.by_id(param.db_config_id.as_str());
+pub async fn redis_script_load(
+    state: State<'_, RedisConnectionState>,
+    param: RedisScriptLoadParam,
+) -> Result<Response<String>, ()> {
+    let operation = match state.operation(param.id.as_str()).await {
+        Ok(operation) => operation,
+        Err(err) => return Ok(Response::from_error(err)),
+    };
+    Ok(operation.script_load(param.script.as_str()).await)
+}
 
-    match option {
-        None => Response::from_error("没有数据"),
-        Some(&ref entity) => {
-            let redis_config = RedisUserPassword {
-                username: format!("{}", entity.username),
-                password: format!("{}", entity.password),
-                host: format!("{}", entity.host),
-                port: entity.port,
-                name: format!("{}", entity.name),
-            };
-            let operation = RedisOperation::new(&redis_config).unwrap();
-            if param.expiration_seconds > 0 {
-                return operation.set_redis_key_expire(
-                    param.db_index,
-                    param.key_name,
-                    param.expiration_seconds as usize,
-                );
-            } else {
-                return operation.persist_redis_key(param.db_index, param.key_name.as_str());
-            }
-        }
-    }
+#[tauri::command]
+pub async fn redis_script_exists(
+    state: State<'_, RedisConnectionState>,
+    param: RedisScriptExistsParam,
+) -> Result<Response<Vec<ScriptExistence>>, ()> {
+    let operation = match state.operation(param.id.as_str()).await {
+        Ok(operation) => operation,
+        Err(err) => return Ok(Response::from_error(err)),
+    };
+    Ok(operation.script_exists(param.shas).await)
 }
 
 #[tauri::command]
-pub fn redis_change_set(param: ChangeRedisSetValueParam) -> Response<bool> {
-    let manager = RedisStorageManager::new();
-    let option = manager.by_id(param.db_config_id.as_str());
+pub async fn redis_script_flush(
+    state: State<'_, RedisConnectionState>,
+    param: GetRedisInfoParam,
+) -> Result<Response<bool>, ()> {
+    let operation = match state.operation(param.id.as_str()).await {
+        Ok(operation) => operation,
+        Err(err) => return Ok(Response::from_error(err)),
+    };
+    Ok(operation.script_flush().await)
+}
 
-    match option {
-        None => Response::from_error("没有数据"),
-        Some(&ref entity) => {
-            let redis_config = RedisUserPassword {
-                username: format!("{}", entity.username),
-                password: format!("{}", entity.password),
-                host: format!("{}", entity.host),
-                port: entity.port,
-                name: format!("{}", entity.name),
-            };
-            let operation = RedisOperation::new(&redis_config).unwrap();
-            operation.change_set(param.db_index, param.key_name, param.old, param.new_val)
-        }
-    }
+#[tauri::command]
+pub async fn redis_function_list(
+    state: State<'_, RedisConnectionState>,
+    param: RedisFunctionListParam,
+) -> Result<Response<Vec<RedisFunctionLibrary>>, ()> {
+    let operation = match state.operation(param.id.as_str()).await {
+        Ok(operation) => operation,
+        Err(err) => return Ok(Response::from_error(err)),
+    };
+    Ok(operation
+        .function_list(param.library_pattern.as_str(), param.with_code)
+        .await)
 }
 
 #[tauri::command]
-pub fn redis_add_set(param: ChangeRedisSetValueParam) -> Response<usize> {
-    let manager = RedisStorageManager::new();
-    let option = manager.by_id(param.db_config_id.as_str());
+pub async fn redis_function_load(
+    state: State<'_, RedisConnectionState>,
+    param: RedisFunctionLoadParam,
+) -> Result<Response<String>, ()> {
+    let operation = match state.operation(param.id.as_str()).await {
+        Ok(operation) => operation,
+        Err(err) => return Ok(Response::from_error(err)),
+    };
+    Ok(operation
+        .function_load(param.code.as_str(), param.replace)
+        .await)
+}
 
-    match option {
-        None => Response::from_error("没有数据"),
-        Some(&ref entity) => {
-            let redis_config = RedisUserPassword {
-                username: format!("{}", entity.username),
-                password: format!("{}", entity.password),
-                host: format!("{}", entity.host),
-                port: entity.port,
-                name: format!("{}", entity.name),
-            };
-            let operation = RedisOperation::new(&redis_config).unwrap();
-            let alues = vec![param.new_val];
-            operation.set_set_data(param.db_index, param.key_name, alues)
-        }
-    }
+#[tauri::command]
+pub async fn redis_function_delete(
+    state: State<'_, RedisConnectionState>,
+    param: RedisFunctionDeleteParam,
+) -> Result<Response<bool>, ()> {
+    let operation = match state.operation(param.id.as_str()).await {
+        Ok(operation) => operation,
+        Err(err) => return Ok(Response::from_error(err)),
+    };
+    Ok(operation.function_delete(param.library_name.as_str()).await)
 }
 
 #[tauri::command]
-pub fn redis_remove_set_member(param: ChangeRedisSetValueParam) -> Response<bool> {
-    let manager = RedisStorageManager::new();
-    let option = manager.by_id(param.db_config_id.as_str());
+pub async fn redis_fcall(
+    state: State<'_, RedisConnectionState>,
+    param: RedisFcallParam,
+) -> Result<Response<RedisReply>, ()> {
+    let operation = match state.operation(param.id.as_str()).await {
+        Ok(operation) => operation,
+        Err(err) => return Ok(Response::from_error(err)),
+    };
+    Ok(operation
+        .fcall(
+            param.db_index,
+            param.function.as_str(),
+            param.keys,
+            param.args,
+            param.read_only,
+        )
+        .await)
+}
 
-    match option {
-        None => Response::from_error("没有数据"),
-        Some(&ref entity) => {
-            let redis_config = RedisUserPassword {
-                username: format!("{}", entity.username),
-                password: format!("{}", entity.password),
-                host: format!("{}", entity.host),
-                port: entity.port,
-                name: format!("{}", entity.name),
-            };
-            let operation = RedisOperation::new(&redis_config).unwrap();
-            operation.remove_set_value(param.db_index, param.key_name, param.old)
-        }
-    }
+/// 查询连接下保存的脚本
+#[tauri::command]
+pub fn redis_query_scripts(param: GetRedisInfoParam) -> Response<Vec<RedisScriptEntity>> {
+    let manager = RedisScriptStorageManager::new();
+    let scripts = manager
+        .by_config_id(param.id.as_str())
+        .into_iter()
+        .cloned()
+        .collect();
+    Response::new("success", Some(scripts))
 }
 
+/// 新增或修改脚本
 #[tauri::command]
-pub fn redis_change_list(param: ChangeRedisListValueParam) -> Response<bool> {
-    let manager = RedisStorageManager::new();
-    let option = manager.by_id(param.db_config_id.as_str());
+pub fn redis_save_script(param: RedisScriptEntity) -> Response<RedisScriptEntity> {
+    let mut manager = RedisScriptStorageManager::new();
+    let script = manager.save(param);
+    Response::new("保存成功", Some(script))
+}
 
-    match option {
-        None => Response::from_error("没有数据"),
-        Some(&ref entity) => {
-            let redis_config = RedisUserPassword {
-                username: format!("{}", entity.username),
-                password: format!("{}", entity.password),
-                host: format!("{}", entity.host),
-                port: entity.port,
-                name: format!("{}", entity.name),
-            };
-            let operation = RedisOperation::new(&redis_config).unwrap();
-            operation.change_list(param.db_index, param.key_name, param.old, param.new_val)
-        }
-    }
+#[tauri::command]
+pub fn redis_delete_script(param: RedisScriptIdParam) -> Response<bool> {
+    let mut manager = RedisScriptStorageManager::new();
+    manager.delete(param.id);
+    Response::ok()
 }
 
 #[tauri::command]
-pub fn redis_remove_list_member(param: ChangeRedisListValueParam) -> Response<bool> {
-    let manager = RedisStorageManager::new();
-    let option = manager.by_id(param.db_config_id.as_str());
+pub async fn redis_config_get(
+    state: State<'_, RedisConnectionState>,
+    param: RedisConfigGetParam,
+) -> Result<Response<Vec<RedisConfigGroup>>, ()> {
+    let operation = match state.operation(param.id.as_str()).await {
+        Ok(operation) => operation,
+        Err(err) => return Ok(Response::from_error(err)),
+    };
+    Ok(operation.config_get(param.pattern.as_str()).await)
+}
 
-    match option {
-        None => Response::from_error("没有数据"),
-        Some(&ref entity) => {
-            let redis_config = RedisUserPassword {
-                username: format!("{}", entity.username),
-                password: format!("{}", entity.password),
-                host: format!("{}", entity.host),
-                port: entity.port,
-                name: format!("{}", entity.name),
-            };
-            let operation = RedisOperation::new(&redis_config).unwrap();
-            operation.remove_list_value(param.db_index, param.key_name, param.old)
-        }
-    }
+#[tauri::command]
+pub async fn redis_config_diff(
+    state: State<'_, RedisConnectionState>,
+    param: GetRedisInfoParam,
+) -> Result<Response<Vec<RedisConfigItem>>, ()> {
+    let operation = match state.operation(param.id.as_str()).await {
+        Ok(operation) => operation,
+        Err(err) => return Ok(Response::from_error(err)),
+    };
+    Ok(operation.config_diff().await)
 }
 
 #[tauri::command]
-pub fn redis_change_zset(param: ChangeRedisZSetValueParam) -> Response<usize> {
-    let manager = RedisStorageManager::new();
-    let option = manager.by_id(param.db_config_id.as_str());
-    dbg!(&param);
-    match option {
-        None => Response::from_error("没有数据"),
-        Some(&ref entity) => {
-            let redis_config = RedisUserPassword {
-                username: format!("{}", entity.username),
-                password: format!("{}", entity.password),
-                host: format!("{}", entity.host),
-                port: entity.port,
-                name: format!("{}", entity.name),
-            };
-            let operation = RedisOperation::new(&redis_config).unwrap();
-            operation.set_zset_data(param.db_index, param.key_name, param.members_scores)
-        }
-    }
+pub async fn redis_config_set(
+    state: State<'_, RedisConnectionState>,
+    param: RedisConfigSetParam,
+) -> Result<Response<bool>, ()> {
+    let operation = match state.operation(param.id.as_str()).await {
+        Ok(operation) => operation,
+        Err(err) => return Ok(Response::from_error(err)),
+    };
+    Ok(operation
+        .config_set(param.name.as_str(), param.value.as_str())
+        .await)
 }
+
 #[tauri::command]
-pub fn redis_remove_zset_member(param: ChangeRedisZSetValueParam) -> Response<bool> {
-    let manager = RedisStorageManager::new();
-    let option = manager.by_id(param.db_config_id.as_str());
-    dbg!(&param);
+pub async fn redis_config_rewrite(
+    state: State<'_, RedisConnectionState>,
+    param: GetRedisInfoParam,
+) -> Result<Response<bool>, ()> {
+    let operation = match state.operation(param.id.as_str()).await {
+        Ok(operation) => operation,
+        Err(err) => return Ok(Response::from_error(err)),
+    };
+    Ok(operation.config_rewrite().await)
+}
 
-    match option {
-        None => Response::from_error("没有数据"),
-        Some(&ref entity) => {
-            let redis_config = RedisUserPassword {
-                username: format!("{}", entity.username),
-                password: format!("{}", entity.password),
-                host: format!("{}", entity.host),
-                port: entity.port,
-                name: format!("{}", entity.name),
-            };
-            let operation = RedisOperation::new(&redis_config).unwrap();
-            for x in param.members_scores {
-                operation.remove_member_from_zset(param.db_index, param.key_name.to_string(), x.0);
-            }
-            Response::new("ok", Some(true))
-        }
-    }
+#[tauri::command]
+pub async fn redis_acl_list(
+    state: State<'_, RedisConnectionState>,
+    param: GetRedisInfoParam,
+) -> Result<Response<Vec<AclUser>>, ()> {
+    let operation = match state.operation(param.id.as_str()).await {
+        Ok(operation) => operation,
+        Err(err) => return Ok(Response::from_error(err)),
+    };
+    Ok(operation.acl_list().await)
+}
+
+#[tauri::command]
+pub async fn redis_acl_get_user(
+    state: State<'_, RedisConnectionState>,
+    param: RedisAclUserParam,
+) -> Result<Response<AclUser>, ()> {
+    let operation = match state.operation(param.id.as_str()).await {
+        Ok(operation) => operation,
+        Err(err) => return Ok(Response::from_error(err)),
+    };
+    Ok(operation.acl_get_user(param.username.as_str()).await)
+}
+
+#[tauri::command]
+pub async fn redis_acl_save_user(
+    state: State<'_, RedisConnectionState>,
+    param: RedisAclSaveUserParam,
+) -> Result<Response<bool>, ()> {
+    let operation = match state.operation(param.id.as_str()).await {
+        Ok(operation) => operation,
+        Err(err) => return Ok(Response::from_error(err)),
+    };
+    Ok(operation
+        .acl_save_user(&param.user, param.new_passwords)
+        .await)
+}
+
+#[tauri::command]
+pub async fn redis_acl_delete_users(
+    state: State<'_, RedisConnectionState>,
+    param: RedisAclDeleteParam,
+) -> Result<Response<i64>, ()> {
+    let operation = match state.operation(param.id.as_str()).await {
+        Ok(operation) => operation,
+        Err(err) => return Ok(Response::from_error(err)),
+    };
+    Ok(operation.acl_delete_users(param.usernames).await)
+}
+
+#[tauri::command]
+pub async fn redis_acl_log(
+    state: State<'_, RedisConnectionState>,
+    param: RedisAclLogParam,
+) -> Result<Response<Vec<AclLogEntry>>, ()> {
+    let operation = match state.operation(param.id.as_str()).await {
+        Ok(operation) => operation,
+        Err(err) => return Ok(Response::from_error(err)),
+    };
+    Ok(operation.acl_log(param.count).await)
+}
+
+#[tauri::command]
+pub async fn redis_acl_log_reset(
+    state: State<'_, RedisConnectionState>,
+    param: GetRedisInfoParam,
+) -> Result<Response<bool>, ()> {
+    let operation = match state.operation(param.id.as_str()).await {
+        Ok(operation) => operation,
+        Err(err) => return Ok(Response::from_error(err)),
+    };
+    Ok(operation.acl_log_reset().await)
+}
+
+#[tauri::command]
+pub async fn redis_replication_status(
+    state: State<'_, RedisConnectionState>,
+    param: RedisReplicationParam,
+) -> Result<Response<RedisReplicationStatus>, ()> {
+    let operation = match state.operation(param.id.as_str()).await {
+        Ok(operation) => operation,
+        Err(err) => return Ok(Response::from_error(err)),
+    };
+    Ok(operation.get_replication_status(param.lag_threshold).await)
+}
+
+#[tauri::command]
+pub async fn redis_persistence_status(
+    state: State<'_, RedisConnectionState>,
+    param: GetRedisInfoParam,
+) -> Result<Response<RedisPersistenceStatus>, ()> {
+    let operation = match state.operation(param.id.as_str()).await {
+        Ok(operation) => operation,
+        Err(err) => return Ok(Response::from_error(err)),
+    };
+    Ok(operation.get_persistence_status().await)
+}
+
+#[tauri::command]
+pub async fn redis_bgsave(
+    state: State<'_, RedisConnectionState>,
+    param: RedisBgsaveParam,
+) -> Result<Response<String>, ()> {
+    let operation = match state.operation(param.id.as_str()).await {
+        Ok(operation) => operation,
+        Err(err) => return Ok(Response::from_error(err)),
+    };
+    Ok(operation.bgsave(param.schedule).await)
+}
+
+#[tauri::command]
+pub async fn redis_bgrewriteaof(
+    state: State<'_, RedisConnectionState>,
+    param: GetRedisInfoParam,
+) -> Result<Response<String>, ()> {
+    let operation = match state.operation(param.id.as_str()).await {
+        Ok(operation) => operation,
+        Err(err) => return Ok(Response::from_error(err)),
+    };
+    Ok(operation.bgrewriteaof().await)
+}
+
+#[tauri::command]
+pub async fn redis_replicaof(
+    state: State<'_, RedisConnectionState>,
+    param: RedisReplicaOfParam,
+) -> Result<Response<String>, ()> {
+    let operation = match state.operation(param.id.as_str()).await {
+        Ok(operation) => operation,
+        Err(err) => return Ok(Response::from_error(err)),
+    };
+    Ok(operation.replicaof(param.host.as_str(), param.port).await)
+}
+
+#[tauri::command]
+pub async fn redis_failover(
+    state: State<'_, RedisConnectionState>,
+    param: RedisFailoverParam,
+) -> Result<Response<String>, ()> {
+    let operation = match state.operation(param.id.as_str()).await {
+        Ok(operation) => operation,
+        Err(err) => return Ok(Response::from_error(err)),
+    };
+    Ok(operation.failover(param.options).await)
 }
@@ -1,6 +1,24 @@
 use serde::{Deserialize, Serialize};
 
-#[derive(Debug, Serialize, Deserialize)]
+/// ES 认证方式
+#[derive(Debug, Serialize, Deserialize, Clone, Copy, PartialEq, Default)]
+#[serde(rename_all = "snake_case")]
+pub enum EsAuthMode {
+    /// 不认证
+    None,
+
+    /// 账号密码（Basic）
+    #[default]
+    Basic,
+
+    /// `Authorization: ApiKey <api_key>`，api_key 为 base64(id:api_key)
+    ApiKey,
+
+    /// `Authorization: Bearer <token>`
+    Bearer,
+}
+
+#[derive(Debug, Serialize, Deserialize, Default)]
 pub struct ESUserPassword {
     /// ES username.
     pub username: String,
@@ -16,4 +34,56 @@ pub struct ESUserPassword {
 
     /// ES database name.
     pub name: String,
+
+    #[serde(default)]
+    pub auth_mode: EsAuthMode,
+
+    /// API key 模式下的编码后 key
+    #[serde(default)]
+    pub api_key: String,
+
+    /// Bearer 模式下的 token
+    #[serde(default)]
+    pub token: String,
+
+    /// 是否使用 https
+    #[serde(default)]
+    pub https: bool,
+
+    /// 其他节点地址，如 `http://es2:9200` 或 `es2:9200`，主节点不可用时依次切换
+    #[serde(default)]
+    pub nodes: Vec<String>,
+
+    /// 请求超时（秒），0 时使用默认值
+    #[serde(default)]
+    pub timeout_secs: u64,
+}
+
+impl ESUserPassword {
+    /// 全部节点地址，host/port 在前，缺省协议按 https 补全，去掉末尾的 `/`
+    pub fn node_urls(&self) -> Vec<String> {
+        let scheme = if self.https { "https" } else { "http" };
+        let mut urls = Vec::new();
+        if !self.host.trim().is_empty() {
+            urls.push(format!("{}:{}", self.host.trim(), self.port));
+        }
+        urls.extend(self.nodes.iter().map(|node| node.trim().to_string()));
+
+        let mut nodes: Vec<String> = Vec::new();
+        for url in urls {
+            if url.is_empty() {
+                continue;
+            }
+            let url = if url.contains("://") {
+                url
+            } else {
+                format!("{}://{}", scheme, url)
+            };
+            let url = url.trim_end_matches('/').to_string();
+            if !nodes.contains(&url) {
+                nodes.push(url);
+            }
+        }
+        nodes
+    }
 }
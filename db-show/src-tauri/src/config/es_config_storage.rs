@@ -1,4 +1,4 @@
-use crate::config::es_config::ESUserPassword;
+use crate::config::es_config::{ESUserPassword, EsAuthMode};
 use serde::{Deserialize, Serialize};
 use uuid::Uuid;
 
@@ -26,6 +26,42 @@ pub struct ESStorageEntity {
 
     /// ES database name.
     pub name: String,
+
+    #[serde(default)]
+    pub auth_mode: EsAuthMode,
+
+    #[serde(default)]
+    pub api_key: String,
+
+    #[serde(default)]
+    pub token: String,
+
+    #[serde(default)]
+    pub https: bool,
+
+    #[serde(default)]
+    pub nodes: Vec<String>,
+
+    #[serde(default)]
+    pub timeout_secs: u64,
+}
+
+impl ESStorageEntity {
+    pub fn to_user_password(&self) -> ESUserPassword {
+        ESUserPassword {
+            username: self.username.clone(),
+            password: self.password.clone(),
+            host: self.host.clone(),
+            port: self.port,
+            name: self.name.clone(),
+            auth_mode: self.auth_mode,
+            api_key: self.api_key.clone(),
+            token: self.token.clone(),
+            https: self.https,
+            nodes: self.nodes.clone(),
+            timeout_secs: self.timeout_secs,
+        }
+    }
 }
 
 pub trait ESStorageService {
@@ -77,6 +113,12 @@ impl ESStorageService for ESStorageManager {
             host: param.host,
             port: param.port,
             name: param.name,
+            auth_mode: param.auth_mode,
+            api_key: param.api_key,
+            token: param.token,
+            https: param.https,
+            nodes: param.nodes,
+            timeout_secs: param.timeout_secs,
         };
         self.values.push(v);
         self.write_config_json();
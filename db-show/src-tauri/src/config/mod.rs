@@ -1,9 +1,11 @@
 pub mod mongo_config;
 pub mod mongo_config_storage;
+pub mod mongo_resume_token_storage;
 pub mod mysql_config;
 pub mod mysql_config_storage;
 pub mod redis_config;
 pub mod redis_config_storage;
+pub mod redis_script_storage;
 
 pub mod es_config;
 pub mod es_config_storage;
@@ -1,6 +1,6 @@
 use serde::{Deserialize, Serialize};
 
-#[derive(Debug, Serialize, Deserialize)]
+#[derive(Debug, Serialize, Deserialize, Default)]
 pub struct MongoUserPassword {
     /// Mongo username.
     pub username: String,
@@ -16,4 +16,86 @@ pub struct MongoUserPassword {
 
     /// Mongo database name.
     pub name: String,
+
+    /// 完整连接串（mongodb:// 或 mongodb+srv://），设置后忽略其他连接字段
+    #[serde(default)]
+    pub connection_string: String,
+
+    /// 是否使用 mongodb+srv:// 通过 DNS SRV 记录发现节点，此时忽略端口
+    #[serde(default)]
+    pub srv: bool,
+
+    /// 认证数据库
+    #[serde(default)]
+    pub auth_source: Option<String>,
+
+    /// 副本集名称
+    #[serde(default)]
+    pub replica_set: Option<String>,
+
+    /// 读偏好：primary、primaryPreferred、secondary、secondaryPreferred、nearest
+    #[serde(default)]
+    pub read_preference: Option<String>,
+}
+
+impl MongoUserPassword {
+    /// 生成连接串，用户名和密码会做 URL 编码
+    pub fn connection_url(&self) -> String {
+        if !self.connection_string.trim().is_empty() {
+            return self.connection_string.trim().to_string();
+        }
+
+        let scheme = if self.srv { "mongodb+srv" } else { "mongodb" };
+        let credential = if self.username.is_empty() && self.password.is_empty() {
+            String::new()
+        } else {
+            format!(
+                "{}:{}@",
+                url_encode(&self.username),
+                url_encode(&self.password)
+            )
+        };
+        let address = if self.srv {
+            self.host.clone()
+        } else {
+            format!("{}:{}", self.host, self.port)
+        };
+
+        let mut query = Vec::new();
+        for (key, value) in [
+            ("authSource", &self.auth_source),
+            ("replicaSet", &self.replica_set),
+            ("readPreference", &self.read_preference),
+        ] {
+            if let Some(value) = value.as_deref().filter(|value| !value.is_empty()) {
+                query.push(format!("{}={}", key, url_encode(value)));
+            }
+        }
+
+        if query.is_empty() {
+            format!("{}://{}{}/", scheme, credential, address)
+        } else {
+            format!(
+                "{}://{}{}/?{}",
+                scheme,
+                credential,
+                address,
+                query.join("&")
+            )
+        }
+    }
+}
+
+/// 按 RFC 3986 对非保留字符以外的字节做百分号编码
+fn url_encode(value: &str) -> String {
+    let mut encoded = String::with_capacity(value.len());
+    for byte in value.bytes() {
+        match byte {
+            b'A'..=b'Z' | b'a'..=b'z' | b'0'..=b'9' | b'-' | b'.' | b'_' | b'~' => {
+                encoded.push(byte as char)
+            }
+            _ => encoded.push_str(&format!("%{:02X}", byte)),
+        }
+    }
+    encoded
 }
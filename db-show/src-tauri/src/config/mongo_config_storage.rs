@@ -10,6 +10,7 @@ use crate::env::init_file::{
 pub struct MongoStorageEntity {
     /// 1. 账号密码模式
     /// 2. ssl
+    /// 3. 连接串模式
     pub r#type: i32,
     pub id: String,
     /// Mongo username.
@@ -26,6 +27,38 @@ pub struct MongoStorageEntity {
 
     /// Mongo database name.
     pub name: String,
+
+    #[serde(default)]
+    pub connection_string: String,
+
+    #[serde(default)]
+    pub srv: bool,
+
+    #[serde(default)]
+    pub auth_source: Option<String>,
+
+    #[serde(default)]
+    pub replica_set: Option<String>,
+
+    #[serde(default)]
+    pub read_preference: Option<String>,
+}
+
+impl MongoStorageEntity {
+    pub fn to_user_password(&self) -> MongoUserPassword {
+        MongoUserPassword {
+            username: self.username.clone(),
+            password: self.password.clone(),
+            host: self.host.clone(),
+            port: self.port,
+            name: self.name.clone(),
+            connection_string: self.connection_string.clone(),
+            srv: self.srv,
+            auth_source: self.auth_source.clone(),
+            replica_set: self.replica_set.clone(),
+            read_preference: self.read_preference.clone(),
+        }
+    }
 }
 
 pub trait MongoStorageService {
@@ -38,6 +71,9 @@ pub trait MongoStorageService {
 /// 账号密码模式
 static USER_PASSWORD_MODEL: i32 = 1;
 
+/// 连接串模式
+static CONNECTION_STRING_MODEL: i32 = 3;
+
 pub struct MongoStorageManager {
     pub values: Vec<MongoStorageEntity>,
 }
@@ -69,14 +105,24 @@ impl MongoStorageService for MongoStorageManager {
     fn add_username_password_model(&mut self, param: MongoUserPassword) {
         let uid = Uuid::new_v4();
 
+        let r#type = if param.connection_string.trim().is_empty() {
+            USER_PASSWORD_MODEL
+        } else {
+            CONNECTION_STRING_MODEL
+        };
         let v = MongoStorageEntity {
-            r#type: USER_PASSWORD_MODEL,
+            r#type,
             id: uid.to_string(),
             username: param.username,
             password: param.password,
             host: param.host,
             port: param.port,
             name: param.name,
+            connection_string: param.connection_string,
+            srv: param.srv,
+            auth_source: param.auth_source,
+            replica_set: param.replica_set,
+            read_preference: param.read_preference,
         };
         self.values.push(v);
         self.write_config_json();
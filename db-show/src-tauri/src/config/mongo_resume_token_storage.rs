@@ -0,0 +1,125 @@
+use chrono::Local;
+use serde::{Deserialize, Serialize};
+
+use crate::env::init_file::{
+    get_home_directory, read_file_content, write_string_to_file, HOME_NAME, MONGO_RESUME_TOKEN_JSON,
+};
+
+/// change stream 的 resume token，按连接和监听范围区分
+#[derive(Debug, Serialize, Deserialize, Clone)]
+pub struct MongoResumeTokenEntity {
+    /// 对应 MongoStorageEntity 的 id
+    pub config_id: String,
+    /// 为空表示监听整个部署
+    #[serde(default)]
+    pub database: String,
+    /// 为空表示监听整个数据库
+    #[serde(default)]
+    pub collection: String,
+    /// resume token（canonical 扩展 JSON）
+    pub token: serde_json::Value,
+    /// 最后保存时间（毫秒时间戳）
+    #[serde(default)]
+    pub updated_at: i64,
+}
+
+pub trait MongoResumeTokenStorageService {
+    fn delete(&mut self, config_id: &str, database: &str, collection: &str);
+    fn write_config_json(&self);
+    /// 同一监听范围只保留最新的 token
+    fn save(&mut self, param: MongoResumeTokenEntity);
+    fn find(
+        &self,
+        config_id: &str,
+        database: &str,
+        collection: &str,
+    ) -> Option<&MongoResumeTokenEntity>;
+}
+
+pub struct MongoResumeTokenStorageManager {
+    pub values: Vec<MongoResumeTokenEntity>,
+}
+
+impl MongoResumeTokenStorageManager {
+    pub fn new() -> Self {
+        MongoResumeTokenStorageManager {
+            values: read_mongo_resume_token_json(),
+        }
+    }
+
+    fn position(&self, config_id: &str, database: &str, collection: &str) -> Option<usize> {
+        self.values.iter().position(|token| {
+            token.config_id == config_id
+                && token.database == database
+                && token.collection == collection
+        })
+    }
+}
+
+impl MongoResumeTokenStorageService for MongoResumeTokenStorageManager {
+    fn delete(&mut self, config_id: &str, database: &str, collection: &str) {
+        if let Some(index) = self.position(config_id, database, collection) {
+            self.values.remove(index);
+            self.write_config_json();
+        }
+    }
+
+    fn write_config_json(&self) {
+        let json = serde_json::to_string(&self.values).expect("Failed to serialize to JSON");
+        write_mongo_resume_token_json(json);
+    }
+
+    fn save(&mut self, mut param: MongoResumeTokenEntity) {
+        param.updated_at = Local::now().timestamp_millis();
+        match self.position(&param.config_id, &param.database, &param.collection) {
+            Some(index) => self.values[index] = param,
+            None => self.values.push(param),
+        }
+        self.write_config_json();
+    }
+
+    fn find(
+        &self,
+        config_id: &str,
+        database: &str,
+        collection: &str,
+    ) -> Option<&MongoResumeTokenEntity> {
+        self.position(config_id, database, collection)
+            .map(|index| &self.values[index])
+    }
+}
+
+pub fn write_mongo_resume_token_json(data: String) {
+    if let Ok(home_directory) = get_home_directory().ok_or("无法获取 HOME 目录") {
+        write_string_to_file(
+            format!(
+                "{}/{}/{}",
+                home_directory, HOME_NAME, MONGO_RESUME_TOKEN_JSON
+            )
+            .as_str(),
+            data.as_str(),
+        )
+        .expect("写入Mongo resume token失败");
+    }
+}
+
+pub fn read_mongo_resume_token_json() -> Vec<MongoResumeTokenEntity> {
+    if let Ok(home_directory) = get_home_directory().ok_or("无法获取 HOME 目录") {
+        let config_path = format!(
+            "{}/{}/{}",
+            home_directory, HOME_NAME, MONGO_RESUME_TOKEN_JSON
+        );
+        if let Ok(file_contents) = read_file_content(config_path.as_str()) {
+            if let Ok(parsed_data) =
+                serde_json::from_str::<Vec<MongoResumeTokenEntity>>(&file_contents)
+            {
+                return parsed_data;
+            } else {
+                eprintln!("Error parsing JSON data from the resume token file");
+            }
+        } else {
+            eprintln!("Error reading file content from the resume token file");
+        }
+    };
+    Vec::new()
+}
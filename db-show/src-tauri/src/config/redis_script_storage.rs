@@ -0,0 +1,113 @@
+use chrono::Local;
+use serde::{Deserialize, Serialize};
+use uuid::Uuid;
+
+use crate::env::init_file::{
+    get_home_directory, read_file_content, write_string_to_file, HOME_NAME, REDIS_SCRIPT_JSON,
+};
+
+/// 本地保存的 Lua 脚本，按连接区分
+#[derive(Debug, Serialize, Deserialize, Clone)]
+pub struct RedisScriptEntity {
+    /// 新建时为空
+    #[serde(default)]
+    pub id: String,
+    /// 对应 RedisStorageEntity 的 id
+    pub config_id: String,
+    pub name: String,
+    pub script: String,
+    #[serde(default)]
+    pub description: String,
+    /// 调试时默认使用的 KEYS
+    #[serde(default)]
+    pub keys: Vec<String>,
+    /// 调试时默认使用的 ARGV
+    #[serde(default)]
+    pub args: Vec<String>,
+    /// 最后修改时间（毫秒时间戳）
+    #[serde(default)]
+    pub updated_at: i64,
+}
+
+pub trait RedisScriptStorageService {
+    fn delete(&mut self, id: String);
+    fn write_config_json(&self);
+    /// id 为空时新增，否则覆盖同 id 的脚本
+    fn save(&mut self, param: RedisScriptEntity) -> RedisScriptEntity;
+    fn by_config_id(&self, config_id: &str) -> Vec<&RedisScriptEntity>;
+}
+
+pub struct RedisScriptStorageManager {
+    pub values: Vec<RedisScriptEntity>,
+}
+
+impl RedisScriptStorageManager {
+    pub fn new() -> Self {
+        RedisScriptStorageManager {
+            values: read_redis_script_json(),
+        }
+    }
+}
+
+impl RedisScriptStorageService for RedisScriptStorageManager {
+    fn delete(&mut self, id: String) {
+        if let Some(index) = self.values.iter().position(|script| script.id == id) {
+            self.values.remove(index);
+            self.write_config_json();
+        } else {
+            println!("找不到id with id '{}' not found.", id);
+        }
+    }
+
+    fn write_config_json(&self) {
+        let json = serde_json::to_string(&self.values).expect("Failed to serialize to JSON");
+        write_redis_script_json(json);
+    }
+
+    fn save(&mut self, mut param: RedisScriptEntity) -> RedisScriptEntity {
+        param.updated_at = Local::now().timestamp_millis();
+        match self.values.iter().position(|script| script.id == param.id) {
+            Some(index) if !param.id.is_empty() => self.values[index] = param.clone(),
+            _ => {
+                param.id = Uuid::new_v4().to_string();
+                self.values.push(param.clone());
+            }
+        }
+        self.write_config_json();
+        param
+    }
+
+    fn by_config_id(&self, config_id: &str) -> Vec<&RedisScriptEntity> {
+        self.values
+            .iter()
+            .filter(|script| script.config_id == config_id)
+            .collect()
+    }
+}
+
+pub fn write_redis_script_json(data: String) {
+    if let Ok(home_directory) = get_home_directory().ok_or("无法获取 HOME 目录") {
+        write_string_to_file(
+            format!("{}/{}/{}", home_directory, HOME_NAME, REDIS_SCRIPT_JSON).as_str(),
+            data.as_str(),
+        )
+        .expect("写入Redis脚本库失败");
+    }
+}
+
+pub fn read_redis_script_json() -> Vec<RedisScriptEntity> {
+    if let Ok(home_directory) = get_home_directory().ok_or("无法获取 HOME 目录") {
+        let config_path = format!("{}/{}/{}", home_directory, HOME_NAME, REDIS_SCRIPT_JSON);
+        if let Ok(file_contents) = read_file_content(config_path.as_str()) {
+            if let Ok(parsed_data) = serde_json::from_str::<Vec<RedisScriptEntity>>(&file_contents)
+            {
+                return parsed_data;
+            } else {
+                eprintln!("Error parsing JSON data from the script file");
+            }
+        } else {
+            eprintln!("Error reading file content from the script file");
+        }
+    };
+    Vec::new()
+}
@@ -5,7 +5,9 @@ use std::{env, fs, io};
 pub static HOME_NAME: &str = "db-show";
 pub static MYSQL_CONFIG_JSON: &str = "mysql.json";
 pub static REDIS_CONFIG_JSON: &str = "redis.json";
+pub static REDIS_SCRIPT_JSON: &str = "redis_script.json";
 pub static MONGO_CONFIG_JSON: &str = "mongo.json";
+pub static MONGO_RESUME_TOKEN_JSON: &str = "mongo_resume_token.json";
 pub static ES_CONFIG_JSON: &str = "es.json";
 pub static ZK_CONFIG_JSON: &str = "zk.json";
 pub static INFLUXDB_CONFIG_JSON: &str = "influxdb.json";
@@ -28,11 +30,25 @@ pub fn init_home() {
             "",
         )
         .expect("创建 redis 配置失败");
+        create_file_if_not_exists(
+            format!("{}/{}/{}", home_directory, HOME_NAME, REDIS_SCRIPT_JSON).as_str(),
+            "",
+        )
+        .expect("创建 redis 脚本库失败");
         create_file_if_not_exists(
             format!("{}/{}/{}", home_directory, HOME_NAME, MONGO_CONFIG_JSON).as_str(),
             "",
         )
         .expect("创建 mongo 配置失败");
+        create_file_if_not_exists(
+            format!(
+                "{}/{}/{}",
+                home_directory, HOME_NAME, MONGO_RESUME_TOKEN_JSON
+            )
+            .as_str(),
+            "",
+        )
+        .expect("创建 mongo resume token 失败");
         create_file_if_not_exists(
             format!("{}/{}/{}", home_directory, HOME_NAME, ES_CONFIG_JSON).as_str(),
             "",
@@ -5,3 +5,4 @@ pub mod env;
 pub mod history;
 pub mod op;
 pub mod resp;
+pub mod util;
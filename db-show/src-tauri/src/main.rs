@@ -1,19 +1,51 @@
 // Prevents additional console window on Windows in release, DO NOT REMOVE!!
 #![cfg_attr(not(debug_assertions), windows_subsystem = "windows")]
 
+use crate::cmd::es_cmd::{
+    add_es_config, es_allocation_explain, es_analyze, es_bulk_import, es_cancel_task,
+    es_close_index, es_close_pit, es_cluster_health, es_count, es_create_index,
+    es_create_repository, es_create_snapshot, es_delete_by_query, es_delete_document,
+    es_delete_index, es_field_caps, es_flush_index, es_force_merge, es_get_cluster_settings,
+    es_get_document, es_get_index_settings, es_get_mapping, es_get_task, es_index_document,
+    es_list_aliases, es_list_indices, es_list_repositories, es_list_shards, es_list_snapshots,
+    es_list_tasks, es_nodes_stats, es_open_index, es_open_pit, es_refresh_index, es_resize_index,
+    es_restore_snapshot, es_search, es_update_aliases, es_update_by_query,
+    es_update_cluster_settings, es_update_document, es_update_index_settings, es_validate_query,
+    query_all_es,
+};
 use crate::cmd::influxdb_cmd::*;
-use crate::cmd::mongo_cmd::{add_mongo_config, mongo_db_names, mongo_info, query_all_mongo};
+use crate::cmd::mongo_cmd::{
+    add_mongo_config, mongo_aggregate, mongo_create_collection, mongo_create_index,
+    mongo_create_role, mongo_create_user, mongo_current_op, mongo_db_names, mongo_delete_documents,
+    mongo_drop_collection, mongo_drop_database, mongo_drop_index, mongo_drop_role, mongo_drop_user,
+    mongo_explain_aggregate, mongo_explain_find, mongo_export_collection, mongo_find_documents,
+    mongo_grant_roles, mongo_import_collection, mongo_index_stats, mongo_infer_schema, mongo_info,
+    mongo_insert_documents, mongo_kill_op, mongo_list_roles, mongo_list_users,
+    mongo_profile_entries, mongo_profiling_status, mongo_replace_document,
+    mongo_replica_set_status, mongo_revoke_roles, mongo_set_profiling_level, mongo_sharding_status,
+    mongo_update_documents, mongo_update_role, mongo_update_user, mongo_watch_list,
+    mongo_watch_start, mongo_watch_stop, query_all_mongo, MongoWatchState,
+};
 use crate::cmd::mysql_cmd::{
     add_mysql_config, get_db_names, get_db_status, get_tables_names, query_all_mysql,
     show_table_create_sql,
 };
 use crate::cmd::redis_cmd::{
-    add_redis_config, query_all_redis, redis_add_set, redis_change_hash, redis_change_list,
-    redis_change_set, redis_change_zset, redis_db_count, redis_delete_redis_key,
+    add_redis_config, query_all_redis, redis_acl_delete_users, redis_acl_get_user, redis_acl_list,
+    redis_acl_log, redis_acl_log_reset, redis_acl_save_user, redis_add_set, redis_bgrewriteaof,
+    redis_bgsave, redis_change_hash, redis_change_list, redis_change_set, redis_change_zset,
+    redis_config_diff, redis_config_get, redis_config_rewrite, redis_config_set, redis_db_count,
+    redis_delete_redis_key, redis_delete_script, redis_eval, redis_evalsha, redis_export_keys,
+    redis_failover, redis_fcall, redis_function_delete, redis_function_list, redis_function_load,
     redis_get_hash_data, redis_get_list_data, redis_get_set_data, redis_get_string_data,
-    redis_get_zset_data, redis_info, redis_keys_page, redis_remove_hash_member,
-    redis_remove_list_member, redis_remove_set_member, redis_remove_zset_member,
-    redis_set_redis_key_expire, redis_set_string_data,
+    redis_get_zset_data, redis_import_keys, redis_info, redis_keys_page, redis_list_sessions,
+    redis_migrate_keys, redis_monitor_start, redis_persistence_status, redis_publish,
+    redis_pubsub_channels, redis_pubsub_numpat, redis_pubsub_numsub, redis_pubsub_subscribe,
+    redis_query_scripts, redis_read_monitor_capture, redis_remove_hash_member,
+    redis_remove_list_member, redis_remove_set_member, redis_remove_zset_member, redis_replicaof,
+    redis_replication_status, redis_save_script, redis_script_exists, redis_script_flush,
+    redis_script_load, redis_set_redis_key_expire, redis_set_string_data, redis_stop_session,
+    RedisConnectionState, RedisSessionState,
 };
 use crate::cmd::zk_cmd::*;
 use crate::env::init_file::init_home;
@@ -23,6 +55,7 @@ pub mod config;
 pub mod env;
 pub mod op;
 pub mod resp;
+pub mod util;
 
 // Learn more about Tauri commands at https://tauri.app/v1/guides/features/command
 #[tauri::command]
@@ -33,6 +66,9 @@ fn greet(name: &str) -> String {
 fn main() {
     init_home();
     tauri::Builder::default()
+        .manage(RedisSessionState::default())
+        .manage(RedisConnectionState::default())
+        .manage(MongoWatchState::default())
         .invoke_handler(tauri::generate_handler![
             greet,
             query_all_mysql,
@@ -48,6 +84,87 @@ fn main() {
             query_all_mongo,
             add_mongo_config,
             mongo_db_names,
+            query_all_es,
+            add_es_config,
+            es_cluster_health,
+            es_nodes_stats,
+            es_list_indices,
+            es_create_index,
+            es_delete_index,
+            es_open_index,
+            es_close_index,
+            es_refresh_index,
+            es_flush_index,
+            es_force_merge,
+            es_resize_index,
+            es_get_index_settings,
+            es_update_index_settings,
+            es_list_aliases,
+            es_update_aliases,
+            es_search,
+            es_open_pit,
+            es_close_pit,
+            es_count,
+            es_validate_query,
+            es_get_document,
+            es_index_document,
+            es_update_document,
+            es_delete_document,
+            es_update_by_query,
+            es_delete_by_query,
+            es_get_task,
+            es_bulk_import,
+            es_get_mapping,
+            es_field_caps,
+            es_analyze,
+            es_list_shards,
+            es_allocation_explain,
+            es_list_tasks,
+            es_cancel_task,
+            es_get_cluster_settings,
+            es_update_cluster_settings,
+            es_list_repositories,
+            es_create_repository,
+            es_list_snapshots,
+            es_create_snapshot,
+            es_restore_snapshot,
+            mongo_replica_set_status,
+            mongo_sharding_status,
+            mongo_watch_start,
+            mongo_watch_stop,
+            mongo_watch_list,
+            mongo_find_documents,
+            mongo_insert_documents,
+            mongo_update_documents,
+            mongo_replace_document,
+            mongo_delete_documents,
+            mongo_aggregate,
+            mongo_explain_aggregate,
+            mongo_infer_schema,
+            mongo_create_index,
+            mongo_drop_index,
+            mongo_index_stats,
+            mongo_explain_find,
+            mongo_export_collection,
+            mongo_import_collection,
+            mongo_current_op,
+            mongo_kill_op,
+            mongo_profiling_status,
+            mongo_set_profiling_level,
+            mongo_profile_entries,
+            mongo_list_users,
+            mongo_create_user,
+            mongo_update_user,
+            mongo_drop_user,
+            mongo_grant_roles,
+            mongo_revoke_roles,
+            mongo_list_roles,
+            mongo_create_role,
+            mongo_update_role,
+            mongo_drop_role,
+            mongo_create_collection,
+            mongo_drop_collection,
+            mongo_drop_database,
             redis_db_count,
             redis_keys_page,
             redis_get_string_data,
@@ -67,6 +184,46 @@ fn main() {
             redis_remove_hash_member,
             redis_remove_zset_member,
             redis_change_zset,
+            redis_pubsub_subscribe,
+            redis_stop_session,
+            redis_list_sessions,
+            redis_publish,
+            redis_pubsub_channels,
+            redis_pubsub_numsub,
+            redis_pubsub_numpat,
+            redis_monitor_start,
+            redis_read_monitor_capture,
+            redis_export_keys,
+            redis_import_keys,
+            redis_migrate_keys,
+            redis_eval,
+            redis_evalsha,
+            redis_script_load,
+            redis_script_exists,
+            redis_script_flush,
+            redis_function_list,
+            redis_function_load,
+            redis_function_delete,
+            redis_fcall,
+            redis_query_scripts,
+            redis_save_script,
+            redis_delete_script,
+            redis_config_get,
+            redis_config_diff,
+            redis_config_set,
+            redis_config_rewrite,
+            redis_acl_list,
+            redis_acl_get_user,
+            redis_acl_save_user,
+            redis_acl_delete_users,
+            redis_acl_log,
+            redis_acl_log_reset,
+            redis_replication_status,
+            redis_persistence_status,
+            redis_bgsave,
+            redis_bgrewriteaof,
+            redis_replicaof,
+            redis_failover,
             query_all_influxdb,
             add_influxdb_config,
             query_all_zookeeper,
@@ -1,3 +1,5 @@
+use std::collections::BTreeMap;
+
 use serde::{Deserialize, Serialize};
 
 #[derive(Debug, Deserialize, Serialize)]
@@ -23,4 +25,1208 @@ pub struct ClusterHealth {
 pub struct NodesStats {
     pub node_count: u32,
     pub total_memory: u64,
+    pub nodes: Vec<EsNodeStats>,
+}
+
+impl NodesStats {
+    /// 解析 `_nodes/stats/jvm,os,fs`，total_memory 为各节点物理内存之和
+    pub fn from_response(response: &serde_json::Value) -> Self {
+        let mut nodes: Vec<EsNodeStats> = response["nodes"]
+            .as_object()
+            .map(|nodes| {
+                nodes
+                    .iter()
+                    .map(|(id, node)| EsNodeStats::from_value(id, node))
+                    .collect()
+            })
+            .unwrap_or_default();
+        nodes.sort_by(|a, b| a.name.cmp(&b.name));
+        NodesStats {
+            node_count: nodes.len() as u32,
+            total_memory: nodes.iter().map(|node| node.mem_total_bytes).sum(),
+            nodes,
+        }
+    }
+}
+
+/// 单个节点的资源使用情况
+#[derive(Debug, Deserialize, Serialize)]
+pub struct EsNodeStats {
+    pub id: String,
+
+    pub name: String,
+
+    pub host: String,
+
+    pub ip: String,
+
+    /// master、data、ingest 等
+    pub roles: Vec<String>,
+
+    pub heap_used_bytes: u64,
+
+    pub heap_max_bytes: u64,
+
+    pub heap_used_percent: u64,
+
+    pub cpu_percent: u64,
+
+    pub load_average_1m: Option<f64>,
+
+    pub mem_total_bytes: u64,
+
+    pub mem_used_percent: u64,
+
+    pub disk_total_bytes: u64,
+
+    /// 当前 JVM 可用的磁盘空间，低水位线按它判断
+    pub disk_available_bytes: u64,
+
+    pub gc_young_count: u64,
+
+    pub gc_young_time_ms: u64,
+
+    pub gc_old_count: u64,
+
+    pub gc_old_time_ms: u64,
+
+    pub uptime_ms: u64,
+}
+
+impl EsNodeStats {
+    pub fn from_value(id: &str, node: &serde_json::Value) -> Self {
+        let jvm = &node["jvm"];
+        let os = &node["os"];
+        let disk = &node["fs"]["total"];
+        let young = &jvm["gc"]["collectors"]["young"];
+        let old = &jvm["gc"]["collectors"]["old"];
+        EsNodeStats {
+            id: id.to_string(),
+            name: json_string(node, "name"),
+            host: json_string(node, "host"),
+            ip: json_string(node, "ip"),
+            roles: node["roles"]
+                .as_array()
+                .map(|roles| {
+                    roles
+                        .iter()
+                        .filter_map(|role| role.as_str())
+                        .map(|role| role.to_string())
+                        .collect()
+                })
+                .unwrap_or_default(),
+            heap_used_bytes: jvm["mem"]["heap_used_in_bytes"].as_u64().unwrap_or(0),
+            heap_max_bytes: jvm["mem"]["heap_max_in_bytes"].as_u64().unwrap_or(0),
+            heap_used_percent: jvm["mem"]["heap_used_percent"].as_u64().unwrap_or(0),
+            cpu_percent: os["cpu"]["percent"].as_u64().unwrap_or(0),
+            load_average_1m: os["cpu"]["load_average"]["1m"].as_f64(),
+            mem_total_bytes: os["mem"]["total_in_bytes"].as_u64().unwrap_or(0),
+            mem_used_percent: os["mem"]["used_percent"].as_u64().unwrap_or(0),
+            disk_total_bytes: disk["total_in_bytes"].as_u64().unwrap_or(0),
+            disk_available_bytes: disk["available_in_bytes"].as_u64().unwrap_or(0),
+            gc_young_count: young["collection_count"].as_u64().unwrap_or(0),
+            gc_young_time_ms: young["collection_time_in_millis"].as_u64().unwrap_or(0),
+            gc_old_count: old["collection_count"].as_u64().unwrap_or(0),
+            gc_old_time_ms: old["collection_time_in_millis"].as_u64().unwrap_or(0),
+            uptime_ms: jvm["uptime_in_millis"].as_u64().unwrap_or(0),
+        }
+    }
+}
+
+/// 读取 _cat 接口中以字符串表示的数字，closed 索引等场景为 null 时取 0
+fn cat_number<T: std::str::FromStr + Default>(row: &serde_json::Value, key: &str) -> T {
+    row.get(key)
+        .and_then(|value| value.as_str())
+        .and_then(|value| value.parse().ok())
+        .unwrap_or_default()
+}
+
+/// 读取字符串字段，不存在或不是字符串时取空字符串
+fn json_string(value: &serde_json::Value, key: &str) -> String {
+    value
+        .get(key)
+        .and_then(|value| value.as_str())
+        .unwrap_or_default()
+        .to_string()
+}
+
+/// `_cat/indices?format=json&bytes=b` 的一行
+#[derive(Debug, Deserialize, Serialize)]
+pub struct EsIndexInfo {
+    pub index: String,
+
+    pub uuid: String,
+
+    /// green、yellow、red
+    pub health: String,
+
+    /// open、close
+    pub status: String,
+
+    pub primaries: u32,
+
+    pub replicas: u32,
+
+    pub docs_count: u64,
+
+    pub docs_deleted: u64,
+
+    /// 包含副本的存储大小（字节）
+    pub store_size: u64,
+
+    /// 主分片存储大小（字节）
+    pub primary_store_size: u64,
+}
+
+impl EsIndexInfo {
+    pub fn from_cat_row(row: &serde_json::Value) -> Self {
+        EsIndexInfo {
+            index: json_string(row, "index"),
+            uuid: json_string(row, "uuid"),
+            health: json_string(row, "health"),
+            status: json_string(row, "status"),
+            primaries: cat_number(row, "pri"),
+            replicas: cat_number(row, "rep"),
+            docs_count: cat_number(row, "docs.count"),
+            docs_deleted: cat_number(row, "docs.deleted"),
+            store_size: cat_number(row, "store.size"),
+            primary_store_size: cat_number(row, "pri.store.size"),
+        }
+    }
+}
+
+/// 索引级写操作的确认结果
+#[derive(Debug, Deserialize, Serialize, Default)]
+pub struct EsAcknowledged {
+    #[serde(default)]
+    pub acknowledged: bool,
+
+    /// 创建、打开、clone/shrink 时主分片是否已在超时前启动
+    #[serde(default)]
+    pub shards_acknowledged: bool,
+}
+
+/// refresh、flush、forcemerge 返回的分片执行情况
+#[derive(Debug, Deserialize, Serialize, Default)]
+pub struct EsShardsSummary {
+    #[serde(default)]
+    pub total: u32,
+
+    #[serde(default)]
+    pub successful: u32,
+
+    #[serde(default)]
+    pub failed: u32,
+}
+
+/// 调整索引分片的方式
+#[derive(Debug, Deserialize, Serialize, Clone, Copy, PartialEq)]
+#[serde(rename_all = "snake_case")]
+pub enum EsResizeType {
+    /// 复制为相同主分片数的新索引
+    Clone,
+
+    /// 收缩为更少主分片数的新索引
+    Shrink,
+}
+
+/// clone/shrink 参数
+#[derive(Debug, Deserialize, Serialize)]
+pub struct EsResizeOptions {
+    pub resize_type: EsResizeType,
+
+    pub target: String,
+
+    /// 目标索引的 settings/aliases（JSON），如 `{"settings": {"index.number_of_shards": 1}}`
+    #[serde(default)]
+    pub body: String,
+
+    /// 执行前为源索引设置 index.blocks.write，clone/shrink 要求源索引只读。
+    /// 失败时自动撤销，成功后保留
+    #[serde(default)]
+    pub block_source_writes: bool,
+}
+
+/// 别名操作
+#[derive(Debug, Deserialize, Serialize, Clone, Copy, PartialEq)]
+#[serde(rename_all = "snake_case")]
+pub enum EsAliasActionType {
+    Add,
+    Remove,
+}
+
+#[derive(Debug, Deserialize, Serialize)]
+pub struct EsAliasAction {
+    pub action: EsAliasActionType,
+
+    pub index: String,
+
+    pub alias: String,
+
+    /// 过滤别名的查询条件（JSON），仅 add 时有效
+    #[serde(default)]
+    pub filter: String,
+
+    /// 是否作为写入索引，仅 add 时有效
+    #[serde(default)]
+    pub is_write_index: Option<bool>,
+}
+
+/// `_cat/aliases?format=json` 的一行
+#[derive(Debug, Deserialize, Serialize)]
+pub struct EsAliasInfo {
+    pub alias: String,
+
+    pub index: String,
+
+    /// 是否带过滤条件
+    pub filtered: bool,
+
+    pub is_write_index: Option<bool>,
+}
+
+impl EsAliasInfo {
+    pub fn from_cat_row(row: &serde_json::Value) -> Self {
+        EsAliasInfo {
+            alias: json_string(row, "alias"),
+            index: json_string(row, "index"),
+            filtered: !matches!(json_string(row, "filter").as_str(), "" | "-"),
+            is_write_index: match json_string(row, "is_write_index").as_str() {
+                "true" => Some(true),
+                "false" => Some(false),
+                _ => None,
+            },
+        }
+    }
+}
+
+/// _search 参数
+#[derive(Debug, Deserialize, Serialize, Default)]
+pub struct EsSearchOptions {
+    /// 索引名或通配符，为空时查询全部；使用 pit_id 时忽略
+    #[serde(default)]
+    pub indices: Vec<String>,
+
+    /// Query DSL 请求体（JSON），可包含 query、aggs、highlight、sort、_source 等
+    #[serde(default)]
+    pub body: String,
+
+    #[serde(default)]
+    pub from: Option<u64>,
+
+    #[serde(default)]
+    pub size: Option<u64>,
+
+    /// 上一页最后一条命中的 sort 值，用于深度翻页
+    #[serde(default)]
+    pub search_after: Option<serde_json::Value>,
+
+    /// point-in-time ID，配合 search_after 保证翻页期间数据一致
+    #[serde(default)]
+    pub pit_id: Option<String>,
+
+    /// PIT 保持时间，如 `1m`，为空时使用默认值
+    #[serde(default)]
+    pub keep_alive: String,
+}
+
+/// 一条命中
+#[derive(Debug, Deserialize, Serialize)]
+pub struct EsHit {
+    pub index: String,
+
+    pub id: String,
+
+    pub score: Option<f64>,
+
+    pub source: Option<serde_json::Value>,
+
+    /// 字段 -> 高亮片段
+    pub highlight: Option<serde_json::Value>,
+
+    /// 排序值，作为下一页的 search_after
+    pub sort: Option<serde_json::Value>,
+}
+
+impl EsHit {
+    pub fn from_value(hit: &serde_json::Value) -> Self {
+        EsHit {
+            index: json_string(hit, "_index"),
+            id: json_string(hit, "_id"),
+            score: hit.get("_score").and_then(|score| score.as_f64()),
+            source: hit.get("_source").cloned(),
+            highlight: hit.get("highlight").cloned(),
+            sort: hit.get("sort").cloned(),
+        }
+    }
+}
+
+/// _search 结果
+#[derive(Debug, Deserialize, Serialize)]
+pub struct EsSearchResult {
+    pub took: u64,
+
+    pub timed_out: bool,
+
+    pub total: u64,
+
+    /// eq 表示精确值，gte 表示下限（超过 track_total_hits）
+    pub total_relation: String,
+
+    pub max_score: Option<f64>,
+
+    pub hits: Vec<EsHit>,
+
+    pub aggregations: Option<serde_json::Value>,
+
+    /// 响应返回的最新 PIT ID，下一页应使用它
+    pub pit_id: Option<String>,
+
+    /// 最后一条命中的 sort 值，为空说明没有排序或没有更多数据
+    pub next_search_after: Option<serde_json::Value>,
+
+    pub shards: EsShardsSummary,
+}
+
+impl EsSearchResult {
+    pub fn from_response(response: &serde_json::Value) -> Self {
+        let hits = &response["hits"];
+        let (total, total_relation) = match &hits["total"] {
+            // 7.x 之前 total 是数字
+            serde_json::Value::Number(total) => (total.as_u64().unwrap_or(0), "eq".to_string()),
+            total => (
+                total["value"].as_u64().unwrap_or(0),
+                total["relation"].as_str().unwrap_or("eq").to_string(),
+            ),
+        };
+        let hits: Vec<EsHit> = hits["hits"]
+            .as_array()
+            .map(|hits| hits.iter().map(EsHit::from_value).collect())
+            .unwrap_or_default();
+        EsSearchResult {
+            took: response["took"].as_u64().unwrap_or(0),
+            timed_out: response["timed_out"].as_bool().unwrap_or(false),
+            total,
+            total_relation,
+            max_score: response["hits"]["max_score"].as_f64(),
+            next_search_after: hits.last().and_then(|hit| hit.sort.clone()),
+            hits,
+            aggregations: response.get("aggregations").cloned(),
+            pit_id: response["pit_id"].as_str().map(|pit_id| pit_id.to_string()),
+            shards: serde_json::from_value(response["_shards"].clone()).unwrap_or_default(),
+        }
+    }
+}
+
+/// _validate/query?explain 中单个索引的结果
+#[derive(Debug, Deserialize, Serialize)]
+pub struct EsQueryExplanation {
+    pub index: String,
+
+    pub valid: bool,
+
+    /// 改写后的 Lucene 查询
+    pub explanation: Option<String>,
+
+    pub error: Option<String>,
+}
+
+#[derive(Debug, Deserialize, Serialize)]
+pub struct EsValidateResult {
+    pub valid: bool,
+
+    pub explanations: Vec<EsQueryExplanation>,
+
+    /// 请求体本身无法解析时的错误
+    pub error: Option<String>,
+}
+
+impl EsValidateResult {
+    pub fn from_response(response: &serde_json::Value) -> Self {
+        EsValidateResult {
+            valid: response["valid"].as_bool().unwrap_or(false),
+            explanations: response["explanations"]
+                .as_array()
+                .map(|items| {
+                    items
+                        .iter()
+                        .map(|item| EsQueryExplanation {
+                            index: json_string(item, "index"),
+                            valid: item["valid"].as_bool().unwrap_or(false),
+                            explanation: item["explanation"].as_str().map(|text| text.to_string()),
+                            error: item["error"].as_str().map(|text| text.to_string()),
+                        })
+                        .collect()
+                })
+                .unwrap_or_default(),
+            error: response["error"].as_str().map(|text| text.to_string()),
+        }
+    }
+}
+
+/// 按 ID 读取的文档
+#[derive(Debug, Deserialize, Serialize)]
+pub struct EsDocument {
+    pub index: String,
+
+    pub id: String,
+
+    pub found: bool,
+
+    pub version: Option<i64>,
+
+    /// 与 primary_term 一起用于乐观并发控制
+    pub seq_no: Option<i64>,
+
+    pub primary_term: Option<i64>,
+
+    pub source: Option<serde_json::Value>,
+}
+
+impl EsDocument {
+    pub fn from_value(value: &serde_json::Value) -> Self {
+        EsDocument {
+            index: json_string(value, "_index"),
+            id: json_string(value, "_id"),
+            found: value["found"].as_bool().unwrap_or(false),
+            version: value["_version"].as_i64(),
+            seq_no: value["_seq_no"].as_i64(),
+            primary_term: value["_primary_term"].as_i64(),
+            source: value.get("_source").cloned(),
+        }
+    }
+}
+
+/// 单文档写入参数
+#[derive(Debug, Deserialize, Serialize, Default)]
+pub struct EsWriteOptions {
+    /// 写入后等待刷新（refresh=wait_for），使结果立即可查
+    #[serde(default)]
+    pub refresh: bool,
+
+    /// 乐观并发控制：文档当前的 seq_no 与 primary_term 一致时才写入
+    #[serde(default)]
+    pub if_seq_no: Option<i64>,
+
+    #[serde(default)]
+    pub if_primary_term: Option<i64>,
+}
+
+/// 单文档写入结果
+#[derive(Debug, Deserialize, Serialize)]
+pub struct EsWriteResult {
+    pub index: String,
+
+    pub id: String,
+
+    pub version: Option<i64>,
+
+    /// created、updated、deleted、noop、not_found
+    pub result: String,
+
+    pub seq_no: Option<i64>,
+
+    pub primary_term: Option<i64>,
+}
+
+impl EsWriteResult {
+    pub fn from_value(value: &serde_json::Value) -> Self {
+        EsWriteResult {
+            index: json_string(value, "_index"),
+            id: json_string(value, "_id"),
+            version: value["_version"].as_i64(),
+            result: json_string(value, "result"),
+            seq_no: value["_seq_no"].as_i64(),
+            primary_term: value["_primary_term"].as_i64(),
+        }
+    }
+}
+
+/// update_by_query / delete_by_query 参数
+#[derive(Debug, Deserialize, Serialize, Default)]
+pub struct EsByQueryOptions {
+    pub indices: Vec<String>,
+
+    /// 请求体（JSON），包含 query，update_by_query 可包含 script
+    #[serde(default)]
+    pub body: String,
+
+    /// 版本冲突时继续执行（conflicts=proceed），否则遇到冲突即中止
+    #[serde(default)]
+    pub conflicts_proceed: bool,
+
+    /// 为 false 时后台执行并返回任务 ID
+    #[serde(default)]
+    pub wait_for_completion: bool,
+
+    /// 最多处理的文档数
+    #[serde(default)]
+    pub max_docs: Option<u64>,
+
+    /// 执行完成后刷新涉及的索引
+    #[serde(default)]
+    pub refresh: bool,
+}
+
+/// by-query 执行结果，后台执行时只有 task
+#[derive(Debug, Deserialize, Serialize, Default)]
+pub struct EsByQueryResult {
+    /// 后台任务 ID，如 `node:123`
+    pub task: Option<String>,
+
+    pub took: u64,
+
+    pub timed_out: bool,
+
+    pub total: u64,
+
+    pub updated: u64,
+
+    pub deleted: u64,
+
+    pub version_conflicts: u64,
+
+    pub noops: u64,
+
+    /// 失败原因
+    pub failures: Vec<String>,
+}
+
+impl EsByQueryResult {
+    pub fn from_value(value: &serde_json::Value) -> Self {
+        EsByQueryResult {
+            task: value["task"].as_str().map(|task| task.to_string()),
+            took: value["took"].as_u64().unwrap_or(0),
+            timed_out: value["timed_out"].as_bool().unwrap_or(false),
+            total: value["total"].as_u64().unwrap_or(0),
+            updated: value["updated"].as_u64().unwrap_or(0),
+            deleted: value["deleted"].as_u64().unwrap_or(0),
+            version_conflicts: value["version_conflicts"].as_u64().unwrap_or(0),
+            noops: value["noops"].as_u64().unwrap_or(0),
+            failures: value["failures"]
+                .as_array()
+                .map(|failures| failures.iter().map(failure_reason).collect())
+                .unwrap_or_default(),
+        }
+    }
+}
+
+/// 提取失败项中的原因，兼容 `{cause: {type, reason}}` 和 `{type, reason}`
+pub fn failure_reason(failure: &serde_json::Value) -> String {
+    let cause = failure.get("cause").unwrap_or(failure);
+    match (cause["type"].as_str(), cause["reason"].as_str()) {
+        (Some(kind), Some(reason)) => format!("{}: {}", kind, reason),
+        (None, Some(reason)) => reason.to_string(),
+        _ => failure.to_string(),
+    }
+}
+
+/// 后台任务进度（GET _tasks/{task_id}）
+#[derive(Debug, Deserialize, Serialize)]
+pub struct EsTaskStatus {
+    pub task_id: String,
+
+    pub completed: bool,
+
+    /// 如 indices:data/write/update/byquery
+    pub action: String,
+
+    pub description: String,
+
+    pub running_time_ms: u64,
+
+    pub total: u64,
+
+    pub updated: u64,
+
+    pub created: u64,
+
+    pub deleted: u64,
+
+    pub version_conflicts: u64,
+
+    /// 任务完成后的执行结果
+    pub result: Option<EsByQueryResult>,
+
+    /// 任务执行失败的原因
+    pub error: Option<String>,
+}
+
+impl EsTaskStatus {
+    pub fn from_value(task_id: &str, value: &serde_json::Value) -> Self {
+        let task = &value["task"];
+        let status = &task["status"];
+        EsTaskStatus {
+            task_id: task_id.to_string(),
+            completed: value["completed"].as_bool().unwrap_or(false),
+            action: json_string(task, "action"),
+            description: json_string(task, "description"),
+            running_time_ms: task["running_time_in_nanos"].as_u64().unwrap_or(0) / 1_000_000,
+            total: status["total"].as_u64().unwrap_or(0),
+            updated: status["updated"].as_u64().unwrap_or(0),
+            created: status["created"].as_u64().unwrap_or(0),
+            deleted: status["deleted"].as_u64().unwrap_or(0),
+            version_conflicts: status["version_conflicts"].as_u64().unwrap_or(0),
+            result: value.get("response").map(EsByQueryResult::from_value),
+            error: value.get("error").map(failure_reason),
+        }
+    }
+}
+
+/// NDJSON 中的一个 bulk 操作：动作行及其后的文档行
+#[derive(Debug, Deserialize, Serialize)]
+pub struct EsBulkOperation {
+    /// 动作行所在的行号（从 1 开始）
+    pub line: usize,
+
+    /// index、create、update、delete
+    pub action: String,
+
+    /// 发送给 _bulk 的内容，以换行结尾
+    pub payload: String,
+}
+
+/// bulk 中失败的一项
+#[derive(Debug, Deserialize, Serialize)]
+pub struct EsBulkItemError {
+    /// 动作行所在的行号
+    pub line: usize,
+
+    pub action: String,
+
+    pub index: String,
+
+    pub id: String,
+
+    pub status: u16,
+
+    pub error: String,
+}
+
+/// bulk 导入结果
+#[derive(Debug, Deserialize, Serialize, Default)]
+pub struct EsBulkResult {
+    pub total: usize,
+
+    pub succeeded: usize,
+
+    pub failed: Vec<EsBulkItemError>,
+}
+
+/// mapping 中的一个字段
+#[derive(Debug, Deserialize, Serialize)]
+pub struct EsMappingField {
+    pub name: String,
+
+    /// 完整路径，如 `user.address.city`
+    pub path: String,
+
+    /// text、keyword、long、date 等，只有 properties 时为 object
+    pub field_type: String,
+
+    pub analyzer: Option<String>,
+
+    pub search_analyzer: Option<String>,
+
+    /// 是否可被搜索，默认 true
+    pub index: bool,
+
+    /// 为空表示使用该类型的默认值
+    pub doc_values: Option<bool>,
+
+    pub format: Option<String>,
+
+    /// multi-fields，如 text 字段下的 `keyword`
+    pub fields: Vec<EsMappingField>,
+
+    /// object / nested 的子字段
+    pub properties: Vec<EsMappingField>,
+}
+
+impl EsMappingField {
+    /// 解析 properties 为字段树，按名称排序
+    pub fn from_properties(properties: &serde_json::Value, prefix: &str) -> Vec<EsMappingField> {
+        let properties = match properties.as_object() {
+            Some(properties) => properties,
+            None => return Vec::new(),
+        };
+        let mut fields: Vec<EsMappingField> = properties
+            .iter()
+            .map(|(name, mapping)| {
+                let path = if prefix.is_empty() {
+                    name.clone()
+                } else {
+                    format!("{}.{}", prefix, name)
+                };
+                let optional_string =
+                    |key: &str| mapping[key].as_str().map(|value| value.to_string());
+                EsMappingField {
+                    name: name.clone(),
+                    field_type: optional_string("type").unwrap_or_else(|| "object".to_string()),
+                    analyzer: optional_string("analyzer"),
+                    search_analyzer: optional_string("search_analyzer"),
+                    index: mapping["index"].as_bool().unwrap_or(true),
+                    doc_values: mapping["doc_values"].as_bool(),
+                    format: optional_string("format"),
+                    fields: EsMappingField::from_properties(&mapping["fields"], &path),
+                    properties: EsMappingField::from_properties(&mapping["properties"], &path),
+                    path,
+                }
+            })
+            .collect();
+        fields.sort_by(|a, b| a.name.cmp(&b.name));
+        fields
+    }
+}
+
+/// 单个索引的 mapping
+#[derive(Debug, Deserialize, Serialize)]
+pub struct EsIndexMapping {
+    pub index: String,
+
+    /// true、false、strict、runtime
+    pub dynamic: Option<String>,
+
+    pub fields: Vec<EsMappingField>,
+}
+
+impl EsIndexMapping {
+    pub fn from_value(index: &str, value: &serde_json::Value) -> Self {
+        let mut mappings = &value["mappings"];
+        // 6.x 及之前 mappings 下还有一层类型名
+        if mappings.get("properties").is_none() {
+            if let Some(type_mapping) = mappings.as_object().and_then(|types| {
+                types
+                    .values()
+                    .find(|mapping| mapping.get("properties").is_some())
+            }) {
+                mappings = type_mapping;
+            }
+        }
+        EsIndexMapping {
+            index: index.to_string(),
+            dynamic: match &mappings["dynamic"] {
+                serde_json::Value::String(dynamic) => Some(dynamic.clone()),
+                serde_json::Value::Bool(dynamic) => Some(dynamic.to_string()),
+                _ => None,
+            },
+            fields: EsMappingField::from_properties(&mappings["properties"], ""),
+        }
+    }
+}
+
+/// _field_caps 中字段的一种类型
+#[derive(Debug, Deserialize, Serialize)]
+pub struct EsFieldCapsType {
+    pub field_type: String,
+
+    pub searchable: bool,
+
+    pub aggregatable: bool,
+
+    /// 字段为该类型的索引，字段只有一种类型时为空
+    pub indices: Vec<String>,
+}
+
+/// _field_caps 中的一个字段
+#[derive(Debug, Deserialize, Serialize)]
+pub struct EsFieldCaps {
+    pub field: String,
+
+    pub types: Vec<EsFieldCapsType>,
+
+    /// 不同索引中类型不一致
+    pub conflict: bool,
+}
+
+impl EsFieldCaps {
+    /// 解析 _field_caps 响应，忽略 `_id` 等元数据字段，按字段名排序
+    pub fn from_response(response: &serde_json::Value) -> Vec<EsFieldCaps> {
+        let fields = match response["fields"].as_object() {
+            Some(fields) => fields,
+            None => return Vec::new(),
+        };
+        let mut caps: Vec<EsFieldCaps> = fields
+            .iter()
+            .filter(|(field, _)| !field.starts_with('_'))
+            .map(|(field, types)| {
+                let types: Vec<EsFieldCapsType> = types
+                    .as_object()
+                    .map(|types| {
+                        types
+                            .iter()
+                            .map(|(field_type, caps)| EsFieldCapsType {
+                                field_type: field_type.clone(),
+                                searchable: caps["searchable"].as_bool().unwrap_or(false),
+                                aggregatable: caps["aggregatable"].as_bool().unwrap_or(false),
+                                indices: caps["indices"]
+                                    .as_array()
+                                    .map(|indices| {
+                                        indices
+                                            .iter()
+                                            .filter_map(|index| index.as_str())
+                                            .map(|index| index.to_string())
+                                            .collect()
+                                    })
+                                    .unwrap_or_default(),
+                            })
+                            .collect()
+                    })
+                    .unwrap_or_default();
+                EsFieldCaps {
+                    field: field.clone(),
+                    conflict: types.len() > 1,
+                    types,
+                }
+            })
+            .collect();
+        caps.sort_by(|a, b| a.field.cmp(&b.field));
+        caps
+    }
+}
+
+/// _analyze 参数，analyzer 和 field 二选一，使用 field 时必须指定 index
+#[derive(Debug, Deserialize, Serialize, Default)]
+pub struct EsAnalyzeOptions {
+    #[serde(default)]
+    pub index: String,
+
+    #[serde(default)]
+    pub analyzer: String,
+
+    #[serde(default)]
+    pub field: String,
+
+    pub text: String,
+}
+
+/// _analyze 返回的分词
+#[derive(Debug, Deserialize, Serialize)]
+pub struct EsAnalyzeToken {
+    pub token: String,
+
+    pub start_offset: u64,
+
+    pub end_offset: u64,
+
+    /// 如 `<ALPHANUM>`、`word`
+    #[serde(rename = "type")]
+    pub token_type: String,
+
+    pub position: u64,
+}
+
+/// `_cat/shards?format=json&bytes=b` 的一行
+#[derive(Debug, Deserialize, Serialize)]
+pub struct EsShardInfo {
+    pub index: String,
+
+    pub shard: u32,
+
+    pub primary: bool,
+
+    /// STARTED、RELOCATING、INITIALIZING、UNASSIGNED
+    pub state: String,
+
+    pub docs: u64,
+
+    /// 存储大小（字节）
+    pub store: u64,
+
+    pub ip: String,
+
+    /// 所在节点，relocating 时为 `源节点 -> ip id 目标节点`
+    pub node: String,
+
+    /// 未分配原因，如 NODE_LEFT、ALLOCATION_FAILED
+    pub unassigned_reason: Option<String>,
+
+    pub unassigned_details: Option<String>,
+}
+
+impl EsShardInfo {
+    pub fn from_cat_row(row: &serde_json::Value) -> Self {
+        let optional_string = |key: &str| {
+            row[key]
+                .as_str()
+                .filter(|value| !value.is_empty())
+                .map(|value| value.to_string())
+        };
+        EsShardInfo {
+            index: json_string(row, "index"),
+            shard: cat_number(row, "shard"),
+            primary: json_string(row, "prirep") == "p",
+            state: json_string(row, "state"),
+            docs: cat_number(row, "docs"),
+            store: cat_number(row, "store"),
+            ip: json_string(row, "ip"),
+            node: json_string(row, "node"),
+            unassigned_reason: optional_string("unassigned.reason"),
+            unassigned_details: optional_string("unassigned.details"),
+        }
+    }
+}
+
+/// 分配决策器的结论
+#[derive(Debug, Deserialize, Serialize)]
+pub struct EsDeciderResult {
+    /// 如 same_shard、disk_threshold、filter
+    pub decider: String,
+
+    /// YES、NO、THROTTLE
+    pub decision: String,
+
+    pub explanation: String,
+}
+
+/// 分片在某个节点上的分配决策
+#[derive(Debug, Deserialize, Serialize)]
+pub struct EsNodeDecision {
+    pub node_name: String,
+
+    /// yes、no、throttled、worse_balance 等
+    pub node_decision: String,
+
+    /// 只包含否决或限流的决策器
+    pub deciders: Vec<EsDeciderResult>,
+}
+
+/// _cluster/allocation/explain 结果
+#[derive(Debug, Deserialize, Serialize)]
+pub struct EsAllocationExplain {
+    pub index: String,
+
+    pub shard: u32,
+
+    pub primary: bool,
+
+    pub current_state: String,
+
+    pub current_node: Option<String>,
+
+    pub unassigned_reason: Option<String>,
+
+    pub unassigned_details: Option<String>,
+
+    /// yes、no、throttled、awaiting_info 等，已分配分片为空
+    pub can_allocate: Option<String>,
+
+    pub allocate_explanation: Option<String>,
+
+    pub node_decisions: Vec<EsNodeDecision>,
+}
+
+impl EsAllocationExplain {
+    pub fn from_response(response: &serde_json::Value) -> Self {
+        let optional_string =
+            |value: &serde_json::Value| value.as_str().map(|value| value.to_string());
+        let node_decisions = response["node_allocation_decisions"]
+            .as_array()
+            .map(|decisions| {
+                decisions
+                    .iter()
+                    .map(|decision| EsNodeDecision {
+                        node_name: json_string(decision, "node_name"),
+                        node_decision: json_string(decision, "node_decision"),
+                        deciders: decision["deciders"]
+                            .as_array()
+                            .map(|deciders| {
+                                deciders
+                                    .iter()
+                                    .map(|decider| EsDeciderResult {
+                                        decider: json_string(decider, "decider"),
+                                        decision: json_string(decider, "decision"),
+                                        explanation: json_string(decider, "explanation"),
+                                    })
+                                    .collect()
+                            })
+                            .unwrap_or_default(),
+                    })
+                    .collect()
+            })
+            .unwrap_or_default();
+        EsAllocationExplain {
+            index: json_string(response, "index"),
+            shard: response["shard"].as_u64().unwrap_or(0) as u32,
+            primary: response["primary"].as_bool().unwrap_or(false),
+            current_state: json_string(response, "current_state"),
+            current_node: optional_string(&response["current_node"]["name"]),
+            unassigned_reason: optional_string(&response["unassigned_info"]["reason"]),
+            unassigned_details: optional_string(&response["unassigned_info"]["details"]),
+            can_allocate: optional_string(&response["can_allocate"]),
+            allocate_explanation: optional_string(&response["allocate_explanation"]),
+            node_decisions,
+        }
+    }
+}
+
+/// 正在运行的任务（GET _tasks）
+#[derive(Debug, Deserialize, Serialize)]
+pub struct EsTaskInfo {
+    /// `节点ID:任务号`
+    pub task_id: String,
+
+    pub node_name: String,
+
+    pub action: String,
+
+    pub description: String,
+
+    pub start_time_ms: u64,
+
+    pub running_time_ms: u64,
+
+    pub cancellable: bool,
+
+    pub parent_task_id: Option<String>,
+}
+
+impl EsTaskInfo {
+    /// 解析 `_tasks?detailed=true`，按运行时间倒序
+    pub fn from_response(response: &serde_json::Value) -> Vec<EsTaskInfo> {
+        let mut tasks = Vec::new();
+        if let Some(nodes) = response["nodes"].as_object() {
+            for node in nodes.values() {
+                let node_name = json_string(node, "name");
+                let Some(node_tasks) = node["tasks"].as_object() else {
+                    continue;
+                };
+                for (task_id, task) in node_tasks {
+                    tasks.push(EsTaskInfo {
+                        task_id: task_id.clone(),
+                        node_name: node_name.clone(),
+                        action: json_string(task, "action"),
+                        description: json_string(task, "description"),
+                        start_time_ms: task["start_time_in_millis"].as_u64().unwrap_or(0),
+                        running_time_ms: task["running_time_in_nanos"].as_u64().unwrap_or(0)
+                            / 1_000_000,
+                        cancellable: task["cancellable"].as_bool().unwrap_or(false),
+                        parent_task_id: task["parent_task_id"]
+                            .as_str()
+                            .map(|parent| parent.to_string()),
+                    });
+                }
+            }
+        }
+        tasks.sort_by_key(|task| std::cmp::Reverse(task.running_time_ms));
+        tasks
+    }
+}
+
+/// 集群配置（flat_settings）
+#[derive(Debug, Deserialize, Serialize, Default)]
+pub struct EsClusterSettings {
+    #[serde(default)]
+    pub persistent: BTreeMap<String, serde_json::Value>,
+
+    #[serde(default)]
+    pub transient: BTreeMap<String, serde_json::Value>,
+
+    /// 仅在 include_defaults 时返回
+    #[serde(default)]
+    pub defaults: BTreeMap<String, serde_json::Value>,
+}
+
+/// 快照仓库
+#[derive(Debug, Deserialize, Serialize)]
+pub struct EsSnapshotRepository {
+    pub name: String,
+
+    /// fs、s3、hdfs、azure、gcs 等
+    pub repository_type: String,
+
+    pub settings: serde_json::Value,
+}
+
+/// 快照信息
+#[derive(Debug, Deserialize, Serialize)]
+pub struct EsSnapshotInfo {
+    pub snapshot: String,
+
+    pub uuid: String,
+
+    /// IN_PROGRESS、SUCCESS、PARTIAL、FAILED
+    pub state: String,
+
+    pub indices: Vec<String>,
+
+    pub start_time: Option<String>,
+
+    pub end_time: Option<String>,
+
+    pub duration_ms: u64,
+
+    pub shards_total: u64,
+
+    pub shards_failed: u64,
+
+    pub shards_successful: u64,
+
+    pub failures: Vec<String>,
+}
+
+impl EsSnapshotInfo {
+    pub fn from_value(value: &serde_json::Value) -> Self {
+        EsSnapshotInfo {
+            snapshot: json_string(value, "snapshot"),
+            uuid: json_string(value, "uuid"),
+            state: json_string(value, "state"),
+            indices: value["indices"]
+                .as_array()
+                .map(|indices| {
+                    indices
+                        .iter()
+                        .filter_map(|index| index.as_str())
+                        .map(|index| index.to_string())
+                        .collect()
+                })
+                .unwrap_or_default(),
+            start_time: value["start_time"].as_str().map(|time| time.to_string()),
+            end_time: value["end_time"].as_str().map(|time| time.to_string()),
+            duration_ms: value["duration_in_millis"].as_u64().unwrap_or(0),
+            shards_total: value["shards"]["total"].as_u64().unwrap_or(0),
+            shards_failed: value["shards"]["failed"].as_u64().unwrap_or(0),
+            shards_successful: value["shards"]["successful"].as_u64().unwrap_or(0),
+            failures: value["failures"]
+                .as_array()
+                .map(|failures| failures.iter().map(failure_reason).collect())
+                .unwrap_or_default(),
+        }
+    }
+}
+
+/// 创建快照参数
+#[derive(Debug, Deserialize, Serialize, Default)]
+pub struct EsSnapshotOptions {
+    /// 为空时备份全部索引
+    #[serde(default)]
+    pub indices: Vec<String>,
+
+    #[serde(default)]
+    pub include_global_state: bool,
+
+    /// 为 false 时后台执行，通过快照列表查看进度
+    #[serde(default)]
+    pub wait_for_completion: bool,
+}
+
+/// 恢复快照参数
+#[derive(Debug, Deserialize, Serialize, Default)]
+pub struct EsRestoreOptions {
+    /// 为空时恢复快照中的全部索引
+    #[serde(default)]
+    pub indices: Vec<String>,
+
+    /// 恢复时重命名，如 pattern `(.+)`、replacement `restored-$1`
+    #[serde(default)]
+    pub rename_pattern: String,
+
+    #[serde(default)]
+    pub rename_replacement: String,
+
+    #[serde(default)]
+    pub include_global_state: bool,
+
+    #[serde(default)]
+    pub wait_for_completion: bool,
 }
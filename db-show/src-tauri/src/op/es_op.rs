@@ -1,76 +1,1315 @@
-use crate::op::es_entity::ClusterHealth;
+use std::collections::BTreeMap;
+use std::fs;
+use std::sync::atomic::{AtomicUsize, Ordering};
+use std::sync::OnceLock;
+use std::time::Duration;
+
+use reqwest::header::{AUTHORIZATION, CONTENT_TYPE};
+use reqwest::{Client, Method, RequestBuilder, StatusCode};
+use serde::de::DeserializeOwned;
+use serde_json::{json, Map, Value};
+
+use crate::config::es_config::{ESUserPassword, EsAuthMode};
+use crate::op::es_entity::{
+    failure_reason, ClusterHealth, EsAcknowledged, EsAliasAction, EsAliasActionType, EsAliasInfo,
+    EsAllocationExplain, EsAnalyzeOptions, EsAnalyzeToken, EsBulkItemError, EsBulkOperation,
+    EsBulkResult, EsByQueryOptions, EsByQueryResult, EsClusterSettings, EsDocument, EsFieldCaps,
+    EsIndexInfo, EsIndexMapping, EsResizeOptions, EsResizeType, EsRestoreOptions, EsSearchOptions,
+    EsSearchResult, EsShardInfo, EsShardsSummary, EsSnapshotInfo, EsSnapshotOptions,
+    EsSnapshotRepository, EsTaskInfo, EsTaskStatus, EsValidateResult, EsWriteOptions,
+    EsWriteResult, NodesStats,
+};
 use crate::resp::resp::Response;
 
+/// 默认请求超时
+const DEFAULT_TIMEOUT: Duration = Duration::from_secs(30);
+
+/// PIT 默认保持时间
+const DEFAULT_KEEP_ALIVE: &str = "1m";
+
+/// bulk 每批最多的操作数
+const BULK_BATCH_SIZE: usize = 1000;
+
+/// bulk 每批请求体的最大字节数
+const BULK_BATCH_BYTES: usize = 5 * 1024 * 1024;
+
+/// 建立连接超时，超时后切换到下一个节点
+const CONNECT_TIMEOUT: Duration = Duration::from_secs(5);
+
+/// ES 7.14 起每个响应都带有该响应头，网关返回的错误页没有
+const ELASTIC_PRODUCT_HEADER: &str = "x-elastic-product";
+
+/// 所有 ES 连接共用的 HTTP 客户端，复用连接池
+fn shared_client() -> &'static Client {
+    static CLIENT: OnceLock<Client> = OnceLock::new();
+    CLIENT.get_or_init(|| {
+        Client::builder()
+            .connect_timeout(CONNECT_TIMEOUT)
+            .timeout(DEFAULT_TIMEOUT)
+            .build()
+            .unwrap_or_default()
+    })
+}
+
+/// 请求使用的认证信息
+enum EsCredential {
+    None,
+    Basic { username: String, password: String },
+    ApiKey(String),
+    Bearer(String),
+}
+
 pub struct EsOperation {
-    pub url: String,
+    client: Client,
+
+    /// 节点地址，如 `http://127.0.0.1:9200`
+    nodes: Vec<String>,
+
+    credential: EsCredential,
+
+    timeout: Duration,
+
+    /// 最近一次可用的节点下标，下次请求优先使用
+    current: AtomicUsize,
 }
 
 impl EsOperation {
-    pub async fn get_index_stats(&self) -> Response<String> {
-        let nodes_stats_url = format!("{}/_cat/indices", self.url);
+    /// 列出索引，pattern 为空时列出全部，如 `logs-*`
+    pub async fn list_indices(&self, pattern: &str) -> Response<Vec<EsIndexInfo>> {
+        let path = if pattern.is_empty() {
+            "_cat/indices?format=json&bytes=b".to_string()
+        } else {
+            format!("_cat/indices/{}?format=json&bytes=b", pattern)
+        };
+        match self.request(Method::GET, &path, None).await {
+            Ok(Value::Array(rows)) => {
+                let mut indices: Vec<EsIndexInfo> =
+                    rows.iter().map(EsIndexInfo::from_cat_row).collect();
+                indices.sort_by(|a, b| a.index.cmp(&b.index));
+                Response::new("操作成功", Some(indices))
+            }
+            Ok(_) => Response::new("操作成功", Some(Vec::new())),
+            Err(err) => Response::from_error(format!("获取索引列表时出错: {}", err)),
+        }
+    }
 
-        match reqwest::get(&nodes_stats_url).await {
-            Ok(response) => {
-                let status_code = response.status().as_u16() as i32;
-                let description = if response.status().is_success() {
-                    "操作成功".to_string()
-                } else {
-                    format!("操作失败，HTTP状态码: {}", status_code)
-                };
+    /// 创建索引，body 为包含 settings/mappings/aliases 的 JSON，可为空
+    pub async fn create_index(&self, index: &str, body: &str) -> Response<EsAcknowledged> {
+        let body = match parse_json_body(body) {
+            Ok(body) => body,
+            Err(err) => return Response::from_error(err),
+        };
+        self.acknowledged(Method::PUT, index, body.as_ref(), "创建索引")
+            .await
+    }
+
+    pub async fn delete_index(&self, index: &str) -> Response<EsAcknowledged> {
+        self.acknowledged(Method::DELETE, index, None, "删除索引")
+            .await
+    }
+
+    pub async fn open_index(&self, index: &str) -> Response<EsAcknowledged> {
+        let path = format!("{}/_open", index);
+        self.acknowledged(Method::POST, &path, None, "打开索引")
+            .await
+    }
+
+    pub async fn close_index(&self, index: &str) -> Response<EsAcknowledged> {
+        let path = format!("{}/_close", index);
+        self.acknowledged(Method::POST, &path, None, "关闭索引")
+            .await
+    }
+
+    pub async fn refresh_index(&self, index: &str) -> Response<EsShardsSummary> {
+        let path = format!("{}/_refresh", index);
+        self.shards_summary(&path, "刷新索引").await
+    }
+
+    pub async fn flush_index(&self, index: &str) -> Response<EsShardsSummary> {
+        let path = format!("{}/_flush", index);
+        self.shards_summary(&path, "flush 索引").await
+    }
+
+    /// 段合并，max_num_segments 为空时由 ES 决定，only_expunge_deletes 只清理已删除文档，两者不能同时指定
+    pub async fn force_merge(
+        &self,
+        index: &str,
+        max_num_segments: Option<u32>,
+        only_expunge_deletes: bool,
+    ) -> Response<EsShardsSummary> {
+        let mut path = format!("{}/_forcemerge", index);
+        match (max_num_segments, only_expunge_deletes) {
+            (Some(_), true) => {
+                return Response::from_error(
+                    "max_num_segments 和 only_expunge_deletes 不能同时指定".to_string(),
+                )
+            }
+            (Some(max_num_segments), false) => {
+                path.push_str(&format!("?max_num_segments={}", max_num_segments))
+            }
+            (None, true) => path.push_str("?only_expunge_deletes=true"),
+            (None, false) => {}
+        }
+        self.shards_summary(&path, "段合并").await
+    }
+
+    /// clone/shrink 为新索引，可选先为源索引加写入锁。
+    /// 执行失败时撤销写入锁；成功后源索引保持只读，需要时将 `index.blocks.write` 设为 null 恢复写入
+    pub async fn resize_index(
+        &self,
+        source: &str,
+        options: &EsResizeOptions,
+    ) -> Response<EsAcknowledged> {
+        let body = match parse_json_body(&options.body) {
+            Ok(body) => body,
+            Err(err) => return Response::from_error(err),
+        };
+        let settings_path = format!("{}/_settings", source);
+        if options.block_source_writes {
+            let settings = json!({ "index.blocks.write": true });
+            if let Err(err) = self
+                .request(Method::PUT, &settings_path, Some(&settings))
+                .await
+            {
+                return Response::from_error(format!("设置源索引只读时出错: {}", err));
+            }
+        }
+        let (action, name) = match options.resize_type {
+            EsResizeType::Clone => ("_clone", "clone 索引"),
+            EsResizeType::Shrink => ("_shrink", "shrink 索引"),
+        };
+        let path = format!("{}/{}/{}", source, action, options.target);
+        let err = match self
+            .request_as::<EsAcknowledged>(Method::POST, &path, body.as_ref())
+            .await
+        {
+            Ok(result) => return Response::new("操作成功", Some(result)),
+            Err(err) => err,
+        };
+        let mut message = format!("{}时出错: {}", name, err);
+        if options.block_source_writes {
+            let settings = json!({ "index.blocks.write": null });
+            if let Err(err) = self
+                .request(Method::PUT, &settings_path, Some(&settings))
+                .await
+            {
+                message.push_str(&format!("，恢复源索引写入时出错: {}", err));
+            }
+        }
+        Response::from_error(message)
+    }
+
+    /// 索引配置（flat_settings），返回 索引名 -> 配置项
+    pub async fn get_index_settings(
+        &self,
+        index: &str,
+    ) -> Response<BTreeMap<String, BTreeMap<String, Value>>> {
+        let path = format!("{}/_settings?flat_settings=true", index);
+        match self.request(Method::GET, &path, None).await {
+            Ok(Value::Object(indices)) => {
+                let settings = indices
+                    .into_iter()
+                    .map(|(index, value)| {
+                        let settings = match value.get("settings") {
+                            Some(Value::Object(settings)) => settings
+                                .iter()
+                                .map(|(key, value)| (key.clone(), value.clone()))
+                                .collect(),
+                            _ => BTreeMap::new(),
+                        };
+                        (index, settings)
+                    })
+                    .collect();
+                Response::new("操作成功", Some(settings))
+            }
+            Ok(_) => Response::new("操作成功", Some(BTreeMap::new())),
+            Err(err) => Response::from_error(format!("获取索引配置时出错: {}", err)),
+        }
+    }
+
+    /// 修改动态配置，如 `{"index.number_of_replicas": 2}`
+    pub async fn update_index_settings(
+        &self,
+        index: &str,
+        settings: &str,
+    ) -> Response<EsAcknowledged> {
+        let settings = match parse_json_body(settings) {
+            Ok(Some(settings)) => settings,
+            Ok(None) => return Response::from_error("settings 不能为空".to_string()),
+            Err(err) => return Response::from_error(err),
+        };
+        let path = format!("{}/_settings", index);
+        self.acknowledged(Method::PUT, &path, Some(&settings), "修改索引配置")
+            .await
+    }
+
+    pub async fn list_aliases(&self) -> Response<Vec<EsAliasInfo>> {
+        match self
+            .request(Method::GET, "_cat/aliases?format=json", None)
+            .await
+        {
+            Ok(Value::Array(rows)) => Response::new(
+                "操作成功",
+                Some(rows.iter().map(EsAliasInfo::from_cat_row).collect()),
+            ),
+            Ok(_) => Response::new("操作成功", Some(Vec::new())),
+            Err(err) => Response::from_error(format!("获取别名列表时出错: {}", err)),
+        }
+    }
+
+    /// 原子地执行一组别名增删
+    pub async fn update_aliases(&self, actions: &[EsAliasAction]) -> Response<EsAcknowledged> {
+        let mut items = Vec::new();
+        for action in actions {
+            let mut item = json!({ "index": action.index, "alias": action.alias });
+            let name = match action.action {
+                EsAliasActionType::Add => {
+                    match parse_json_body(&action.filter) {
+                        Ok(Some(filter)) => item["filter"] = filter,
+                        Ok(None) => {}
+                        Err(err) => return Response::from_error(err),
+                    }
+                    if let Some(is_write_index) = action.is_write_index {
+                        item["is_write_index"] = Value::Bool(is_write_index);
+                    }
+                    "add"
+                }
+                EsAliasActionType::Remove => "remove",
+            };
+            items.push(json!({ name: item }));
+        }
+        let body = json!({ "actions": items });
+        self.acknowledged(Method::POST, "_aliases", Some(&body), "修改别名")
+            .await
+    }
+
+    /// 各节点堆内存、CPU、磁盘和 GC 情况
+    pub async fn get_nodes_stats(&self) -> Response<NodesStats> {
+        match self
+            .request(Method::GET, "_nodes/stats/jvm,os,fs", None)
+            .await
+        {
+            Ok(response) => Response::new("操作成功", Some(NodesStats::from_response(&response))),
+            Err(err) => Response::from_error(format!("获取节点状态时出错: {}", err)),
+        }
+    }
 
-                // 获取节点统计信息
+    pub async fn get_cluster_health(&self) -> Response<ClusterHealth> {
+        match self
+            .request_as::<ClusterHealth>(Method::GET, "_cluster/health", None)
+            .await
+        {
+            Ok(cluster_health) => Response::new("操作成功", Some(cluster_health)),
+            Err(err) => Response::from_error(err),
+        }
+    }
 
-                let nodes_stats = response.text().await.unwrap_or_else(|_| String::new());
+    /// 根据连接配置创建，校验节点地址和认证信息
+    pub fn new(es_user_password: &ESUserPassword) -> Result<Self, String> {
+        let nodes = es_user_password.node_urls();
+        if nodes.is_empty() {
+            return Err("没有可用的 ES 节点地址".to_string());
+        }
 
-                Response::new(description, Some(nodes_stats))
+        let credential = match es_user_password.auth_mode {
+            EsAuthMode::None => EsCredential::None,
+            EsAuthMode::Basic if es_user_password.username.is_empty() => EsCredential::None,
+            EsAuthMode::Basic => EsCredential::Basic {
+                username: es_user_password.username.clone(),
+                password: es_user_password.password.clone(),
+            },
+            EsAuthMode::ApiKey if es_user_password.api_key.is_empty() => {
+                return Err("API key 不能为空".to_string())
             }
-            Err(err) => Response::from_error(format!("请求失败: {}", err)),
+            EsAuthMode::ApiKey => EsCredential::ApiKey(es_user_password.api_key.clone()),
+            EsAuthMode::Bearer if es_user_password.token.is_empty() => {
+                return Err("token 不能为空".to_string())
+            }
+            EsAuthMode::Bearer => EsCredential::Bearer(es_user_password.token.clone()),
+        };
+
+        let timeout = if es_user_password.timeout_secs > 0 {
+            Duration::from_secs(es_user_password.timeout_secs)
+        } else {
+            DEFAULT_TIMEOUT
+        };
+
+        Ok(Self {
+            client: shared_client().clone(),
+            nodes,
+            credential,
+            timeout,
+            current: AtomicUsize::new(0),
+        })
+    }
+
+    /// 执行 _search，支持 from/size 分页以及 search_after + PIT 深度翻页
+    pub async fn search(&self, options: &EsSearchOptions) -> Response<EsSearchResult> {
+        let mut body = match parse_json_object(&options.body) {
+            Ok(body) => body,
+            Err(err) => return Response::from_error(err),
+        };
+        if let Some(from) = options.from {
+            body.insert("from".to_string(), json!(from));
+        }
+        if let Some(size) = options.size {
+            body.insert("size".to_string(), json!(size));
+        }
+        if let Some(search_after) = &options.search_after {
+            body.insert("search_after".to_string(), search_after.clone());
+        }
+        // 使用 PIT 时请求路径不能带索引
+        let path = match &options.pit_id {
+            Some(pit_id) => {
+                body.insert(
+                    "pit".to_string(),
+                    json!({ "id": pit_id, "keep_alive": keep_alive(&options.keep_alive) }),
+                );
+                "_search".to_string()
+            }
+            None => index_path(&options.indices, "_search"),
+        };
+
+        match self
+            .request(Method::POST, &path, Some(&Value::Object(body)))
+            .await
+        {
+            Ok(response) => {
+                Response::new("操作成功", Some(EsSearchResult::from_response(&response)))
+            }
+            Err(err) => Response::from_error(format!("查询时出错: {}", err)),
         }
     }
 
-    pub async fn get_nodes_stats(&self) -> Response<String> {
-        let nodes_stats_url = format!("{}/_nodes/stats", self.url);
+    /// 打开 point-in-time，返回 PIT ID
+    pub async fn open_pit(&self, indices: &[String], keep_alive_time: &str) -> Response<String> {
+        let path = format!(
+            "{}?keep_alive={}",
+            index_path(indices, "_pit"),
+            keep_alive(keep_alive_time)
+        );
+        match self.request(Method::POST, &path, None).await {
+            Ok(response) => match response["id"].as_str() {
+                Some(pit_id) => Response::new("操作成功", Some(pit_id.to_string())),
+                None => Response::from_error("响应中没有 PIT ID".to_string()),
+            },
+            Err(err) => Response::from_error(format!("打开 PIT 时出错: {}", err)),
+        }
+    }
 
-        match reqwest::get(&nodes_stats_url).await {
+    pub async fn close_pit(&self, pit_id: &str) -> Response<bool> {
+        let body = json!({ "id": pit_id });
+        match self.request(Method::DELETE, "_pit", Some(&body)).await {
+            Ok(response) => Response::new(
+                "操作成功",
+                Some(response["succeeded"].as_bool().unwrap_or(false)),
+            ),
+            Err(err) => Response::from_error(format!("关闭 PIT 时出错: {}", err)),
+        }
+    }
+
+    /// 统计匹配文档数，只使用请求体中的 query
+    pub async fn count(&self, indices: &[String], body: &str) -> Response<u64> {
+        let body = match query_only(body) {
+            Ok(body) => body,
+            Err(err) => return Response::from_error(err),
+        };
+        let path = index_path(indices, "_count");
+        match self.request(Method::POST, &path, Some(&body)).await {
             Ok(response) => {
-                let status_code = response.status().as_u16() as i32;
-                let description = if response.status().is_success() {
-                    "操作成功".to_string()
-                } else {
-                    format!("操作失败，HTTP状态码: {}", status_code)
-                };
+                Response::new("操作成功", Some(response["count"].as_u64().unwrap_or(0)))
+            }
+            Err(err) => Response::from_error(format!("统计文档数时出错: {}", err)),
+        }
+    }
+
+    /// 校验查询并返回改写后的 Lucene 查询，只使用请求体中的 query
+    pub async fn validate_query(
+        &self,
+        indices: &[String],
+        body: &str,
+    ) -> Response<EsValidateResult> {
+        let body = match query_only(body) {
+            Ok(body) => body,
+            Err(err) => return Response::from_error(err),
+        };
+        let path = format!("{}?explain=true", index_path(indices, "_validate/query"));
+        match self.request(Method::POST, &path, Some(&body)).await {
+            Ok(response) => {
+                Response::new("操作成功", Some(EsValidateResult::from_response(&response)))
+            }
+            Err(err) => Response::from_error(format!("校验查询时出错: {}", err)),
+        }
+    }
+
+    /// 按 ID 读取文档，不存在时 found 为 false
+    pub async fn get_document(&self, index: &str, id: &str) -> Response<EsDocument> {
+        let path = format!("{}/_doc/{}", index, encode_segment(id));
+        let (status, text) = match self.send(Method::GET, &path, None).await {
+            Ok(response) => response,
+            Err(err) => return Response::from_error(format!("读取文档时出错: {}", err)),
+        };
+        // 文档不存在时同样返回 404，但带有 found: false
+        let document = serde_json::from_str::<Value>(&text)
+            .ok()
+            .filter(|value| value.get("found").is_some());
+        match document {
+            Some(document) if status.is_success() || status == StatusCode::NOT_FOUND => {
+                Response::new("操作成功", Some(EsDocument::from_value(&document)))
+            }
+            _ => Response::from_error(format!(
+                "读取文档时出错: {}",
+                es_error_message(status, &text)
+            )),
+        }
+    }
 
-                // 获取节点统计信息
+    /// 写入文档，id 为空时自动生成；create 为 true 时文档已存在则失败
+    pub async fn index_document(
+        &self,
+        index: &str,
+        id: &str,
+        body: &str,
+        create: bool,
+        options: &EsWriteOptions,
+    ) -> Response<EsWriteResult> {
+        let body = match parse_json_object(body) {
+            Ok(body) => Value::Object(body),
+            Err(err) => return Response::from_error(err),
+        };
+        let (method, path) = match (id.is_empty(), create) {
+            (true, _) => (Method::POST, format!("{}/_doc", index)),
+            (false, true) => (
+                Method::PUT,
+                format!("{}/_create/{}", index, encode_segment(id)),
+            ),
+            (false, false) => (
+                Method::PUT,
+                format!("{}/_doc/{}", index, encode_segment(id)),
+            ),
+        };
+        let path = format!("{}{}", path, write_query(options));
+        self.write_result(method, &path, Some(&body), "写入文档")
+            .await
+    }
 
-                let nodes_stats = response.text().await.unwrap_or_else(|_| String::new());
+    /// 局部更新文档，doc_as_upsert 为 true 时文档不存在则以 doc 创建
+    pub async fn update_document(
+        &self,
+        index: &str,
+        id: &str,
+        doc: &str,
+        doc_as_upsert: bool,
+        options: &EsWriteOptions,
+    ) -> Response<EsWriteResult> {
+        let doc = match parse_json_object(doc) {
+            Ok(doc) => Value::Object(doc),
+            Err(err) => return Response::from_error(err),
+        };
+        let body = json!({ "doc": doc, "doc_as_upsert": doc_as_upsert });
+        let path = format!(
+            "{}/_update/{}{}",
+            index,
+            encode_segment(id),
+            write_query(options)
+        );
+        self.write_result(Method::POST, &path, Some(&body), "更新文档")
+            .await
+    }
 
-                Response::new(description, Some(nodes_stats))
+    pub async fn delete_document(
+        &self,
+        index: &str,
+        id: &str,
+        options: &EsWriteOptions,
+    ) -> Response<EsWriteResult> {
+        let path = format!(
+            "{}/_doc/{}{}",
+            index,
+            encode_segment(id),
+            write_query(options)
+        );
+        self.write_result(Method::DELETE, &path, None, "删除文档")
+            .await
+    }
+
+    pub async fn update_by_query(&self, options: &EsByQueryOptions) -> Response<EsByQueryResult> {
+        self.by_query("_update_by_query", options, "按条件更新")
+            .await
+    }
+
+    pub async fn delete_by_query(&self, options: &EsByQueryOptions) -> Response<EsByQueryResult> {
+        self.by_query("_delete_by_query", options, "按条件删除")
+            .await
+    }
+
+    /// 查询后台任务进度
+    pub async fn get_task(&self, task_id: &str) -> Response<EsTaskStatus> {
+        let path = format!("_tasks/{}", encode_segment(task_id));
+        match self.request(Method::GET, &path, None).await {
+            Ok(response) => Response::new(
+                "操作成功",
+                Some(EsTaskStatus::from_value(task_id, &response)),
+            ),
+            Err(err) => Response::from_error(format!("查询任务时出错: {}", err)),
+        }
+    }
+
+    /// 从 NDJSON 文件批量导入，index 为动作行未指定 _index 时的默认索引，逐项记录失败原因
+    pub async fn bulk_import(
+        &self,
+        index: &str,
+        file_path: &str,
+        refresh: bool,
+    ) -> Response<EsBulkResult> {
+        let content = match fs::read_to_string(file_path) {
+            Ok(content) => content,
+            Err(err) => return Response::from_error(format!("读取导入文件失败: {}", err)),
+        };
+        let operations = match parse_bulk_operations(&content) {
+            Ok(operations) => operations,
+            Err(err) => return Response::from_error(err),
+        };
+        let mut path = if index.is_empty() {
+            "_bulk".to_string()
+        } else {
+            format!("{}/_bulk", index)
+        };
+        if refresh {
+            path.push_str("?refresh=wait_for");
+        }
+
+        let mut result = EsBulkResult {
+            total: operations.len(),
+            ..Default::default()
+        };
+        for batch in bulk_batches(&operations) {
+            let body: String = batch
+                .iter()
+                .map(|operation| operation.payload.as_str())
+                .collect();
+            let response = self
+                .send(Method::POST, &path, Some(("application/x-ndjson", body)))
+                .await
+                .and_then(|(status, text)| {
+                    if status.is_success() {
+                        serde_json::from_str::<Value>(&text)
+                            .map_err(|err| format!("解析响应失败: {}", err))
+                    } else {
+                        Err(es_error_message(status, &text))
+                    }
+                });
+            let items = match response {
+                Ok(response) => response["items"].as_array().cloned().unwrap_or_default(),
+                Err(err) => {
+                    // 整批失败时每一项都记为失败
+                    for operation in batch {
+                        result.failed.push(EsBulkItemError {
+                            line: operation.line,
+                            action: operation.action.clone(),
+                            index: String::new(),
+                            id: String::new(),
+                            status: 0,
+                            error: err.clone(),
+                        });
+                    }
+                    continue;
+                }
+            };
+            for (operation, item) in batch.iter().zip(items.iter()) {
+                let detail = item.get(operation.action.as_str()).unwrap_or(item);
+                match detail.get("error") {
+                    Some(error) => result.failed.push(EsBulkItemError {
+                        line: operation.line,
+                        action: operation.action.clone(),
+                        index: detail["_index"].as_str().unwrap_or_default().to_string(),
+                        id: detail["_id"].as_str().unwrap_or_default().to_string(),
+                        status: detail["status"].as_u64().unwrap_or(0) as u16,
+                        error: failure_reason(error),
+                    }),
+                    None => result.succeeded += 1,
+                }
+            }
+            // 响应中缺少结果的操作无法确认是否写入，记为失败
+            for operation in batch.iter().skip(items.len()) {
+                result.failed.push(EsBulkItemError {
+                    line: operation.line,
+                    action: operation.action.clone(),
+                    index: String::new(),
+                    id: String::new(),
+                    status: 0,
+                    error: "响应中缺少该操作的结果".to_string(),
+                });
             }
-            Err(err) => Response::from_error(format!("请求失败: {}", err)),
+        }
+        Response::new("导入完成", Some(result))
+    }
+
+    /// 索引 mapping，解析为字段树
+    pub async fn get_mapping(&self, index: &str) -> Response<Vec<EsIndexMapping>> {
+        let path = format!("{}/_mapping", index);
+        match self.request(Method::GET, &path, None).await {
+            Ok(Value::Object(indices)) => Response::new(
+                "操作成功",
+                Some(
+                    indices
+                        .iter()
+                        .map(|(index, value)| EsIndexMapping::from_value(index, value))
+                        .collect(),
+                ),
+            ),
+            Ok(_) => Response::new("操作成功", Some(Vec::new())),
+            Err(err) => Response::from_error(format!("获取 mapping 时出错: {}", err)),
         }
     }
-    pub async fn get_cluster_health(&self) -> Response<ClusterHealth> {
-        let cluster_health_url = format!("{}/_cluster/health", self.url);
 
-        match reqwest::get(&cluster_health_url).await {
+    /// 跨索引查看字段类型，fields 为空时查看全部字段，conflicts_only 只返回类型冲突的字段
+    pub async fn field_caps(
+        &self,
+        pattern: &str,
+        fields: &str,
+        conflicts_only: bool,
+    ) -> Response<Vec<EsFieldCaps>> {
+        let fields = if fields.trim().is_empty() {
+            "*"
+        } else {
+            fields.trim()
+        };
+        let path = format!(
+            "{}?fields={}",
+            index_path(&[pattern.to_string()], "_field_caps"),
+            fields
+        );
+        match self.request(Method::GET, &path, None).await {
             Ok(response) => {
-                let status_code = response.status().as_u16() as i32;
-                let description = if response.status().is_success() {
-                    "操作成功".to_string()
+                let mut caps = EsFieldCaps::from_response(&response);
+                if conflicts_only {
+                    caps.retain(|caps| caps.conflict);
+                }
+                Response::new("操作成功", Some(caps))
+            }
+            Err(err) => Response::from_error(format!("获取字段类型时出错: {}", err)),
+        }
+    }
+
+    /// 使用分析器或字段的分析器对文本分词
+    pub async fn analyze(&self, options: &EsAnalyzeOptions) -> Response<Vec<EsAnalyzeToken>> {
+        let mut body = json!({ "text": options.text });
+        if !options.field.is_empty() {
+            if options.index.is_empty() {
+                return Response::from_error("按字段分词时必须指定索引".to_string());
+            }
+            body["field"] = json!(options.field);
+        } else if !options.analyzer.is_empty() {
+            body["analyzer"] = json!(options.analyzer);
+        }
+        let path = index_path(std::slice::from_ref(&options.index), "_analyze");
+        match self.request(Method::POST, &path, Some(&body)).await {
+            Ok(response) => {
+                let tokens = serde_json::from_value(response["tokens"].clone()).unwrap_or_default();
+                Response::new("操作成功", Some(tokens))
+            }
+            Err(err) => Response::from_error(format!("分词时出错: {}", err)),
+        }
+    }
+
+    /// 分片分布，pattern 为空时查看全部索引，unassigned_only 只返回未分配的分片
+    pub async fn list_shards(
+        &self,
+        pattern: &str,
+        unassigned_only: bool,
+    ) -> Response<Vec<EsShardInfo>> {
+        let endpoint = if pattern.is_empty() {
+            "_cat/shards".to_string()
+        } else {
+            format!("_cat/shards/{}", pattern)
+        };
+        let path = format!(
+            "{}?format=json&bytes=b&h={}",
+            endpoint,
+            "index,shard,prirep,state,docs,store,ip,node,unassigned.reason,unassigned.details"
+        );
+        match self.request(Method::GET, &path, None).await {
+            Ok(Value::Array(rows)) => {
+                let mut shards: Vec<EsShardInfo> =
+                    rows.iter().map(EsShardInfo::from_cat_row).collect();
+                if unassigned_only {
+                    shards.retain(|shard| shard.state == "UNASSIGNED");
+                }
+                shards.sort_by(|a, b| {
+                    (&a.index, a.shard, !a.primary).cmp(&(&b.index, b.shard, !b.primary))
+                });
+                Response::new("操作成功", Some(shards))
+            }
+            Ok(_) => Response::new("操作成功", Some(Vec::new())),
+            Err(err) => Response::from_error(format!("获取分片列表时出错: {}", err)),
+        }
+    }
+
+    /// 解释分片为何未分配或为何留在当前节点，index 为空时解释第一个未分配的分片
+    pub async fn allocation_explain(
+        &self,
+        index: &str,
+        shard: u32,
+        primary: bool,
+    ) -> Response<EsAllocationExplain> {
+        let body = json!({ "index": index, "shard": shard, "primary": primary });
+        let body = if index.is_empty() { None } else { Some(&body) };
+        match self
+            .request(Method::POST, "_cluster/allocation/explain", body)
+            .await
+        {
+            Ok(response) => Response::new(
+                "操作成功",
+                Some(EsAllocationExplain::from_response(&response)),
+            ),
+            Err(err) => Response::from_error(format!("获取分配解释时出错: {}", err)),
+        }
+    }
+
+    /// 正在运行的任务，actions 为逗号分隔的动作过滤，如 `*byquery,*search*`
+    pub async fn list_tasks(&self, actions: &str) -> Response<Vec<EsTaskInfo>> {
+        let mut path = "_tasks?detailed=true".to_string();
+        if !actions.trim().is_empty() {
+            path.push_str(&format!("&actions={}", actions.trim()));
+        }
+        match self.request(Method::GET, &path, None).await {
+            Ok(response) => Response::new("操作成功", Some(EsTaskInfo::from_response(&response))),
+            Err(err) => Response::from_error(format!("获取任务列表时出错: {}", err)),
+        }
+    }
+
+    pub async fn cancel_task(&self, task_id: &str) -> Response<bool> {
+        let path = format!("_tasks/{}/_cancel", encode_segment(task_id));
+        match self.request(Method::POST, &path, None).await {
+            Ok(response) => {
+                // 任务不存在或不可取消时错误在 node_failures / task_failures 中
+                let failures = response["node_failures"]
+                    .as_array()
+                    .into_iter()
+                    .chain(response["task_failures"].as_array())
+                    .flatten()
+                    .map(failure_reason)
+                    .collect::<Vec<String>>();
+                if failures.is_empty() {
+                    Response::new("操作成功", Some(true))
                 } else {
-                    format!("操作失败，HTTP状态码: {}", status_code)
-                };
-                let cluster_health = response.text().await.unwrap_or_else(|_| String::new());
+                    Response::from_error(format!("取消任务时出错: {}", failures.join("; ")))
+                }
+            }
+            Err(err) => Response::from_error(format!("取消任务时出错: {}", err)),
+        }
+    }
+
+    pub async fn get_cluster_settings(
+        &self,
+        include_defaults: bool,
+    ) -> Response<EsClusterSettings> {
+        let path = format!(
+            "_cluster/settings?flat_settings=true&include_defaults={}",
+            include_defaults
+        );
+        match self
+            .request_as::<EsClusterSettings>(Method::GET, &path, None)
+            .await
+        {
+            Ok(settings) => Response::new("操作成功", Some(settings)),
+            Err(err) => Response::from_error(format!("获取集群配置时出错: {}", err)),
+        }
+    }
+
+    /// 修改集群配置，settings 为扁平配置项（JSON），值为 null 时恢复默认；返回修改后的配置
+    pub async fn update_cluster_settings(
+        &self,
+        settings: &str,
+        persistent: bool,
+    ) -> Response<EsClusterSettings> {
+        let settings = match parse_json_object(settings) {
+            Ok(settings) if !settings.is_empty() => Value::Object(settings),
+            Ok(_) => return Response::from_error("settings 不能为空".to_string()),
+            Err(err) => return Response::from_error(err),
+        };
+        let scope = if persistent {
+            "persistent"
+        } else {
+            "transient"
+        };
+        let body = json!({ scope: settings });
+        match self
+            .request_as::<EsClusterSettings>(
+                Method::PUT,
+                "_cluster/settings?flat_settings=true",
+                Some(&body),
+            )
+            .await
+        {
+            Ok(settings) => Response::new("操作成功", Some(settings)),
+            Err(err) => Response::from_error(format!("修改集群配置时出错: {}", err)),
+        }
+    }
+
+    pub async fn list_repositories(&self) -> Response<Vec<EsSnapshotRepository>> {
+        match self.request(Method::GET, "_snapshot", None).await {
+            Ok(Value::Object(repositories)) => Response::new(
+                "操作成功",
+                Some(
+                    repositories
+                        .iter()
+                        .map(|(name, repository)| EsSnapshotRepository {
+                            name: name.clone(),
+                            repository_type: repository["type"]
+                                .as_str()
+                                .unwrap_or_default()
+                                .to_string(),
+                            settings: repository["settings"].clone(),
+                        })
+                        .collect(),
+                ),
+            ),
+            Ok(_) => Response::new("操作成功", Some(Vec::new())),
+            Err(err) => Response::from_error(format!("获取快照仓库时出错: {}", err)),
+        }
+    }
 
-                let cluster_health: ClusterHealth =
-                    serde_json::from_str(cluster_health.as_str()).unwrap();
+    /// 注册快照仓库，settings 为仓库配置（JSON），如 `{"location": "/backup"}`
+    pub async fn create_repository(
+        &self,
+        name: &str,
+        repository_type: &str,
+        settings: &str,
+        verify: bool,
+    ) -> Response<EsAcknowledged> {
+        let settings = match parse_json_object(settings) {
+            Ok(settings) => Value::Object(settings),
+            Err(err) => return Response::from_error(err),
+        };
+        let body = json!({ "type": repository_type, "settings": settings });
+        let path = format!("_snapshot/{}?verify={}", encode_segment(name), verify);
+        self.acknowledged(Method::PUT, &path, Some(&body), "创建快照仓库")
+            .await
+    }
 
-                Response::new(description, Some(cluster_health))
+    /// 仓库中的快照，按开始时间倒序
+    pub async fn list_snapshots(&self, repository: &str) -> Response<Vec<EsSnapshotInfo>> {
+        let path = format!("_snapshot/{}/_all", encode_segment(repository));
+        match self.request(Method::GET, &path, None).await {
+            Ok(response) => {
+                let mut snapshots: Vec<EsSnapshotInfo> = response["snapshots"]
+                    .as_array()
+                    .map(|snapshots| snapshots.iter().map(EsSnapshotInfo::from_value).collect())
+                    .unwrap_or_default();
+                snapshots.sort_by(|a, b| b.start_time.cmp(&a.start_time));
+                Response::new("操作成功", Some(snapshots))
             }
-            Err(err) => Response::from_error(format!("请求失败: {}", err)),
+            Err(err) => Response::from_error(format!("获取快照列表时出错: {}", err)),
         }
     }
-    pub fn new(string: String) -> Self {
-        Self { url: string }
+
+    pub async fn create_snapshot(
+        &self,
+        repository: &str,
+        snapshot: &str,
+        options: &EsSnapshotOptions,
+    ) -> Response<bool> {
+        let mut body = json!({ "include_global_state": options.include_global_state });
+        if !options.indices.is_empty() {
+            body["indices"] = json!(options.indices.join(","));
+        }
+        let path = format!(
+            "_snapshot/{}/{}?wait_for_completion={}",
+            encode_segment(repository),
+            encode_segment(snapshot),
+            options.wait_for_completion
+        );
+        match self.request(Method::PUT, &path, Some(&body)).await {
+            Ok(_) => Response::new("操作成功", Some(true)),
+            Err(err) => Response::from_error(format!("创建快照时出错: {}", err)),
+        }
     }
+
+    /// 恢复快照，目标索引已存在且打开时需先关闭或通过重命名恢复到新索引
+    pub async fn restore_snapshot(
+        &self,
+        repository: &str,
+        snapshot: &str,
+        options: &EsRestoreOptions,
+    ) -> Response<bool> {
+        let mut body = json!({ "include_global_state": options.include_global_state });
+        if !options.indices.is_empty() {
+            body["indices"] = json!(options.indices.join(","));
+        }
+        if !options.rename_pattern.is_empty() {
+            body["rename_pattern"] = json!(options.rename_pattern);
+            body["rename_replacement"] = json!(options.rename_replacement);
+        }
+        let path = format!(
+            "_snapshot/{}/{}/_restore?wait_for_completion={}",
+            encode_segment(repository),
+            encode_segment(snapshot),
+            options.wait_for_completion
+        );
+        match self.request(Method::POST, &path, Some(&body)).await {
+            Ok(_) => Response::new("操作成功", Some(true)),
+            Err(err) => Response::from_error(format!("恢复快照时出错: {}", err)),
+        }
+    }
+
+    async fn write_result(
+        &self,
+        method: Method,
+        path: &str,
+        body: Option<&Value>,
+        action: &str,
+    ) -> Response<EsWriteResult> {
+        match self.request(method, path, body).await {
+            Ok(response) => Response::new("操作成功", Some(EsWriteResult::from_value(&response))),
+            Err(err) => Response::from_error(format!("{}时出错: {}", action, err)),
+        }
+    }
+
+    async fn by_query(
+        &self,
+        endpoint: &str,
+        options: &EsByQueryOptions,
+        action: &str,
+    ) -> Response<EsByQueryResult> {
+        let body = match parse_json_object(&options.body) {
+            Ok(body) => Value::Object(body),
+            Err(err) => return Response::from_error(err),
+        };
+        let mut params = vec![format!(
+            "wait_for_completion={}",
+            options.wait_for_completion
+        )];
+        if options.conflicts_proceed {
+            params.push("conflicts=proceed".to_string());
+        }
+        if let Some(max_docs) = options.max_docs {
+            params.push(format!("max_docs={}", max_docs));
+        }
+        if options.refresh {
+            params.push("refresh=true".to_string());
+        }
+        let path = format!(
+            "{}?{}",
+            index_path(&options.indices, endpoint),
+            params.join("&")
+        );
+        match self.request(Method::POST, &path, Some(&body)).await {
+            Ok(response) => Response::new("操作成功", Some(EsByQueryResult::from_value(&response))),
+            Err(err) => Response::from_error(format!("{}时出错: {}", action, err)),
+        }
+    }
+
+    /// 执行返回 acknowledged 的请求
+    async fn acknowledged(
+        &self,
+        method: Method,
+        path: &str,
+        body: Option<&Value>,
+        action: &str,
+    ) -> Response<EsAcknowledged> {
+        match self.request_as::<EsAcknowledged>(method, path, body).await {
+            Ok(result) => Response::new("操作成功", Some(result)),
+            Err(err) => Response::from_error(format!("{}时出错: {}", action, err)),
+        }
+    }
+
+    /// 执行返回 _shards 的 POST 请求
+    async fn shards_summary(&self, path: &str, action: &str) -> Response<EsShardsSummary> {
+        match self.request(Method::POST, path, None).await {
+            Ok(value) => {
+                let summary = value
+                    .get("_shards")
+                    .and_then(|shards| serde_json::from_value(shards.clone()).ok())
+                    .unwrap_or_default();
+                Response::new("操作成功", Some(summary))
+            }
+            Err(err) => Response::from_error(format!("{}时出错: {}", action, err)),
+        }
+    }
+
+    /// 节点地址列表
+    pub fn nodes(&self) -> &[String] {
+        &self.nodes
+    }
+
+    /// 发送 JSON 请求并解析响应，非 2xx 时返回 ES 的错误原因
+    pub async fn request(
+        &self,
+        method: Method,
+        path: &str,
+        body: Option<&Value>,
+    ) -> Result<Value, String> {
+        let body = body.map(|body| ("application/json", body.to_string()));
+        let (status, text) = self.send(method, path, body).await?;
+        if !status.is_success() {
+            return Err(es_error_message(status, &text));
+        }
+        if text.trim().is_empty() {
+            return Ok(Value::Null);
+        }
+        serde_json::from_str(&text).map_err(|err| format!("解析响应失败: {}", err))
+    }
+
+    /// 发送 JSON 请求并反序列化为指定类型
+    pub async fn request_as<T: DeserializeOwned>(
+        &self,
+        method: Method,
+        path: &str,
+        body: Option<&Value>,
+    ) -> Result<T, String> {
+        let value = self.request(method, path, body).await?;
+        serde_json::from_value(value).map_err(|err| format!("解析响应失败: {}", err))
+    }
+
+    /// 发送请求，body 为 (Content-Type, 内容)。
+    /// GET/HEAD 在连接失败、超时或返回 502/503/504 时依次切换到下一个节点；
+    /// 其他方法只在连接失败或 ES 自身返回 503 时切换，网关的 502/504 可能意味着写请求仍在执行，
+    /// 切换会导致重复写入。
+    async fn send(
+        &self,
+        method: Method,
+        path: &str,
+        body: Option<(&str, String)>,
+    ) -> Result<(StatusCode, String), String> {
+        let start = self.current.load(Ordering::Relaxed);
+        let idempotent = method == Method::GET || method == Method::HEAD;
+        let mut last_error = String::new();
+
+        for offset in 0..self.nodes.len() {
+            let index = (start + offset) % self.nodes.len();
+            let node = &self.nodes[index];
+            let url = format!("{}/{}", node, path.trim_start_matches('/'));
+            let mut request = self
+                .authorize(self.client.request(method.clone(), url))
+                .timeout(self.timeout);
+            if let Some((content_type, body)) = &body {
+                request = request
+                    .header(CONTENT_TYPE, *content_type)
+                    .body(body.clone());
+            }
+
+            match request.send().await {
+                Ok(response) => {
+                    let status = response.status();
+                    let from_es = response.headers().contains_key(ELASTIC_PRODUCT_HEADER);
+                    let text = response
+                        .text()
+                        .await
+                        .map_err(|err| format!("读取响应失败: {}", err))?;
+                    let failover = if idempotent {
+                        is_unavailable(status)
+                    } else {
+                        status == StatusCode::SERVICE_UNAVAILABLE && (from_es || is_es_error(&text))
+                    };
+                    if failover {
+                        last_error = format!("{} 返回 HTTP {}", node, status.as_u16());
+                        continue;
+                    }
+                    self.current.store(index, Ordering::Relaxed);
+                    return Ok((status, text));
+                }
+                Err(err) if err.is_connect() || (idempotent && err.is_timeout()) => {
+                    last_error = format!("{} 不可用: {}", node, err);
+                }
+                Err(err) => return Err(format!("请求失败: {}", err)),
+            }
+        }
+        Err(format!("所有节点均不可用: {}", last_error))
+    }
+
+    fn authorize(&self, request: RequestBuilder) -> RequestBuilder {
+        match &self.credential {
+            EsCredential::None => request,
+            EsCredential::Basic { username, password } => {
+                request.basic_auth(username, Some(password))
+            }
+            EsCredential::ApiKey(api_key) => {
+                request.header(AUTHORIZATION, format!("ApiKey {}", api_key))
+            }
+            EsCredential::Bearer(token) => request.bearer_auth(token),
+        }
+    }
+}
+
+/// 解析 JSON 请求体，空字符串返回 None
+pub fn parse_json_body(body: &str) -> Result<Option<Value>, String> {
+    if body.trim().is_empty() {
+        return Ok(None);
+    }
+    serde_json::from_str(body)
+        .map(Some)
+        .map_err(|err| format!("JSON 格式错误: {}", err))
+}
+
+/// 解析 JSON 对象请求体，空字符串返回空对象
+fn parse_json_object(body: &str) -> Result<Map<String, Value>, String> {
+    match parse_json_body(body)? {
+        None => Ok(Map::new()),
+        Some(Value::Object(body)) => Ok(body),
+        Some(_) => Err("请求体必须是 JSON 对象".to_string()),
+    }
+}
+
+/// 只保留请求体中的 query，_count 和 _validate 不接受其他字段
+fn query_only(body: &str) -> Result<Value, String> {
+    let body = parse_json_object(body)?;
+    Ok(match body.get("query") {
+        Some(query) => json!({ "query": query }),
+        None => json!({}),
+    })
+}
+
+/// 拼接 `索引1,索引2/接口`，索引为空时直接访问接口
+fn index_path(indices: &[String], endpoint: &str) -> String {
+    let indices: Vec<&str> = indices
+        .iter()
+        .map(|index| index.trim())
+        .filter(|index| !index.is_empty())
+        .collect();
+    if indices.is_empty() {
+        endpoint.to_string()
+    } else {
+        format!("{}/{}", indices.join(","), endpoint)
+    }
+}
+
+fn keep_alive(keep_alive: &str) -> &str {
+    if keep_alive.trim().is_empty() {
+        DEFAULT_KEEP_ALIVE
+    } else {
+        keep_alive.trim()
+    }
+}
+
+/// 按行解析 bulk NDJSON，delete 只有动作行，其余动作后跟一行文档
+pub fn parse_bulk_operations(content: &str) -> Result<Vec<EsBulkOperation>, String> {
+    let mut operations = Vec::new();
+    let mut lines = content
+        .lines()
+        .enumerate()
+        .filter(|(_, line)| !line.trim().is_empty());
+    while let Some((index, line)) = lines.next() {
+        let line_number = index + 1;
+        let action_line: Value = serde_json::from_str(line)
+            .map_err(|err| format!("第 {} 行不是合法的 JSON: {}", line_number, err))?;
+        let action = match action_line.as_object() {
+            Some(action) if action.len() == 1 => action.keys().next().cloned().unwrap_or_default(),
+            _ => return Err(format!("第 {} 行不是合法的 bulk 动作", line_number)),
+        };
+        let mut payload = format!("{}\n", line.trim());
+        match action.as_str() {
+            "delete" => {}
+            "index" | "create" | "update" => match lines.next() {
+                Some((_, source)) => {
+                    payload.push_str(source.trim());
+                    payload.push('\n');
+                }
+                None => {
+                    return Err(format!(
+                        "第 {} 行的 {} 动作缺少文档内容",
+                        line_number, action
+                    ))
+                }
+            },
+            _ => return Err(format!("第 {} 行的动作 {} 不支持", line_number, action)),
+        }
+        operations.push(EsBulkOperation {
+            line: line_number,
+            action,
+            payload,
+        });
+    }
+    Ok(operations)
+}
+
+/// 按操作数和请求体大小切分批次
+fn bulk_batches(operations: &[EsBulkOperation]) -> Vec<&[EsBulkOperation]> {
+    let mut batches = Vec::new();
+    let mut start = 0;
+    let mut bytes = 0;
+    for (index, operation) in operations.iter().enumerate() {
+        let count = index - start;
+        if count > 0
+            && (count >= BULK_BATCH_SIZE || bytes + operation.payload.len() > BULK_BATCH_BYTES)
+        {
+            batches.push(&operations[start..index]);
+            start = index;
+            bytes = 0;
+        }
+        bytes += operation.payload.len();
+    }
+    if start < operations.len() {
+        batches.push(&operations[start..]);
+    }
+    batches
+}
+
+/// 单文档写入的查询参数
+fn write_query(options: &EsWriteOptions) -> String {
+    let mut params = Vec::new();
+    if options.refresh {
+        params.push("refresh=wait_for".to_string());
+    }
+    if let Some(if_seq_no) = options.if_seq_no {
+        params.push(format!("if_seq_no={}", if_seq_no));
+    }
+    if let Some(if_primary_term) = options.if_primary_term {
+        params.push(format!("if_primary_term={}", if_primary_term));
+    }
+    if params.is_empty() {
+        String::new()
+    } else {
+        format!("?{}", params.join("&"))
+    }
+}
+
+/// 对路径中的文档 ID 等做百分号编码
+fn encode_segment(value: &str) -> String {
+    let mut encoded = String::with_capacity(value.len());
+    for byte in value.bytes() {
+        match byte {
+            b'A'..=b'Z' | b'a'..=b'z' | b'0'..=b'9' | b'-' | b'.' | b'_' | b'~' | b':' => {
+                encoded.push(byte as char)
+            }
+            _ => encoded.push_str(&format!("%{:02X}", byte)),
+        }
+    }
+    encoded
+}
+
+/// 节点暂时不可用，可以换节点重试
+fn is_unavailable(status: StatusCode) -> bool {
+    matches!(
+        status,
+        StatusCode::BAD_GATEWAY | StatusCode::SERVICE_UNAVAILABLE | StatusCode::GATEWAY_TIMEOUT
+    )
+}
+
+/// 是否为 ES 返回的错误响应（`{"error": {...}, "status": ...}`），用于区分网关返回的错误页
+fn is_es_error(text: &str) -> bool {
+    serde_json::from_str::<Value>(text)
+        .map(|value| value["error"].is_object() && value["status"].is_u64())
+        .unwrap_or(false)
+}
+
+/// 提取 ES 错误响应中的 error.type 和 error.reason
+pub fn es_error_message(status: StatusCode, text: &str) -> String {
+    let reason =
+        serde_json::from_str::<Value>(text)
+            .ok()
+            .and_then(|value| match value.get("error")? {
+                Value::String(error) => Some(error.clone()),
+                error => Some(format!(
+                    "{}: {}",
+                    error["type"].as_str().unwrap_or_default(),
+                    error["reason"].as_str().unwrap_or_default()
+                )),
+            });
+    format!(
+        "操作失败，HTTP状态码: {}，{}",
+        status.as_u16(),
+        reason.unwrap_or_else(|| text.to_string())
+    )
 }
@@ -5,6 +5,7 @@ pub mod mongo_entity;
 pub mod mongo_op;
 pub mod mqtt_op;
 pub mod mysql_op;
+pub mod redis_entity;
 pub mod redis_op;
 pub mod zk_op;
 mod rabbit_op;
@@ -1,4 +1,11 @@
+use std::collections::BTreeMap;
+
+use chrono::Local;
+use mongodb::bson::{doc, Bson, Document};
+use mongodb::change_stream::event::ChangeStreamEvent;
 use serde::{Deserialize, Serialize};
+use tokio::sync::oneshot;
+use tokio::task::JoinHandle;
 
 #[derive(Deserialize, Serialize, Debug)]
 pub struct MongoServerInfo {
@@ -11,52 +18,187 @@ pub struct MongoServerInfo {
     pub uptime_estimate: i64,
 }
 
+impl MongoServerInfo {
+    pub fn from_document(document: &Document) -> Self {
+        MongoServerInfo {
+            host: document.get_str("host").unwrap_or_default().to_string(),
+            version: document.get_str("version").unwrap_or_default().to_string(),
+            process: document.get_str("process").unwrap_or_default().to_string(),
+            pid: document_i64(document, "pid").unwrap_or(0),
+            uptime: document_f64(document, "uptime").unwrap_or(0.0),
+            uptime_millis: document_i64(document, "uptimeMillis").unwrap_or(0),
+            uptime_estimate: document_i64(document, "uptimeEstimate").unwrap_or(0),
+        }
+    }
+}
+
 /// 结构体表示 MongoDB 服务器的内存信息
 #[derive(Deserialize, Serialize, Debug)]
 pub struct MongoMemInfo {
     /// MongoDB 服务器运行的位数
-    pub bits: i32,
+    pub bits: i64,
 
     /// MongoDB 进程当前占用的物理内存大小（以兆字节为单位）
-    pub resident: i32,
+    pub resident: Option<i64>,
 
     /// MongoDB 进程的虚拟内存大小（以兆字节为单位）
     #[serde(rename = "virtual")]
-    pub virtual_memory: i32,
+    pub virtual_memory: Option<i64>,
 
     /// 服务器是否支持内存统计信息
     pub supported: bool,
 }
 
-///  MongoDB 服务器的连接信息
+impl MongoMemInfo {
+    pub fn from_document(document: &Document) -> Self {
+        MongoMemInfo {
+            bits: document_i64(document, "bits").unwrap_or(0),
+            resident: document_i64(document, "resident"),
+            virtual_memory: document_i64(document, "virtual"),
+            supported: document.get_bool("supported").unwrap_or(false),
+        }
+    }
+}
+
+///  MongoDB 服务器的连接信息，部分字段随服务端版本不同可能不存在
 #[derive(Deserialize, Serialize, Debug)]
 pub struct MongoConnectionsInfo {
     /// 当前活动连接数
-    pub current: i32,
+    pub current: i64,
 
     /// 可用连接数
-    pub available: i32,
+    pub available: i64,
 
     /// 总创建的连接数
-    pub total_created: i32,
+    pub total_created: i64,
 
     /// 被拒绝的连接数
-    pub rejected: i32,
+    pub rejected: Option<i64>,
 
     /// 当前活跃的连接数
-    pub active: i32,
+    pub active: Option<i64>,
 
     /// 线程连接数
-    pub threaded: i32,
+    pub threaded: Option<i64>,
 
     /// 消耗 IsMaster 操作的连接数
-    pub exhaust_is_master: i32,
+    pub exhaust_is_master: Option<i64>,
 
     /// 消耗 Hello 操作的连接数
-    pub exhaust_hello: i32,
+    pub exhaust_hello: Option<i64>,
 
     /// 等待拓扑变化的连接数
-    pub awaiting_topology_changes: i32,
+    pub awaiting_topology_changes: Option<i64>,
+}
+
+impl MongoConnectionsInfo {
+    pub fn from_document(document: &Document) -> Self {
+        MongoConnectionsInfo {
+            current: document_i64(document, "current").unwrap_or(0),
+            available: document_i64(document, "available").unwrap_or(0),
+            total_created: document_i64(document, "totalCreated").unwrap_or(0),
+            rejected: document_i64(document, "rejected"),
+            active: document_i64(document, "active"),
+            threaded: document_i64(document, "threaded"),
+            exhaust_is_master: document_i64(document, "exhaustIsMaster"),
+            exhaust_hello: document_i64(document, "exhaustHello"),
+            awaiting_topology_changes: document_i64(document, "awaitingTopologyChanges"),
+        }
+    }
+}
+
+/// 自启动以来各类操作的累计次数
+#[derive(Deserialize, Serialize, Debug, Default)]
+pub struct MongoOpcounters {
+    pub insert: i64,
+    pub query: i64,
+    pub update: i64,
+    pub delete: i64,
+    pub getmore: i64,
+    pub command: i64,
+}
+
+impl MongoOpcounters {
+    pub fn from_document(document: &Document) -> Self {
+        MongoOpcounters {
+            insert: document_i64(document, "insert").unwrap_or(0),
+            query: document_i64(document, "query").unwrap_or(0),
+            update: document_i64(document, "update").unwrap_or(0),
+            delete: document_i64(document, "delete").unwrap_or(0),
+            getmore: document_i64(document, "getmore").unwrap_or(0),
+            command: document_i64(document, "command").unwrap_or(0),
+        }
+    }
+}
+
+/// 网络流量统计
+#[derive(Deserialize, Serialize, Debug, Default)]
+pub struct MongoNetworkInfo {
+    pub bytes_in: i64,
+    pub bytes_out: i64,
+    pub physical_bytes_in: Option<i64>,
+    pub physical_bytes_out: Option<i64>,
+    pub num_requests: i64,
+}
+
+impl MongoNetworkInfo {
+    pub fn from_document(document: &Document) -> Self {
+        MongoNetworkInfo {
+            bytes_in: document_i64(document, "bytesIn").unwrap_or(0),
+            bytes_out: document_i64(document, "bytesOut").unwrap_or(0),
+            physical_bytes_in: document_i64(document, "physicalBytesIn"),
+            physical_bytes_out: document_i64(document, "physicalBytesOut"),
+            num_requests: document_i64(document, "numRequests").unwrap_or(0),
+        }
+    }
+}
+
+/// WiredTiger 缓存使用情况
+#[derive(Deserialize, Serialize, Debug, Default)]
+pub struct MongoWiredTigerCache {
+    /// 当前缓存占用字节数
+    pub bytes_in_cache: i64,
+
+    /// 配置的缓存上限字节数
+    pub maximum_bytes: i64,
+
+    /// 缓存中的脏数据字节数
+    pub dirty_bytes: i64,
+
+    pub pages_read_into_cache: i64,
+
+    pub pages_written_from_cache: i64,
+
+    /// 缓存使用率（百分比）
+    pub usage_percent: f64,
+
+    /// 脏数据占比（百分比）
+    pub dirty_percent: f64,
+}
+
+impl MongoWiredTigerCache {
+    pub fn from_document(document: &Document) -> Self {
+        let bytes_in_cache = document_i64(document, "bytes currently in the cache").unwrap_or(0);
+        let maximum_bytes = document_i64(document, "maximum bytes configured").unwrap_or(0);
+        let dirty_bytes = document_i64(document, "tracked dirty bytes in the cache").unwrap_or(0);
+        let ratio = |bytes: i64| {
+            if maximum_bytes > 0 {
+                bytes as f64 * 100.0 / maximum_bytes as f64
+            } else {
+                0.0
+            }
+        };
+        MongoWiredTigerCache {
+            bytes_in_cache,
+            maximum_bytes,
+            dirty_bytes,
+            pages_read_into_cache: document_i64(document, "pages read into cache").unwrap_or(0),
+            pages_written_from_cache: document_i64(document, "pages written from cache")
+                .unwrap_or(0),
+            usage_percent: ratio(bytes_in_cache),
+            dirty_percent: ratio(dirty_bytes),
+        }
+    }
 }
 
 #[derive(Deserialize, Serialize, Debug)]
@@ -64,4 +206,1432 @@ pub struct MongoServerInfoCol {
     pub mongo_server_info: MongoServerInfo,
     pub mongo_mem_info: MongoMemInfo,
     pub mongo_connections_info: MongoConnectionsInfo,
+    pub mongo_opcounters: MongoOpcounters,
+    pub mongo_network_info: MongoNetworkInfo,
+    /// 非 WiredTiger 存储引擎时为空
+    pub mongo_wired_tiger_cache: Option<MongoWiredTigerCache>,
+}
+
+impl MongoServerInfoCol {
+    /// 解析 serverStatus 结果，缺失的字段或分段使用默认值
+    pub fn from_server_status(status: &Document) -> Self {
+        let section = |name: &str| status.get_document(name).ok();
+        let empty = Document::new();
+        MongoServerInfoCol {
+            mongo_server_info: MongoServerInfo::from_document(status),
+            mongo_mem_info: MongoMemInfo::from_document(section("mem").unwrap_or(&empty)),
+            mongo_connections_info: MongoConnectionsInfo::from_document(
+                section("connections").unwrap_or(&empty),
+            ),
+            mongo_opcounters: section("opcounters")
+                .map(MongoOpcounters::from_document)
+                .unwrap_or_default(),
+            mongo_network_info: section("network")
+                .map(MongoNetworkInfo::from_document)
+                .unwrap_or_default(),
+            mongo_wired_tiger_cache: section("wiredTiger")
+                .and_then(|wired_tiger| wired_tiger.get_document("cache").ok())
+                .map(MongoWiredTigerCache::from_document),
+        }
+    }
+}
+
+/// 文档查询参数，filter / projection / sort 均为扩展 JSON 字符串，为空时表示不限制
+#[derive(Deserialize, Serialize, Debug, Default)]
+pub struct MongoFindOptions {
+    #[serde(default)]
+    pub filter: String,
+
+    #[serde(default)]
+    pub projection: String,
+
+    #[serde(default)]
+    pub sort: String,
+
+    #[serde(default)]
+    pub skip: u64,
+
+    /// 每页条数，小于等于 0 时使用默认值
+    #[serde(default)]
+    pub limit: i64,
+
+    /// 服务端最大执行时间（毫秒），0 表示不限制
+    #[serde(default)]
+    pub max_time_ms: u64,
+
+    /// 是否精确计数，否则在没有过滤条件时使用 estimatedDocumentCount
+    #[serde(default)]
+    pub exact_count: bool,
+
+    /// 游标分页：上一页最后一条文档的 _id（扩展 JSON），设置后忽略 skip 并按 _id 排序
+    #[serde(default)]
+    pub after: Option<serde_json::Value>,
+}
+
+/// 文档查询结果，文档以 canonical 扩展 JSON 返回
+#[derive(Deserialize, Serialize, Debug)]
+pub struct MongoFindResult {
+    pub documents: Vec<serde_json::Value>,
+
+    /// 满足过滤条件的文档总数
+    pub total: u64,
+
+    /// total 是否为精确值
+    pub exact: bool,
+
+    /// 是否还有下一页
+    pub has_more: bool,
+
+    /// 下一页游标，即本页最后一条文档的 _id
+    pub next_cursor: Option<serde_json::Value>,
+}
+
+/// 文档写操作结果，_id 均以 canonical 扩展 JSON 返回
+#[derive(Deserialize, Serialize, Debug, Default)]
+pub struct MongoWriteResult {
+    /// 新插入文档的 _id，按输入顺序排列
+    pub inserted_ids: Vec<serde_json::Value>,
+
+    pub matched_count: u64,
+
+    pub modified_count: u64,
+
+    pub deleted_count: u64,
+
+    /// upsert 时新建文档的 _id
+    pub upserted_id: Option<serde_json::Value>,
+
+    /// 是否为预演，预演时 deleted_count 为将被删除的文档数
+    pub dry_run: bool,
+}
+
+/// 聚合管道执行参数，pipeline 为扩展 JSON 数组
+#[derive(Deserialize, Serialize, Debug, Default)]
+pub struct MongoAggregateOptions {
+    pub pipeline: String,
+
+    #[serde(default)]
+    pub allow_disk_use: bool,
+
+    /// 服务端最大执行时间（毫秒），0 表示不限制
+    #[serde(default)]
+    pub max_time_ms: u64,
+
+    /// 预览到第 N 个阶段（从 0 开始）的输出，为空时执行完整管道
+    #[serde(default)]
+    pub preview_stage: Option<usize>,
+
+    /// 结果条数上限，预览时小于等于 0 则使用默认值，完整执行时小于等于 0 表示不限制
+    #[serde(default)]
+    pub limit: i64,
+}
+
+/// 聚合管道执行结果
+#[derive(Deserialize, Serialize, Debug)]
+pub struct MongoAggregateResult {
+    pub documents: Vec<serde_json::Value>,
+
+    /// 管道总阶段数
+    pub stage_count: usize,
+
+    /// 实际执行的阶段数（不含追加的 $limit）
+    pub executed_stages: usize,
+}
+
+/// 字段上观察到的 BSON 类型及出现次数
+#[derive(Deserialize, Serialize, Debug)]
+pub struct MongoFieldType {
+    /// 类型名称，与 $type 别名一致，如 string、int、objectId
+    pub bson_type: String,
+
+    pub count: u64,
+
+    /// 占该字段出现次数的百分比
+    pub percentage: f64,
+}
+
+/// 推断出的字段结构，数组元素以 `[]` 作为字段名
+#[derive(Deserialize, Serialize, Debug)]
+pub struct MongoSchemaField {
+    pub name: String,
+
+    /// 完整路径，如 `items.[].price`
+    pub path: String,
+
+    /// 字段出现次数
+    pub count: u64,
+
+    /// 在父级文档中出现的百分比；对 `[]` 而言为非空数组所占的百分比
+    pub presence: f64,
+
+    pub types: Vec<MongoFieldType>,
+
+    /// 数值或日期的最小值（relaxed 扩展 JSON），数值优先
+    pub min: Option<serde_json::Value>,
+
+    pub max: Option<serde_json::Value>,
+
+    /// 示例值（relaxed 扩展 JSON）
+    pub examples: Vec<serde_json::Value>,
+
+    pub children: Vec<MongoSchemaField>,
+}
+
+/// 集合结构分析报告
+#[derive(Deserialize, Serialize, Debug)]
+pub struct MongoSchemaReport {
+    pub database: String,
+
+    pub collection: String,
+
+    /// 实际参与分析的文档数
+    pub sample_size: u64,
+
+    pub fields: Vec<MongoSchemaField>,
+}
+
+/// 每个字段保留的示例值个数
+const MAX_SCHEMA_EXAMPLES: usize = 3;
+
+/// 示例字符串的最大长度
+const MAX_EXAMPLE_STRING_LEN: usize = 200;
+
+/// 根据抽样文档逐个累计字段统计，最后生成字段树
+#[derive(Default)]
+pub struct MongoSchemaAnalyzer {
+    root: SchemaStats,
+}
+
+impl MongoSchemaAnalyzer {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    pub fn observe(&mut self, document: &Document) {
+        self.root.observe_document(document);
+    }
+
+    pub fn document_count(&self) -> u64 {
+        self.root.document_count
+    }
+
+    pub fn into_fields(self) -> Vec<MongoSchemaField> {
+        self.root.children_fields("")
+    }
+}
+
+#[derive(Default)]
+struct SchemaStats {
+    count: u64,
+    types: BTreeMap<&'static str, u64>,
+    number_min: Option<(f64, Bson)>,
+    number_max: Option<(f64, Bson)>,
+    date_min: Option<i64>,
+    date_max: Option<i64>,
+    examples: Vec<serde_json::Value>,
+    /// 值为文档的次数，即子字段的父级计数
+    document_count: u64,
+    /// 值为数组的次数
+    array_count: u64,
+    non_empty_arrays: u64,
+    children: Vec<(String, SchemaStats)>,
+}
+
+impl SchemaStats {
+    fn observe(&mut self, value: &Bson) {
+        self.count += 1;
+        *self.types.entry(bson_type_name(value)).or_insert(0) += 1;
+        match value {
+            Bson::Document(document) => self.observe_document(document),
+            Bson::Array(items) => {
+                self.array_count += 1;
+                if !items.is_empty() {
+                    self.non_empty_arrays += 1;
+                }
+                for item in items {
+                    self.child("[]").observe(item);
+                }
+            }
+            Bson::Int32(_) | Bson::Int64(_) | Bson::Double(_) => {
+                let number = match value {
+                    Bson::Int32(v) => *v as f64,
+                    Bson::Int64(v) => *v as f64,
+                    Bson::Double(v) => *v,
+                    _ => unreachable!(),
+                };
+                if !matches!(&self.number_min, Some((min, _)) if *min <= number) {
+                    self.number_min = Some((number, value.clone()));
+                }
+                if !matches!(&self.number_max, Some((max, _)) if *max >= number) {
+                    self.number_max = Some((number, value.clone()));
+                }
+                self.add_example(value);
+            }
+            Bson::DateTime(date) => {
+                let millis = date.timestamp_millis();
+                self.date_min = Some(self.date_min.map_or(millis, |min| min.min(millis)));
+                self.date_max = Some(self.date_max.map_or(millis, |max| max.max(millis)));
+                self.add_example(value);
+            }
+            _ => self.add_example(value),
+        }
+    }
+
+    fn observe_document(&mut self, document: &Document) {
+        self.document_count += 1;
+        for (key, value) in document {
+            self.child(key).observe(value);
+        }
+    }
+
+    fn child(&mut self, name: &str) -> &mut SchemaStats {
+        let index = match self.children.iter().position(|(key, _)| key == name) {
+            Some(index) => index,
+            None => {
+                self.children
+                    .push((name.to_string(), SchemaStats::default()));
+                self.children.len() - 1
+            }
+        };
+        &mut self.children[index].1
+    }
+
+    fn add_example(&mut self, value: &Bson) {
+        if self.examples.len() >= MAX_SCHEMA_EXAMPLES {
+            return;
+        }
+        let example = match value {
+            Bson::String(text) if text.chars().count() > MAX_EXAMPLE_STRING_LEN => {
+                let truncated: String = text.chars().take(MAX_EXAMPLE_STRING_LEN).collect();
+                Bson::String(format!("{}...", truncated))
+            }
+            _ => value.clone(),
+        };
+        let example = example.into_relaxed_extjson();
+        if !self.examples.contains(&example) {
+            self.examples.push(example);
+        }
+    }
+
+    fn children_fields(&self, path: &str) -> Vec<MongoSchemaField> {
+        self.children
+            .iter()
+            .map(|(name, stats)| {
+                let (present, parent_count) = if name == "[]" {
+                    (self.non_empty_arrays, self.array_count)
+                } else {
+                    (stats.count, self.document_count)
+                };
+                let path = if path.is_empty() {
+                    name.to_string()
+                } else {
+                    format!("{}.{}", path, name)
+                };
+                stats.to_field(name, path, percentage(present, parent_count))
+            })
+            .collect()
+    }
+
+    fn to_field(&self, name: &str, path: String, presence: f64) -> MongoSchemaField {
+        let mut types: Vec<MongoFieldType> = self
+            .types
+            .iter()
+            .map(|(bson_type, count)| MongoFieldType {
+                bson_type: bson_type.to_string(),
+                count: *count,
+                percentage: percentage(*count, self.count),
+            })
+            .collect();
+        types.sort_by_key(|item| std::cmp::Reverse(item.count));
+
+        let date_value = |millis: i64| Bson::DateTime(mongodb::bson::DateTime::from_millis(millis));
+        let (min, max) = if self.number_min.is_some() {
+            (
+                self.number_min.as_ref().map(|(_, v)| v.clone()),
+                self.number_max.as_ref().map(|(_, v)| v.clone()),
+            )
+        } else {
+            (self.date_min.map(date_value), self.date_max.map(date_value))
+        };
+
+        MongoSchemaField {
+            name: name.to_string(),
+            children: self.children_fields(&path),
+            path,
+            count: self.count,
+            presence,
+            types,
+            min: min.map(|v| v.into_relaxed_extjson()),
+            max: max.map(|v| v.into_relaxed_extjson()),
+            examples: self.examples.clone(),
+        }
+    }
+}
+
+fn percentage(count: u64, total: u64) -> f64 {
+    if total == 0 {
+        0.0
+    } else {
+        count as f64 * 100.0 / total as f64
+    }
+}
+
+/// BSON 类型名称，与 MongoDB $type 别名保持一致
+pub fn bson_type_name(value: &Bson) -> &'static str {
+    match value {
+        Bson::Double(_) => "double",
+        Bson::String(_) => "string",
+        Bson::Document(_) => "object",
+        Bson::Array(_) => "array",
+        Bson::Binary(_) => "binData",
+        Bson::Undefined => "undefined",
+        Bson::ObjectId(_) => "objectId",
+        Bson::Boolean(_) => "bool",
+        Bson::DateTime(_) => "date",
+        Bson::Null => "null",
+        Bson::RegularExpression(_) => "regex",
+        Bson::DbPointer(_) => "dbPointer",
+        Bson::JavaScriptCode(_) => "javascript",
+        Bson::Symbol(_) => "symbol",
+        Bson::JavaScriptCodeWithScope(_) => "javascriptWithScope",
+        Bson::Int32(_) => "int",
+        Bson::Timestamp(_) => "timestamp",
+        Bson::Int64(_) => "long",
+        Bson::Decimal128(_) => "decimal",
+        Bson::MinKey => "minKey",
+        Bson::MaxKey => "maxKey",
+    }
+}
+
+/// 创建索引参数，keys 为扩展 JSON，如 `{"a": 1, "b": -1}`、`{"content": "text"}`、`{"loc": "2dsphere"}`
+#[derive(Deserialize, Serialize, Debug, Default)]
+pub struct MongoIndexOptions {
+    pub keys: String,
+
+    /// 索引名称，为空时由服务端生成
+    #[serde(default)]
+    pub name: Option<String>,
+
+    #[serde(default)]
+    pub unique: bool,
+
+    #[serde(default)]
+    pub sparse: bool,
+
+    /// 部分索引过滤条件（扩展 JSON）
+    #[serde(default)]
+    pub partial_filter: String,
+
+    /// TTL 索引过期秒数
+    #[serde(default)]
+    pub expire_after_seconds: Option<u64>,
+
+    #[serde(default)]
+    pub hidden: bool,
+
+    /// 文本索引默认语言
+    #[serde(default)]
+    pub default_language: Option<String>,
+
+    /// 文本索引字段权重（扩展 JSON）
+    #[serde(default)]
+    pub weights: String,
+}
+
+/// $indexStats 返回的索引使用情况
+#[derive(Deserialize, Serialize, Debug)]
+pub struct MongoIndexStats {
+    pub name: String,
+
+    pub key: serde_json::Value,
+
+    pub host: String,
+
+    /// 自 since 以来的访问次数，为 0 说明索引可能未被使用
+    pub accesses: i64,
+
+    /// 统计开始时间（RFC 3339）
+    pub since: Option<String>,
+}
+
+impl MongoIndexStats {
+    pub fn from_document(document: &Document) -> Self {
+        let accesses = document.get_document("accesses").ok();
+        MongoIndexStats {
+            name: document.get_str("name").unwrap_or_default().to_string(),
+            key: document
+                .get("key")
+                .map(|key| key.clone().into_relaxed_extjson())
+                .unwrap_or(serde_json::Value::Null),
+            host: document.get_str("host").unwrap_or_default().to_string(),
+            accesses: accesses
+                .and_then(|accesses| document_i64(accesses, "ops"))
+                .unwrap_or(0),
+            since: accesses
+                .and_then(|accesses| accesses.get_datetime("since").ok())
+                .and_then(|since| since.try_to_rfc3339_string().ok()),
+        }
+    }
+}
+
+/// explain("executionStats") 的摘要
+#[derive(Deserialize, Serialize, Debug, Default)]
+pub struct MongoExplainSummary {
+    /// 胜出计划的阶段，从根到叶，如 ["FETCH", "IXSCAN"]
+    pub winning_plan: Vec<String>,
+
+    /// 胜出计划使用的索引
+    pub index_names: Vec<String>,
+
+    pub keys_examined: i64,
+
+    pub docs_examined: i64,
+
+    pub returned: i64,
+
+    pub execution_time_ms: i64,
+
+    /// 是否发生全表扫描
+    pub collscan: bool,
+
+    /// 原始 explain 输出（relaxed 扩展 JSON）
+    pub raw: serde_json::Value,
+}
+
+impl MongoExplainSummary {
+    pub fn from_explain(explain: &Document) -> Self {
+        let mut summary = MongoExplainSummary::default();
+        if let Ok(plan) = explain
+            .get_document("queryPlanner")
+            .and_then(|planner| planner.get_document("winningPlan"))
+        {
+            collect_plan_stages(plan, &mut summary.winning_plan, &mut summary.index_names);
+        }
+        if let Ok(stats) = explain.get_document("executionStats") {
+            summary.keys_examined = document_i64(stats, "totalKeysExamined").unwrap_or(0);
+            summary.docs_examined = document_i64(stats, "totalDocsExamined").unwrap_or(0);
+            summary.returned = document_i64(stats, "nReturned").unwrap_or(0);
+            summary.execution_time_ms = document_i64(stats, "executionTimeMillis").unwrap_or(0);
+        }
+        summary.collscan = summary.winning_plan.iter().any(|stage| stage == "COLLSCAN");
+        summary.raw = Bson::Document(explain.clone()).into_relaxed_extjson();
+        summary
+    }
+}
+
+/// 递归收集计划树中的阶段和索引名称，兼容 SBE 的 queryPlan 与分片集群的 shards
+fn collect_plan_stages(plan: &Document, stages: &mut Vec<String>, index_names: &mut Vec<String>) {
+    if let Ok(query_plan) = plan.get_document("queryPlan") {
+        return collect_plan_stages(query_plan, stages, index_names);
+    }
+    if let Ok(stage) = plan.get_str("stage") {
+        stages.push(stage.to_string());
+    }
+    if let Ok(index_name) = plan.get_str("indexName") {
+        if !index_names.iter().any(|name| name == index_name) {
+            index_names.push(index_name.to_string());
+        }
+    }
+    if let Ok(input) = plan.get_document("inputStage") {
+        collect_plan_stages(input, stages, index_names);
+    }
+    for key in ["inputStages", "shards"] {
+        if let Ok(inputs) = plan.get_array(key) {
+            for input in inputs {
+                if let Bson::Document(input) = input {
+                    let input = input.get_document("winningPlan").unwrap_or(input);
+                    collect_plan_stages(input, stages, index_names);
+                }
+            }
+        }
+    }
+}
+
+/// 读取整数字段，兼容 Int32 / Int64 / Double
+pub fn document_i64(document: &Document, key: &str) -> Option<i64> {
+    match document.get(key)? {
+        Bson::Int32(value) => Some(*value as i64),
+        Bson::Int64(value) => Some(*value),
+        Bson::Double(value) => Some(*value as i64),
+        _ => None,
+    }
+}
+
+/// 读取浮点字段，兼容 Int32 / Int64 / Double
+pub fn document_f64(document: &Document, key: &str) -> Option<f64> {
+    match document.get(key)? {
+        Bson::Int32(value) => Some(*value as f64),
+        Bson::Int64(value) => Some(*value as f64),
+        Bson::Double(value) => Some(*value),
+        _ => None,
+    }
+}
+
+/// 副本集成员状态（replSetGetStatus.members）
+#[derive(Deserialize, Serialize, Debug)]
+pub struct MongoReplicaMember {
+    pub id: i64,
+
+    /// host:port
+    pub name: String,
+
+    /// 0 STARTUP、1 PRIMARY、2 SECONDARY、7 ARBITER、8 DOWN 等
+    pub state: i64,
+
+    pub state_str: String,
+
+    pub health: bool,
+
+    pub uptime: i64,
+
+    /// 最后应用的 oplog 时间（RFC 3339）
+    pub optime_date: Option<String>,
+
+    /// 相对主节点的复制延迟（秒），主节点或无法计算时为空
+    pub lag_seconds: Option<i64>,
+
+    /// 当选主节点的时间，仅主节点有值
+    pub election_date: Option<String>,
+
+    pub sync_source: String,
+
+    pub ping_ms: Option<i64>,
+
+    /// 是否为当前连接的节点
+    pub is_self: bool,
+}
+
+/// 副本集状态
+#[derive(Deserialize, Serialize, Debug)]
+pub struct MongoReplicaSetStatus {
+    pub set: String,
+
+    pub my_state: i64,
+
+    pub term: Option<i64>,
+
+    /// 主节点 host:port，选举中时为空
+    pub primary: Option<String>,
+
+    pub members: Vec<MongoReplicaMember>,
+}
+
+impl MongoReplicaSetStatus {
+    pub fn from_document(document: &Document) -> Self {
+        let members: Vec<&Document> = document
+            .get_array("members")
+            .map(|members| members.iter().filter_map(Bson::as_document).collect())
+            .unwrap_or_default();
+        let primary = members
+            .iter()
+            .find(|member| document_i64(member, "state") == Some(1));
+        let primary_optime = primary
+            .and_then(|member| member.get_datetime("optimeDate").ok())
+            .map(|date| date.timestamp_millis());
+
+        MongoReplicaSetStatus {
+            set: document.get_str("set").unwrap_or_default().to_string(),
+            my_state: document_i64(document, "myState").unwrap_or(0),
+            term: document_i64(document, "term"),
+            primary: primary.and_then(|member| member.get_str("name").ok().map(str::to_string)),
+            members: members
+                .iter()
+                .map(|member| {
+                    let state = document_i64(member, "state").unwrap_or(0);
+                    let optime = member
+                        .get_datetime("optimeDate")
+                        .ok()
+                        .map(|date| date.timestamp_millis());
+                    let lag_seconds = match (state, primary_optime, optime) {
+                        (2, Some(primary), Some(optime)) => Some((primary - optime).max(0) / 1000),
+                        _ => None,
+                    };
+                    MongoReplicaMember {
+                        id: document_i64(member, "_id").unwrap_or(0),
+                        name: member.get_str("name").unwrap_or_default().to_string(),
+                        state,
+                        state_str: member.get_str("stateStr").unwrap_or_default().to_string(),
+                        health: document_f64(member, "health").unwrap_or(0.0) > 0.0,
+                        uptime: document_i64(member, "uptime").unwrap_or(0),
+                        optime_date: document_date(member, "optimeDate"),
+                        lag_seconds,
+                        election_date: document_date(member, "electionDate"),
+                        sync_source: member
+                            .get_str("syncSourceHost")
+                            .or_else(|_| member.get_str("syncingTo"))
+                            .unwrap_or_default()
+                            .to_string(),
+                        ping_ms: document_i64(member, "pingMs"),
+                        is_self: member.get_bool("self").unwrap_or(false),
+                    }
+                })
+                .collect(),
+        }
+    }
+}
+
+/// 分片信息（listShards）
+#[derive(Deserialize, Serialize, Debug)]
+pub struct MongoShardInfo {
+    pub id: String,
+
+    /// 分片副本集连接串，如 rs1/host1:27018,host2:27018
+    pub host: String,
+
+    pub state: Option<i64>,
+
+    /// 是否正在移除该分片
+    pub draining: bool,
+
+    pub tags: Vec<String>,
+}
+
+impl MongoShardInfo {
+    pub fn from_document(document: &Document) -> Self {
+        MongoShardInfo {
+            id: document.get_str("_id").unwrap_or_default().to_string(),
+            host: document.get_str("host").unwrap_or_default().to_string(),
+            state: document_i64(document, "state"),
+            draining: document.get_bool("draining").unwrap_or(false),
+            tags: document
+                .get_array("tags")
+                .map(|tags| {
+                    tags.iter()
+                        .filter_map(Bson::as_str)
+                        .map(str::to_string)
+                        .collect()
+                })
+                .unwrap_or_default(),
+        }
+    }
+}
+
+/// 均衡器状态（balancerStatus）
+#[derive(Deserialize, Serialize, Debug, Default)]
+pub struct MongoBalancerStatus {
+    /// full 或 off
+    pub mode: String,
+
+    pub in_balancer_round: bool,
+
+    pub num_balancer_rounds: i64,
+}
+
+/// 单个分片上的 chunk 数
+#[derive(Deserialize, Serialize, Debug)]
+pub struct MongoShardChunkCount {
+    pub shard: String,
+
+    pub chunks: i64,
+}
+
+/// 集合在各分片上的 chunk 分布
+#[derive(Deserialize, Serialize, Debug)]
+pub struct MongoChunkDistribution {
+    pub namespace: String,
+
+    pub total_chunks: i64,
+
+    pub jumbo_chunks: i64,
+
+    pub shards: Vec<MongoShardChunkCount>,
+}
+
+/// 被标记为 jumbo 的 chunk
+#[derive(Deserialize, Serialize, Debug)]
+pub struct MongoJumboChunk {
+    pub namespace: String,
+
+    pub shard: String,
+
+    pub min: serde_json::Value,
+
+    pub max: serde_json::Value,
+}
+
+/// 分片集群状态，仅连接 mongos 时可用
+#[derive(Deserialize, Serialize, Debug)]
+pub struct MongoShardingStatus {
+    pub shards: Vec<MongoShardInfo>,
+
+    pub balancer: MongoBalancerStatus,
+
+    pub distributions: Vec<MongoChunkDistribution>,
+
+    pub jumbo_chunks: Vec<MongoJumboChunk>,
+}
+
+/// 读取日期字段并转换为 RFC 3339 字符串
+pub fn document_date(document: &Document, key: &str) -> Option<String> {
+    document
+        .get_datetime(key)
+        .ok()
+        .and_then(|date| date.try_to_rfc3339_string().ok())
+}
+
+/// change stream 监听参数，database 为空时监听整个部署，collection 为空时监听整个数据库
+#[derive(Deserialize, Serialize, Debug, Default)]
+pub struct MongoWatchOptions {
+    #[serde(default)]
+    pub database: String,
+
+    #[serde(default)]
+    pub collection: String,
+
+    /// 过滤管道（扩展 JSON），如 `[{"$match": {"operationType": "insert"}}]`
+    #[serde(default)]
+    pub pipeline: String,
+
+    /// 更新事件是否回查完整文档（fullDocument: updateLookup）
+    #[serde(default)]
+    pub full_document: bool,
+
+    /// 是否从上次保存的 resume token 继续
+    #[serde(default)]
+    pub resume: bool,
+}
+
+/// change stream 推送的一条变更事件
+#[derive(Deserialize, Serialize, Debug, Clone)]
+pub struct MongoChangeEvent {
+    /// 所属监听会话 id
+    pub session_id: String,
+
+    /// insert、update、replace、delete、drop、rename、dropDatabase、invalidate 等
+    pub operation_type: String,
+
+    /// db.collection
+    pub namespace: String,
+
+    pub document_key: Option<serde_json::Value>,
+
+    /// 包含 updatedFields、removedFields
+    pub update_description: Option<serde_json::Value>,
+
+    pub full_document: Option<serde_json::Value>,
+
+    /// 服务端集群时间（秒）
+    pub cluster_time: Option<u32>,
+
+    pub resume_token: Option<serde_json::Value>,
+
+    /// 收到事件的时间（毫秒时间戳）
+    pub timestamp: i64,
+
+    /// change stream 中断时的错误，此时 operation_type 为 error，resume_token 为最后收到的 token
+    #[serde(default)]
+    pub error: Option<String>,
+}
+
+impl MongoChangeEvent {
+    pub fn from_event(session_id: &str, event: ChangeStreamEvent<Document>) -> Self {
+        let operation_type = match mongodb::bson::to_bson(&event.operation_type) {
+            Ok(Bson::String(operation_type)) => operation_type,
+            _ => format!("{:?}", event.operation_type),
+        };
+        let namespace = match &event.ns {
+            Some(ns) => match &ns.coll {
+                Some(coll) => format!("{}.{}", ns.db, coll),
+                None => ns.db.clone(),
+            },
+            None => String::new(),
+        };
+        let update_description = event.update_description.map(|description| {
+            let removed_fields: Vec<Bson> = description
+                .removed_fields
+                .into_iter()
+                .map(Bson::String)
+                .collect();
+            Bson::Document(doc! {
+                "updatedFields": description.updated_fields,
+                "removedFields": removed_fields,
+            })
+            .into_canonical_extjson()
+        });
+
+        MongoChangeEvent {
+            session_id: session_id.to_string(),
+            operation_type,
+            namespace,
+            document_key: event
+                .document_key
+                .map(|key| Bson::Document(key).into_canonical_extjson()),
+            update_description,
+            full_document: event
+                .full_document
+                .map(|document| Bson::Document(document).into_canonical_extjson()),
+            cluster_time: event.cluster_time.map(|time| time.time),
+            resume_token: mongodb::bson::to_bson(&event.id)
+                .ok()
+                .map(Bson::into_canonical_extjson),
+            timestamp: Local::now().timestamp_millis(),
+            error: None,
+        }
+    }
+
+    /// change stream 出错结束时推送的事件，前端可据此从 resume_token 继续监听
+    pub fn error(session_id: &str, error: String, resume_token: Option<serde_json::Value>) -> Self {
+        MongoChangeEvent {
+            session_id: session_id.to_string(),
+            operation_type: "error".to_string(),
+            namespace: String::new(),
+            document_key: None,
+            update_description: None,
+            full_document: None,
+            cluster_time: None,
+            resume_token,
+            timestamp: Local::now().timestamp_millis(),
+            error: Some(error),
+        }
+    }
+}
+
+/// change stream 监听会话的描述信息
+#[derive(Deserialize, Serialize, Debug, Clone)]
+pub struct MongoWatchInfo {
+    pub session_id: String,
+
+    /// 对应 MongoStorageEntity 的 id
+    pub config_id: String,
+
+    pub database: String,
+
+    pub collection: String,
+
+    pub full_document: bool,
+
+    /// 是否从保存的 resume token 继续
+    pub resumed: bool,
+
+    /// 会话开始时间（毫秒时间戳）
+    pub started_at: i64,
+}
+
+/// 后台 change stream 任务的句柄，stop 后返回最后的 resume token，drop 时结束监听
+pub struct MongoWatchHandle {
+    stop: Option<oneshot::Sender<()>>,
+    join: Option<JoinHandle<Option<serde_json::Value>>>,
+}
+
+impl MongoWatchHandle {
+    pub fn new(stop: oneshot::Sender<()>, join: JoinHandle<Option<serde_json::Value>>) -> Self {
+        Self {
+            stop: Some(stop),
+            join: Some(join),
+        }
+    }
+
+    pub fn is_running(&self) -> bool {
+        matches!(&self.join, Some(join) if !join.is_finished())
+    }
+
+    pub async fn stop(&mut self) -> Option<serde_json::Value> {
+        if let Some(stop) = self.stop.take() {
+            let _ = stop.send(());
+        }
+        match self.join.take() {
+            Some(join) => join.await.ok().flatten(),
+            None => None,
+        }
+    }
+}
+
+impl Drop for MongoWatchHandle {
+    fn drop(&mut self) {
+        if let Some(stop) = self.stop.take() {
+            let _ = stop.send(());
+        }
+    }
+}
+
+/// 集合导入导出格式
+#[derive(Serialize, Deserialize, Debug, Clone, Copy, PartialEq)]
+pub enum MongoTransferFormat {
+    /// JSON 数组，文档为 relaxed 扩展 JSON
+    JsonArray,
+    /// 每行一个 canonical 扩展 JSON 文档
+    JsonLines,
+    /// 首行为字段路径（支持 a.b 形式的嵌套路径）
+    Csv,
+}
+
+/// 集合导出参数
+#[derive(Serialize, Deserialize, Debug)]
+pub struct MongoExportOptions {
+    pub format: MongoTransferFormat,
+
+    #[serde(default)]
+    pub filter: String,
+
+    #[serde(default)]
+    pub projection: String,
+
+    #[serde(default)]
+    pub sort: String,
+
+    /// CSV 导出的字段路径，为空时根据前若干条文档推断
+    #[serde(default)]
+    pub fields: Vec<String>,
+
+    /// 最多导出条数，小于等于 0 表示不限制
+    #[serde(default)]
+    pub limit: i64,
+}
+
+#[derive(Serialize, Deserialize, Debug)]
+pub struct MongoExportResult {
+    pub file_path: String,
+    pub exported: u64,
+    /// CSV 导出实际使用的字段
+    pub fields: Vec<String>,
+}
+
+/// 集合导入参数
+#[derive(Serialize, Deserialize, Debug)]
+pub struct MongoImportOptions {
+    pub format: MongoTransferFormat,
+
+    /// 按这些字段匹配已有文档并整体替换（不存在时插入），为空时直接批量插入
+    #[serde(default)]
+    pub upsert_fields: Vec<String>,
+
+    /// 每批插入的文档数，0 时使用默认值
+    #[serde(default)]
+    pub batch_size: usize,
+}
+
+/// 单条文档导入失败的原因
+#[derive(Serialize, Deserialize, Debug)]
+pub struct MongoDocumentError {
+    /// 文档在导入文件中的序号（从 1 开始）
+    pub index: usize,
+    pub error: String,
+}
+
+#[derive(Serialize, Deserialize, Debug, Default)]
+pub struct MongoImportResult {
+    pub total: usize,
+    pub inserted: usize,
+    /// upsert 模式下新建的文档数
+    pub upserted: usize,
+    /// upsert 模式下替换的已有文档数
+    pub replaced: usize,
+    pub failed: Vec<MongoDocumentError>,
+}
+
+/// currentOp 过滤条件
+#[derive(Deserialize, Serialize, Debug, Default)]
+pub struct MongoCurrentOpFilter {
+    /// 是否包含空闲连接和系统操作（$all）
+    #[serde(default)]
+    pub all: bool,
+
+    /// 命名空间，如 `db` 或 `db.collection`，`db` 匹配该库下所有集合
+    #[serde(default)]
+    pub ns: String,
+
+    /// 只返回运行时间不少于该秒数的操作
+    #[serde(default)]
+    pub min_secs_running: i64,
+}
+
+/// currentOp.inprog 中的一个操作
+#[derive(Deserialize, Serialize, Debug)]
+pub struct MongoCurrentOp {
+    /// 操作 ID，分片集群下为 `shard:opid` 形式
+    pub opid: String,
+
+    pub active: bool,
+
+    pub ns: String,
+
+    /// query、insert、update、remove、getmore、command 等
+    pub op: String,
+
+    pub secs_running: i64,
+
+    pub microsecs_running: i64,
+
+    /// 客户端地址，mongos 上为 client_s
+    pub client: String,
+
+    pub app_name: String,
+
+    /// 连接描述，如 conn123
+    pub desc: String,
+
+    pub plan_summary: String,
+
+    pub waiting_for_lock: bool,
+
+    /// 原始命令（relaxed 扩展 JSON）
+    pub command: serde_json::Value,
+}
+
+impl MongoCurrentOp {
+    pub fn from_document(document: &Document) -> Self {
+        let opid = match document.get("opid") {
+            Some(Bson::String(opid)) => opid.clone(),
+            Some(_) => document_i64(document, "opid")
+                .map(|opid| opid.to_string())
+                .unwrap_or_default(),
+            None => String::new(),
+        };
+        let client = document
+            .get_str("client")
+            .or_else(|_| document.get_str("client_s"))
+            .unwrap_or_default();
+        MongoCurrentOp {
+            opid,
+            active: document.get_bool("active").unwrap_or(false),
+            ns: document.get_str("ns").unwrap_or_default().to_string(),
+            op: document.get_str("op").unwrap_or_default().to_string(),
+            secs_running: document_i64(document, "secs_running").unwrap_or(0),
+            microsecs_running: document_i64(document, "microsecs_running").unwrap_or(0),
+            client: client.to_string(),
+            app_name: document.get_str("appName").unwrap_or_default().to_string(),
+            desc: document.get_str("desc").unwrap_or_default().to_string(),
+            plan_summary: document
+                .get_str("planSummary")
+                .unwrap_or_default()
+                .to_string(),
+            waiting_for_lock: document.get_bool("waitingForLock").unwrap_or(false),
+            command: document
+                .get("command")
+                .map(|command| command.clone().into_relaxed_extjson())
+                .unwrap_or(serde_json::Value::Null),
+        }
+    }
+}
+
+/// 数据库 profiler 状态
+#[derive(Deserialize, Serialize, Debug)]
+pub struct MongoProfilingStatus {
+    /// 0 关闭、1 仅记录慢操作、2 记录所有操作
+    pub level: i64,
+
+    /// 慢操作阈值（毫秒）
+    pub slowms: i64,
+
+    pub sample_rate: Option<f64>,
+}
+
+impl MongoProfilingStatus {
+    /// 解析 profile 命令返回，was 为设置前的级别
+    pub fn from_document(document: &Document) -> Self {
+        MongoProfilingStatus {
+            level: document_i64(document, "was").unwrap_or(0),
+            slowms: document_i64(document, "slowms").unwrap_or(0),
+            sample_rate: document_f64(document, "sampleRate"),
+        }
+    }
+}
+
+/// system.profile 查询条件
+#[derive(Deserialize, Serialize, Debug, Default)]
+pub struct MongoProfileQuery {
+    /// 命名空间，空时不过滤
+    #[serde(default)]
+    pub ns: String,
+
+    /// 只返回耗时不少于该毫秒数的记录
+    #[serde(default)]
+    pub min_millis: i64,
+
+    /// 返回条数，0 时使用默认值
+    #[serde(default)]
+    pub limit: i64,
+}
+
+/// system.profile 中的一条记录
+#[derive(Deserialize, Serialize, Debug)]
+pub struct MongoProfileEntry {
+    pub op: String,
+
+    pub ns: String,
+
+    pub millis: i64,
+
+    /// 记录时间（RFC 3339）
+    pub ts: Option<String>,
+
+    pub plan_summary: String,
+
+    pub keys_examined: i64,
+
+    pub docs_examined: i64,
+
+    pub returned: i64,
+
+    pub response_length: i64,
+
+    pub client: String,
+
+    pub user: String,
+
+    /// 原始命令（relaxed 扩展 JSON）
+    pub command: serde_json::Value,
+}
+
+impl MongoProfileEntry {
+    pub fn from_document(document: &Document) -> Self {
+        MongoProfileEntry {
+            op: document.get_str("op").unwrap_or_default().to_string(),
+            ns: document.get_str("ns").unwrap_or_default().to_string(),
+            millis: document_i64(document, "millis").unwrap_or(0),
+            ts: document_date(document, "ts"),
+            plan_summary: document
+                .get_str("planSummary")
+                .unwrap_or_default()
+                .to_string(),
+            keys_examined: document_i64(document, "keysExamined").unwrap_or(0),
+            docs_examined: document_i64(document, "docsExamined").unwrap_or(0),
+            returned: document_i64(document, "nreturned").unwrap_or(0),
+            response_length: document_i64(document, "responseLength").unwrap_or(0),
+            client: document.get_str("client").unwrap_or_default().to_string(),
+            user: document.get_str("user").unwrap_or_default().to_string(),
+            command: document
+                .get("command")
+                .map(|command| command.clone().into_relaxed_extjson())
+                .unwrap_or(serde_json::Value::Null),
+        }
+    }
+}
+
+/// 角色引用，如 `{ role: "readWrite", db: "production" }`
+#[derive(Deserialize, Serialize, Debug, Clone, PartialEq)]
+pub struct MongoRoleRef {
+    pub role: String,
+
+    pub db: String,
+}
+
+impl MongoRoleRef {
+    pub fn to_document(&self) -> Document {
+        doc! { "role": self.role.as_str(), "db": self.db.as_str() }
+    }
+
+    /// 解析角色数组，兼容只写角色名的简写形式，此时使用 default_db
+    pub fn from_array(value: Option<&Bson>, default_db: &str) -> Vec<MongoRoleRef> {
+        let items = match value {
+            Some(Bson::Array(items)) => items,
+            _ => return Vec::new(),
+        };
+        items
+            .iter()
+            .filter_map(|item| match item {
+                Bson::String(role) => Some(MongoRoleRef {
+                    role: role.clone(),
+                    db: default_db.to_string(),
+                }),
+                Bson::Document(document) => Some(MongoRoleRef {
+                    role: document.get_str("role").ok()?.to_string(),
+                    db: document.get_str("db").unwrap_or(default_db).to_string(),
+                }),
+                _ => None,
+            })
+            .collect()
+    }
+}
+
+/// usersInfo 返回的用户
+#[derive(Deserialize, Serialize, Debug)]
+pub struct MongoUserInfo {
+    pub user: String,
+
+    /// 用户所属的认证数据库
+    pub db: String,
+
+    pub roles: Vec<MongoRoleRef>,
+
+    /// SCRAM-SHA-1、SCRAM-SHA-256 等
+    pub mechanisms: Vec<String>,
+
+    pub custom_data: serde_json::Value,
+}
+
+impl MongoUserInfo {
+    pub fn from_document(document: &Document) -> Self {
+        let db = document.get_str("db").unwrap_or_default().to_string();
+        MongoUserInfo {
+            user: document.get_str("user").unwrap_or_default().to_string(),
+            roles: MongoRoleRef::from_array(document.get("roles"), &db),
+            mechanisms: document
+                .get_array("mechanisms")
+                .map(|items| {
+                    items
+                        .iter()
+                        .filter_map(|item| item.as_str().map(|item| item.to_string()))
+                        .collect()
+                })
+                .unwrap_or_default(),
+            custom_data: document
+                .get("customData")
+                .map(|data| data.clone().into_relaxed_extjson())
+                .unwrap_or(serde_json::Value::Null),
+            db,
+        }
+    }
+}
+
+/// 创建或修改用户的参数，修改时 password 为空表示不改密码
+#[derive(Deserialize, Serialize, Debug, Default)]
+pub struct MongoUserOptions {
+    pub user: String,
+
+    #[serde(default)]
+    pub password: String,
+
+    #[serde(default)]
+    pub roles: Vec<MongoRoleRef>,
+
+    /// 自定义数据（扩展 JSON 对象）
+    #[serde(default)]
+    pub custom_data: String,
+
+    /// 认证机制，空时使用服务端默认值
+    #[serde(default)]
+    pub mechanisms: Vec<String>,
+}
+
+/// 权限作用的资源：数据库/集合、集群或任意资源
+#[derive(Deserialize, Serialize, Debug, Clone, Default, PartialEq)]
+pub struct MongoPrivilegeResource {
+    /// 数据库名，空字符串表示所有数据库
+    #[serde(default)]
+    pub db: Option<String>,
+
+    /// 集合名，空字符串表示所有集合
+    #[serde(default)]
+    pub collection: Option<String>,
+
+    #[serde(default)]
+    pub cluster: bool,
+
+    #[serde(default)]
+    pub any_resource: bool,
+}
+
+impl MongoPrivilegeResource {
+    pub fn from_document(document: &Document) -> Self {
+        MongoPrivilegeResource {
+            db: document.get_str("db").ok().map(|db| db.to_string()),
+            collection: document
+                .get_str("collection")
+                .ok()
+                .map(|collection| collection.to_string()),
+            cluster: document.get_bool("cluster").unwrap_or(false),
+            any_resource: document.get_bool("anyResource").unwrap_or(false),
+        }
+    }
+
+    pub fn to_document(&self) -> Document {
+        if self.any_resource {
+            return doc! { "anyResource": true };
+        }
+        if self.cluster {
+            return doc! { "cluster": true };
+        }
+        doc! {
+            "db": self.db.clone().unwrap_or_default(),
+            "collection": self.collection.clone().unwrap_or_default(),
+        }
+    }
+}
+
+/// 一条权限：资源及允许的操作
+#[derive(Deserialize, Serialize, Debug, Clone, PartialEq)]
+pub struct MongoPrivilege {
+    pub resource: MongoPrivilegeResource,
+
+    /// find、insert、update、remove、createIndex 等
+    pub actions: Vec<String>,
+}
+
+impl MongoPrivilege {
+    pub fn from_document(document: &Document) -> Self {
+        MongoPrivilege {
+            resource: document
+                .get_document("resource")
+                .map(MongoPrivilegeResource::from_document)
+                .unwrap_or_default(),
+            actions: document
+                .get_array("actions")
+                .map(|items| {
+                    items
+                        .iter()
+                        .filter_map(|item| item.as_str().map(|item| item.to_string()))
+                        .collect()
+                })
+                .unwrap_or_default(),
+        }
+    }
+
+    pub fn to_document(&self) -> Document {
+        doc! {
+            "resource": self.resource.to_document(),
+            "actions": self.actions.clone(),
+        }
+    }
+
+    /// 解析权限数组
+    pub fn from_array(value: Option<&Bson>) -> Vec<MongoPrivilege> {
+        match value {
+            Some(Bson::Array(items)) => items
+                .iter()
+                .filter_map(|item| item.as_document())
+                .map(MongoPrivilege::from_document)
+                .collect(),
+            _ => Vec::new(),
+        }
+    }
+}
+
+/// rolesInfo 返回的角色，包含展开后的权限
+#[derive(Deserialize, Serialize, Debug)]
+pub struct MongoRoleInfo {
+    pub role: String,
+
+    pub db: String,
+
+    pub is_builtin: bool,
+
+    /// 直接继承的角色
+    pub roles: Vec<MongoRoleRef>,
+
+    /// 递归展开后继承的所有角色
+    pub inherited_roles: Vec<MongoRoleRef>,
+
+    /// 角色自身定义的权限
+    pub privileges: Vec<MongoPrivilege>,
+
+    /// 包含继承在内的全部权限
+    pub inherited_privileges: Vec<MongoPrivilege>,
+}
+
+impl MongoRoleInfo {
+    pub fn from_document(document: &Document) -> Self {
+        let db = document.get_str("db").unwrap_or_default().to_string();
+        MongoRoleInfo {
+            role: document.get_str("role").unwrap_or_default().to_string(),
+            is_builtin: document.get_bool("isBuiltin").unwrap_or(false),
+            roles: MongoRoleRef::from_array(document.get("roles"), &db),
+            inherited_roles: MongoRoleRef::from_array(document.get("inheritedRoles"), &db),
+            privileges: MongoPrivilege::from_array(document.get("privileges")),
+            inherited_privileges: MongoPrivilege::from_array(document.get("inheritedPrivileges")),
+            db,
+        }
+    }
+}
+
+/// 创建或修改自定义角色的参数，修改时整体替换权限和继承角色
+#[derive(Deserialize, Serialize, Debug, Default)]
+pub struct MongoRoleOptions {
+    pub role: String,
+
+    #[serde(default)]
+    pub privileges: Vec<MongoPrivilege>,
+
+    #[serde(default)]
+    pub roles: Vec<MongoRoleRef>,
 }
@@ -1,13 +1,52 @@
+use std::collections::{BTreeMap, HashMap};
+use std::fs::{self, File};
+use std::io::{BufWriter, Write};
+use std::time::Duration;
+
 use futures_lite::StreamExt;
-use mongodb::bson::{doc, Document};
-use mongodb::options::ClientOptions;
-use mongodb::{Client, IndexModel};
+use mongodb::bson::{doc, Bson, Document};
+use mongodb::change_stream::event::ResumeToken;
+use mongodb::error::{BulkWriteFailure, ErrorKind};
+use mongodb::options::{
+    AggregateOptions, ChangeStreamOptions, ClientOptions, CountOptions,
+    EstimatedDocumentCountOptions, FindOptions, FullDocumentType, IndexOptions, InsertManyOptions,
+    ReplaceOptions, UpdateOptions,
+};
+use mongodb::{Client, Cursor, IndexModel};
+use tokio::sync::oneshot;
 
 use crate::config::mongo_config::MongoUserPassword;
 use crate::op::mongo_entity::{
-    MongoConnectionsInfo, MongoMemInfo, MongoServerInfo, MongoServerInfoCol,
+    document_i64, MongoAggregateOptions, MongoAggregateResult, MongoBalancerStatus,
+    MongoChangeEvent, MongoChunkDistribution, MongoCurrentOp, MongoCurrentOpFilter,
+    MongoDocumentError, MongoExplainSummary, MongoExportOptions, MongoExportResult,
+    MongoFindOptions, MongoFindResult, MongoImportOptions, MongoImportResult, MongoIndexOptions,
+    MongoIndexStats, MongoJumboChunk, MongoPrivilege, MongoProfileEntry, MongoProfileQuery,
+    MongoProfilingStatus, MongoReplicaSetStatus, MongoRoleInfo, MongoRoleOptions, MongoRoleRef,
+    MongoSchemaAnalyzer, MongoSchemaReport, MongoServerInfoCol, MongoShardChunkCount,
+    MongoShardInfo, MongoShardingStatus, MongoTransferFormat, MongoUserInfo, MongoUserOptions,
+    MongoWatchHandle, MongoWatchOptions, MongoWriteResult,
 };
 use crate::resp::resp::Response;
+use crate::util::csv::{csv_escape, parse_csv_records};
+
+/// 文档浏览默认每页条数
+const DEFAULT_PAGE_SIZE: i64 = 50;
+
+/// 分片状态中最多返回的 jumbo chunk 数
+const MAX_JUMBO_CHUNKS: i64 = 100;
+
+/// CSV 导出未指定字段时用于推断列的文档数
+const CSV_FIELD_SAMPLE_SIZE: usize = 100;
+
+/// 导入时每批插入的默认文档数
+const IMPORT_BATCH_SIZE: usize = 500;
+
+/// 读取 system.profile 的默认条数
+const DEFAULT_PROFILE_LIMIT: i64 = 100;
+
+/// 结构分析默认抽样文档数
+const DEFAULT_SCHEMA_SAMPLE_SIZE: u64 = 100;
 
 pub struct MongoOperation {
     client: Client,
@@ -77,115 +116,1787 @@ impl MongoOperation {
         }
     }
 
-    pub async fn collection_names(&self, database_name: &str) -> Response<Vec<String>> {
-        let db = self.client.database(database_name);
+    /// 按条件查询文档，支持 skip 分页和基于 _id 的游标分页
+    pub async fn find_documents(
+        &self,
+        database_name: &str,
+        collection_name: &str,
+        options: &MongoFindOptions,
+    ) -> Response<MongoFindResult> {
+        let filter = match parse_ext_json_document(&options.filter) {
+            Ok(document) => document,
+            Err(err) => return Response::from_error(format!("filter 格式错误: {}", err)),
+        };
+        let projection = match parse_ext_json_document(&options.projection) {
+            Ok(document) => document,
+            Err(err) => return Response::from_error(format!("projection 格式错误: {}", err)),
+        };
+        let sort = match parse_ext_json_document(&options.sort) {
+            Ok(document) => document,
+            Err(err) => return Response::from_error(format!("sort 格式错误: {}", err)),
+        };
 
-        match db.list_collection_names(None).await {
-            Ok(collections) => Response::new("Operation successful", Some(collections)),
-            Err(err) => Response::from_error(format!("Error listing collections: {}", err)),
+        let collection = self
+            .client
+            .database(database_name)
+            .collection::<Document>(collection_name);
+        let max_time = if options.max_time_ms > 0 {
+            Some(Duration::from_millis(options.max_time_ms))
+        } else {
+            None
+        };
+        let limit = if options.limit > 0 {
+            options.limit
+        } else {
+            DEFAULT_PAGE_SIZE
+        };
+
+        let mut find_options = FindOptions::default();
+        find_options.max_time = max_time;
+        // 多取一条用于判断是否还有下一页
+        find_options.limit = Some(limit + 1);
+        if !projection.is_empty() {
+            find_options.projection = Some(projection);
+        }
+
+        let query = match &options.after {
+            Some(after) => {
+                if sort.keys().any(|key| key != "_id") {
+                    return Response::from_error("游标分页仅支持按 _id 排序");
+                }
+                let descending = sort.get("_id").map(is_descending).unwrap_or(false);
+                let after = match Bson::try_from(after.clone()) {
+                    Ok(after) => after,
+                    Err(err) => return Response::from_error(format!("游标格式错误: {}", err)),
+                };
+                find_options.sort = Some(doc! { "_id": if descending { -1 } else { 1 } });
+
+                let mut range = Document::new();
+                range.insert(if descending { "$lt" } else { "$gt" }, after);
+                let cursor_filter = doc! { "_id": range };
+                if filter.is_empty() {
+                    cursor_filter
+                } else {
+                    doc! { "$and": [filter.clone(), cursor_filter] }
+                }
+            }
+            None => {
+                find_options.skip = Some(options.skip);
+                if !sort.is_empty() {
+                    find_options.sort = Some(sort);
+                }
+                filter.clone()
+            }
+        };
+
+        let mut cursor = match collection.find(query, find_options).await {
+            Ok(cursor) => cursor,
+            Err(err) => return Response::from_error(format!("查询文档时出错: {}", err)),
+        };
+        let mut documents = Vec::new();
+        while let Some(item) = cursor.next().await {
+            match item {
+                Ok(document) => documents.push(document),
+                Err(err) => return Response::from_error(format!("读取文档时出错: {}", err)),
+            }
         }
+        let has_more = documents.len() as i64 > limit;
+        documents.truncate(limit as usize);
+        let next_cursor = if has_more {
+            documents
+                .last()
+                .and_then(|document| document.get("_id"))
+                .map(|id| id.clone().into_canonical_extjson())
+        } else {
+            None
+        };
+
+        let (total, exact) = if options.exact_count || !filter.is_empty() {
+            let mut count_options = CountOptions::default();
+            count_options.max_time = max_time;
+            match collection.count_documents(filter, count_options).await {
+                Ok(count) => (count, true),
+                Err(err) => return Response::from_error(format!("统计文档数时出错: {}", err)),
+            }
+        } else {
+            let mut count_options = EstimatedDocumentCountOptions::default();
+            count_options.max_time = max_time;
+            match collection.estimated_document_count(count_options).await {
+                Ok(count) => (count, false),
+                Err(err) => return Response::from_error(format!("统计文档数时出错: {}", err)),
+            }
+        };
+
+        let result = MongoFindResult {
+            documents: documents.into_iter().map(document_to_ext_json).collect(),
+            total,
+            exact,
+            has_more,
+            next_cursor,
+        };
+        Response::new("操作成功", Some(result))
     }
-    pub async fn get_server_info(&self) -> Response<MongoServerInfoCol> {
-        let admin_db = self.client.database("admin");
-        let command = doc! {
-            "serverStatus": 1,
-            "recordStats": 0,
-            "repl": 0,
-            "metrics": 1,
-            "locks": 0,
-            "backgroundFlushing": 0,
-            "asserts": 0,
-            "connections": 1,
-            "extra_info": 0,
-            "index_counters": 0,
-            "network": 1,
-            "opcounters": 0,
-            "opcountersRepl": 0,
-            "mem": 1,
-            "wiredTiger": 0,
-            "ft": 0,
-            "uptime": 1,
-            "uptimeEstimate": 1,
+
+    /// 插入文档，传入 JSON 对象时执行 insertOne，传入数组时执行 insertMany
+    pub async fn insert_documents(
+        &self,
+        database_name: &str,
+        collection_name: &str,
+        documents: &str,
+    ) -> Response<MongoWriteResult> {
+        let documents = match parse_ext_json_documents(documents) {
+            Ok(documents) => documents,
+            Err(err) => return Response::from_error(format!("文档格式错误: {}", err)),
         };
+        if documents.is_empty() {
+            return Response::from_error("没有需要插入的文档");
+        }
+        let collection = self
+            .client
+            .database(database_name)
+            .collection::<Document>(collection_name);
 
-        if let Ok(result) = admin_db.run_command(command, None).await {
-            // dbg!(&result);
-            // 获取服务器信息中的某个字段，这里假设你想获取 "version" 字段
-            let server_info = MongoServerInfo {
-                host: result.get_str("host").unwrap().to_string(),
-                version: result.get_str("version").unwrap().to_string(),
-                process: result.get_str("process").unwrap().to_string(),
-                pid: result.get_i64("pid").unwrap(),
-                uptime: result.get_f64("uptime").unwrap(),
-                uptime_millis: result.get_i64("uptimeMillis").unwrap(),
-                uptime_estimate: result.get_i64("uptimeEstimate").unwrap(),
-            };
-            let mem_document = result.get_document("mem").unwrap();
-            let mem_info = MongoMemInfo {
-                bits: mem_document.get_i32("bits").unwrap(),
-                resident: mem_document.get_i32("resident").unwrap(),
-                virtual_memory: mem_document.get_i32("virtual").unwrap(),
-                supported: mem_document.get_bool("supported").unwrap(),
-            };
-            let connection_document = result.get_document("connections").unwrap();
-            let conn_info = MongoConnectionsInfo {
-                current: connection_document.get_i32("current").unwrap(),
-                available: connection_document.get_i32("available").unwrap(),
-                total_created: connection_document.get_i32("totalCreated").unwrap(),
-                rejected: connection_document.get_i32("rejected").unwrap(),
-                active: connection_document.get_i32("active").unwrap(),
-                threaded: connection_document.get_i32("threaded").unwrap(),
-                exhaust_is_master: connection_document.get_i32("exhaustIsMaster").unwrap(),
-                exhaust_hello: connection_document.get_i32("exhaustHello").unwrap(),
-                awaiting_topology_changes: connection_document
-                    .get_i32("awaitingTopologyChanges")
-                    .unwrap(),
-            };
-            let r = MongoServerInfoCol {
-                mongo_server_info: server_info,
-                mongo_mem_info: mem_info,
-                mongo_connections_info: conn_info,
-            };
-            return Response::new("Server info retrieved successfully", Some(r));
+        let inserted_ids = if documents.len() == 1 {
+            match collection.insert_one(&documents[0], None).await {
+                Ok(result) => vec![result.inserted_id],
+                Err(err) => return Response::from_error(format!("插入文档时出错: {}", err)),
+            }
+        } else {
+            match collection.insert_many(documents, None).await {
+                Ok(result) => {
+                    let mut ids: Vec<(usize, Bson)> = result.inserted_ids.into_iter().collect();
+                    ids.sort_by_key(|(index, _)| *index);
+                    ids.into_iter().map(|(_, id)| id).collect()
+                }
+                Err(err) => return Response::from_error(format!("插入文档时出错: {}", err)),
+            }
         };
 
-        // 如果获取信息失败或无法找到期望的字段，返回错误信息
-        Response::from_error("Failed to retrieve server info")
+        let result = MongoWriteResult {
+            inserted_ids: inserted_ids
+                .into_iter()
+                .map(|id| id.into_canonical_extjson())
+                .collect(),
+            ..Default::default()
+        };
+        Response::new("插入文档成功", Some(result))
     }
-    pub async fn db_names(&self) -> Response<Vec<String>> {
-        match self.client.list_database_names(None, None).await {
-            Ok(databases) => Response::new("Operation successful", Some(databases)),
-            Err(err) => Response::from_error(format!("Error listing database names: {}", err)),
+
+    /// 使用更新操作符（$set、$inc 等）更新文档
+    pub async fn update_documents(
+        &self,
+        database_name: &str,
+        collection_name: &str,
+        filter: &str,
+        update: &str,
+        many: bool,
+        upsert: bool,
+    ) -> Response<MongoWriteResult> {
+        let filter = match parse_ext_json_document(filter) {
+            Ok(document) => document,
+            Err(err) => return Response::from_error(format!("filter 格式错误: {}", err)),
+        };
+        let update = match parse_ext_json_document(update) {
+            Ok(document) => document,
+            Err(err) => return Response::from_error(format!("update 格式错误: {}", err)),
+        };
+        if update.is_empty() || update.keys().any(|key| !key.starts_with('$')) {
+            return Response::from_error("update 只能包含更新操作符，如 $set、$unset、$inc");
+        }
+        let collection = self
+            .client
+            .database(database_name)
+            .collection::<Document>(collection_name);
+        let mut update_options = UpdateOptions::default();
+        update_options.upsert = Some(upsert);
+
+        let result = if many {
+            collection.update_many(filter, update, update_options).await
+        } else {
+            collection.update_one(filter, update, update_options).await
+        };
+        match result {
+            Ok(result) => {
+                let result = MongoWriteResult {
+                    matched_count: result.matched_count,
+                    modified_count: result.modified_count,
+                    upserted_id: result.upserted_id.map(|id| id.into_canonical_extjson()),
+                    ..Default::default()
+                };
+                Response::new("更新文档成功", Some(result))
+            }
+            Err(err) => Response::from_error(format!("更新文档时出错: {}", err)),
         }
     }
-    pub async fn new(
-        mongo_user_password: &MongoUserPassword,
-    ) -> Result<Self, Box<dyn std::error::Error>> {
-        let mongodb_url =
-            if mongo_user_password.password.is_empty() && mongo_user_password.username.is_empty() {
-                format!(
-                    "mongodb://{}:{}/",
-                    mongo_user_password.host, mongo_user_password.port
-                )
-            } else {
-                format!(
-                    "mongodb://{}:{}@{}:{}/",
-                    mongo_user_password.username,
-                    mongo_user_password.password,
-                    mongo_user_password.host,
-                    mongo_user_password.port
-                )
+
+    /// 按 _id 整体替换文档，document 中必须包含 _id
+    pub async fn replace_document(
+        &self,
+        database_name: &str,
+        collection_name: &str,
+        document: &str,
+    ) -> Response<MongoWriteResult> {
+        let document = match parse_ext_json_document(document) {
+            Ok(document) => document,
+            Err(err) => return Response::from_error(format!("文档格式错误: {}", err)),
+        };
+        let id = match document.get("_id") {
+            Some(id) => id.clone(),
+            None => return Response::from_error("替换文档必须包含 _id"),
+        };
+        let collection = self
+            .client
+            .database(database_name)
+            .collection::<Document>(collection_name);
+
+        match collection
+            .replace_one(doc! { "_id": id }, document, None)
+            .await
+        {
+            Ok(result) => {
+                let result = MongoWriteResult {
+                    matched_count: result.matched_count,
+                    modified_count: result.modified_count,
+                    ..Default::default()
+                };
+                Response::new("替换文档成功", Some(result))
+            }
+            Err(err) => Response::from_error(format!("替换文档时出错: {}", err)),
+        }
+    }
+
+    /// 按条件删除文档，filter 不能为空；dry_run 时只统计将被删除的文档数
+    pub async fn delete_documents(
+        &self,
+        database_name: &str,
+        collection_name: &str,
+        filter: &str,
+        many: bool,
+        dry_run: bool,
+    ) -> Response<MongoWriteResult> {
+        let filter = match parse_ext_json_document(filter) {
+            Ok(document) => document,
+            Err(err) => return Response::from_error(format!("filter 格式错误: {}", err)),
+        };
+        if filter.is_empty() {
+            return Response::from_error("删除文档必须指定过滤条件");
+        }
+        let collection = self
+            .client
+            .database(database_name)
+            .collection::<Document>(collection_name);
+
+        if dry_run {
+            let mut count_options = CountOptions::default();
+            if !many {
+                count_options.limit = Some(1);
+            }
+            return match collection.count_documents(filter, count_options).await {
+                Ok(count) => {
+                    let result = MongoWriteResult {
+                        deleted_count: count,
+                        dry_run: true,
+                        ..Default::default()
+                    };
+                    Response::new("操作成功", Some(result))
+                }
+                Err(err) => Response::from_error(format!("统计文档数时出错: {}", err)),
             };
+        }
 
-        println!("mongo url = {}", mongodb_url);
-        // 创建客户端选项
-        let mut client_options = ClientOptions::parse(&mongodb_url).await.unwrap();
+        let result = if many {
+            collection.delete_many(filter, None).await
+        } else {
+            collection.delete_one(filter, None).await
+        };
+        match result {
+            Ok(result) => {
+                let result = MongoWriteResult {
+                    deleted_count: result.deleted_count,
+                    ..Default::default()
+                };
+                Response::new("删除文档成功", Some(result))
+            }
+            Err(err) => Response::from_error(format!("删除文档时出错: {}", err)),
+        }
+    }
 
-        // 设置连接池的最大大小
-        client_options.max_pool_size = Some(5);
+    /// 执行聚合管道，设置 preview_stage 时只执行到该阶段并追加 $limit，便于逐阶段调试
+    pub async fn aggregate(
+        &self,
+        database_name: &str,
+        collection_name: &str,
+        options: &MongoAggregateOptions,
+    ) -> Response<MongoAggregateResult> {
+        let (pipeline, stage_count, executed_stages) = match build_pipeline(options) {
+            Ok(pipeline) => pipeline,
+            Err(err) => return Response::from_error(err),
+        };
+        let collection = self
+            .client
+            .database(database_name)
+            .collection::<Document>(collection_name);
+        let mut aggregate_options = AggregateOptions::default();
+        aggregate_options.allow_disk_use = Some(options.allow_disk_use);
+        if options.max_time_ms > 0 {
+            aggregate_options.max_time = Some(Duration::from_millis(options.max_time_ms));
+        }
 
-        // 连接MongoDB
-        let client = Client::with_options(client_options).unwrap();
-        Ok(Self { client })
+        let mut cursor = match collection.aggregate(pipeline, aggregate_options).await {
+            Ok(cursor) => cursor,
+            Err(err) => return Response::from_error(format!("执行聚合时出错: {}", err)),
+        };
+        let mut documents = Vec::new();
+        while let Some(item) = cursor.next().await {
+            match item {
+                Ok(document) => documents.push(document_to_ext_json(document)),
+                Err(err) => return Response::from_error(format!("读取聚合结果时出错: {}", err)),
+            }
+        }
+
+        let result = MongoAggregateResult {
+            documents,
+            stage_count,
+            executed_stages,
+        };
+        Response::new("操作成功", Some(result))
+    }
+
+    /// 获取聚合管道的执行计划，verbosity 可选 queryPlanner、executionStats、allPlansExecution
+    pub async fn explain_aggregate(
+        &self,
+        database_name: &str,
+        collection_name: &str,
+        options: &MongoAggregateOptions,
+        verbosity: &str,
+    ) -> Response<serde_json::Value> {
+        let (pipeline, _, _) = match build_pipeline(options) {
+            Ok(pipeline) => pipeline,
+            Err(err) => return Response::from_error(err),
+        };
+        let verbosity = if verbosity.is_empty() {
+            "queryPlanner"
+        } else {
+            verbosity
+        };
+        let pipeline: Vec<Bson> = pipeline.into_iter().map(Bson::Document).collect();
+        let command = doc! {
+            "explain": {
+                "aggregate": collection_name,
+                "pipeline": pipeline,
+                "cursor": {},
+                "allowDiskUse": options.allow_disk_use,
+            },
+            "verbosity": verbosity,
+        };
+
+        match self
+            .client
+            .database(database_name)
+            .run_command(command, None)
+            .await
+        {
+            Ok(result) => Response::new("操作成功", Some(document_to_ext_json(result))),
+            Err(err) => Response::from_error(format!("获取执行计划时出错: {}", err)),
+        }
+    }
+
+    /// 通过 $sample 抽样文档推断集合结构
+    pub async fn infer_schema(
+        &self,
+        database_name: &str,
+        collection_name: &str,
+        sample_size: u64,
+    ) -> Response<MongoSchemaReport> {
+        let sample_size = if sample_size > 0 {
+            sample_size
+        } else {
+            DEFAULT_SCHEMA_SAMPLE_SIZE
+        };
+        let collection = self
+            .client
+            .database(database_name)
+            .collection::<Document>(collection_name);
+        let pipeline = vec![doc! { "$sample": { "size": sample_size as i64 } }];
+        let mut aggregate_options = AggregateOptions::default();
+        aggregate_options.allow_disk_use = Some(true);
+
+        let mut cursor = match collection.aggregate(pipeline, aggregate_options).await {
+            Ok(cursor) => cursor,
+            Err(err) => return Response::from_error(format!("抽样文档时出错: {}", err)),
+        };
+        let mut analyzer = MongoSchemaAnalyzer::new();
+        while let Some(item) = cursor.next().await {
+            match item {
+                Ok(document) => analyzer.observe(&document),
+                Err(err) => return Response::from_error(format!("读取文档时出错: {}", err)),
+            }
+        }
+
+        let report = MongoSchemaReport {
+            database: database_name.to_string(),
+            collection: collection_name.to_string(),
+            sample_size: analyzer.document_count(),
+            fields: analyzer.into_fields(),
+        };
+        Response::new("操作成功", Some(report))
+    }
+
+    /// 创建索引，支持复合、唯一、部分、TTL、文本、2dsphere 和隐藏索引，返回索引名称
+    pub async fn create_index(
+        &self,
+        database_name: &str,
+        collection_name: &str,
+        options: &MongoIndexOptions,
+    ) -> Response<String> {
+        let keys = match parse_ext_json_document(&options.keys) {
+            Ok(keys) if !keys.is_empty() => keys,
+            Ok(_) => return Response::from_error("索引键不能为空"),
+            Err(err) => return Response::from_error(format!("索引键格式错误: {}", err)),
+        };
+        let partial_filter = match parse_ext_json_document(&options.partial_filter) {
+            Ok(document) => document,
+            Err(err) => return Response::from_error(format!("部分索引条件格式错误: {}", err)),
+        };
+        let weights = match parse_ext_json_document(&options.weights) {
+            Ok(document) => document,
+            Err(err) => return Response::from_error(format!("权重格式错误: {}", err)),
+        };
+
+        let mut index_options = IndexOptions::default();
+        index_options.name = options.name.clone().filter(|name| !name.is_empty());
+        if options.unique {
+            index_options.unique = Some(true);
+        }
+        if options.sparse {
+            index_options.sparse = Some(true);
+        }
+        if options.hidden {
+            index_options.hidden = Some(true);
+        }
+        if !partial_filter.is_empty() {
+            index_options.partial_filter_expression = Some(partial_filter);
+        }
+        if !weights.is_empty() {
+            index_options.weights = Some(weights);
+        }
+        index_options.expire_after = options.expire_after_seconds.map(Duration::from_secs);
+        index_options.default_language = options.default_language.clone();
+
+        let index = IndexModel::builder()
+            .keys(keys)
+            .options(index_options)
+            .build();
+        match self
+            .client
+            .database(database_name)
+            .collection::<Document>(collection_name)
+            .create_index(index, None)
+            .await
+        {
+            Ok(result) => Response::new("创建索引成功", Some(result.index_name)),
+            Err(err) => Response::from_error(format!("创建索引时出错: {}", err)),
+        }
+    }
+
+    pub async fn drop_index(
+        &self,
+        database_name: &str,
+        collection_name: &str,
+        index_name: &str,
+    ) -> Response<bool> {
+        if index_name == "_id_" {
+            return Response::from_error("不能删除 _id 索引");
+        }
+        match self
+            .client
+            .database(database_name)
+            .collection::<Document>(collection_name)
+            .drop_index(index_name, None)
+            .await
+        {
+            Ok(_) => Response::new("删除索引成功", Some(true)),
+            Err(err) => Response::from_error(format!("删除索引时出错: {}", err)),
+        }
+    }
+
+    /// 通过 $indexStats 获取各索引的访问次数，用于发现未使用的索引
+    pub async fn index_stats(
+        &self,
+        database_name: &str,
+        collection_name: &str,
+    ) -> Response<Vec<MongoIndexStats>> {
+        let collection = self
+            .client
+            .database(database_name)
+            .collection::<Document>(collection_name);
+        let mut cursor = match collection
+            .aggregate(vec![doc! { "$indexStats": {} }], None)
+            .await
+        {
+            Ok(cursor) => cursor,
+            Err(err) => return Response::from_error(format!("获取索引统计时出错: {}", err)),
+        };
+        let mut stats = Vec::new();
+        while let Some(item) = cursor.next().await {
+            match item {
+                Ok(document) => stats.push(MongoIndexStats::from_document(&document)),
+                Err(err) => return Response::from_error(format!("读取索引统计时出错: {}", err)),
+            }
+        }
+        stats.sort_by_key(|item| item.accesses);
+        Response::new("操作成功", Some(stats))
+    }
+
+    /// 以 executionStats 模式 explain 查询，并汇总胜出计划和扫描量
+    pub async fn explain_find(
+        &self,
+        database_name: &str,
+        collection_name: &str,
+        options: &MongoFindOptions,
+    ) -> Response<MongoExplainSummary> {
+        let mut find = doc! { "find": collection_name };
+        for (key, text) in [
+            ("filter", &options.filter),
+            ("projection", &options.projection),
+            ("sort", &options.sort),
+        ] {
+            match parse_ext_json_document(text) {
+                Ok(document) if !document.is_empty() => {
+                    find.insert(key, document);
+                }
+                Ok(_) => {}
+                Err(err) => return Response::from_error(format!("{} 格式错误: {}", key, err)),
+            }
+        }
+        if options.skip > 0 {
+            find.insert("skip", options.skip as i64);
+        }
+        if options.limit > 0 {
+            find.insert("limit", options.limit);
+        }
+        let mut command = doc! { "explain": find, "verbosity": "executionStats" };
+        if options.max_time_ms > 0 {
+            command.insert("maxTimeMS", options.max_time_ms as i64);
+        }
+
+        match self
+            .client
+            .database(database_name)
+            .run_command(command, None)
+            .await
+        {
+            Ok(result) => {
+                Response::new("操作成功", Some(MongoExplainSummary::from_explain(&result)))
+            }
+            Err(err) => Response::from_error(format!("获取执行计划时出错: {}", err)),
+        }
+    }
+
+    /// 获取副本集成员状态，非副本集部署时返回错误
+    pub async fn replica_set_status(&self) -> Response<MongoReplicaSetStatus> {
+        match self
+            .client
+            .database("admin")
+            .run_command(doc! { "replSetGetStatus": 1 }, None)
+            .await
+        {
+            Ok(result) => Response::new(
+                "操作成功",
+                Some(MongoReplicaSetStatus::from_document(&result)),
+            ),
+            Err(err) => Response::from_error(format!("获取副本集状态时出错: {}", err)),
+        }
+    }
+
+    /// 获取分片集群的分片列表、均衡器状态、chunk 分布和 jumbo chunk，仅支持 mongos
+    pub async fn sharding_status(&self) -> Response<MongoShardingStatus> {
+        let admin_db = self.client.database("admin");
+        match admin_db.run_command(doc! { "isMaster": 1 }, None).await {
+            Ok(result) if result.get_str("msg").ok() == Some("isdbgrid") => {}
+            Ok(_) => return Response::from_error("当前连接不是 mongos"),
+            Err(err) => return Response::from_error(format!("获取节点信息时出错: {}", err)),
+        }
+
+        let shards = match admin_db.run_command(doc! { "listShards": 1 }, None).await {
+            Ok(result) => result
+                .get_array("shards")
+                .map(|shards| {
+                    shards
+                        .iter()
+                        .filter_map(Bson::as_document)
+                        .map(MongoShardInfo::from_document)
+                        .collect()
+                })
+                .unwrap_or_default(),
+            Err(err) => return Response::from_error(format!("获取分片列表时出错: {}", err)),
+        };
+        let balancer = match admin_db
+            .run_command(doc! { "balancerStatus": 1 }, None)
+            .await
+        {
+            Ok(result) => MongoBalancerStatus {
+                mode: result.get_str("mode").unwrap_or_default().to_string(),
+                in_balancer_round: result.get_bool("inBalancerRound").unwrap_or(false),
+                num_balancer_rounds: document_i64(&result, "numBalancerRounds").unwrap_or(0),
+            },
+            Err(err) => return Response::from_error(format!("获取均衡器状态时出错: {}", err)),
+        };
+
+        let config_db = self.client.database("config");
+        // 5.0 起 config.chunks 只记录集合 uuid，需要通过 config.collections 映射回命名空间
+        let mut namespaces = HashMap::new();
+        let collections = match config_db
+            .collection::<Document>("collections")
+            .find(doc! {}, None)
+            .await
+        {
+            Ok(cursor) => collect_documents(cursor).await,
+            Err(err) => Err(err),
+        };
+        match collections {
+            Ok(collections) => {
+                for collection in collections {
+                    if let (Ok(namespace), Some(uuid)) =
+                        (collection.get_str("_id"), collection.get("uuid"))
+                    {
+                        namespaces.insert(uuid.to_string(), namespace.to_string());
+                    }
+                }
+            }
+            Err(err) => return Response::from_error(format!("读取分片集合时出错: {}", err)),
+        }
+        let namespace_of = |chunk: &Document| -> String {
+            if let Ok(namespace) = chunk.get_str("ns") {
+                return namespace.to_string();
+            }
+            match chunk.get("uuid") {
+                Some(uuid) => namespaces
+                    .get(&uuid.to_string())
+                    .cloned()
+                    .unwrap_or_else(|| uuid.to_string()),
+                None => String::new(),
+            }
+        };
+
+        let chunks = config_db.collection::<Document>("chunks");
+        let pipeline = vec![
+            doc! { "$group": {
+                "_id": { "ns": "$ns", "uuid": "$uuid", "shard": "$shard" },
+                "chunks": { "$sum": 1 },
+                "jumbo": { "$sum": { "$cond": [{ "$eq": ["$jumbo", true] }, 1, 0] } },
+            } },
+            doc! { "$sort": { "_id.shard": 1 } },
+        ];
+        let groups = match chunks.aggregate(pipeline, None).await {
+            Ok(cursor) => collect_documents(cursor).await,
+            Err(err) => Err(err),
+        };
+        let mut distributions: BTreeMap<String, MongoChunkDistribution> = BTreeMap::new();
+        match groups {
+            Ok(groups) => {
+                for group in groups {
+                    let key = match group.get_document("_id") {
+                        Ok(key) => key,
+                        Err(_) => continue,
+                    };
+                    let namespace = namespace_of(key);
+                    let count = document_i64(&group, "chunks").unwrap_or(0);
+                    let distribution =
+                        distributions.entry(namespace.clone()).or_insert_with(|| {
+                            MongoChunkDistribution {
+                                namespace,
+                                total_chunks: 0,
+                                jumbo_chunks: 0,
+                                shards: Vec::new(),
+                            }
+                        });
+                    distribution.total_chunks += count;
+                    distribution.jumbo_chunks += document_i64(&group, "jumbo").unwrap_or(0);
+                    distribution.shards.push(MongoShardChunkCount {
+                        shard: key.get_str("shard").unwrap_or_default().to_string(),
+                        chunks: count,
+                    });
+                }
+            }
+            Err(err) => return Response::from_error(format!("统计 chunk 分布时出错: {}", err)),
+        }
+
+        let mut find_options = FindOptions::default();
+        find_options.limit = Some(MAX_JUMBO_CHUNKS);
+        let jumbo = match chunks.find(doc! { "jumbo": true }, find_options).await {
+            Ok(cursor) => collect_documents(cursor).await,
+            Err(err) => Err(err),
+        };
+        let jumbo_chunks = match jumbo {
+            Ok(jumbo) => jumbo
+                .iter()
+                .map(|chunk| MongoJumboChunk {
+                    namespace: namespace_of(chunk),
+                    shard: chunk.get_str("shard").unwrap_or_default().to_string(),
+                    min: chunk
+                        .get("min")
+                        .map(|min| min.clone().into_relaxed_extjson())
+                        .unwrap_or_default(),
+                    max: chunk
+                        .get("max")
+                        .map(|max| max.clone().into_relaxed_extjson())
+                        .unwrap_or_default(),
+                })
+                .collect(),
+            Err(err) => return Response::from_error(format!("读取 jumbo chunk 时出错: {}", err)),
+        };
+
+        let status = MongoShardingStatus {
+            shards,
+            balancer,
+            distributions: distributions.into_values().collect(),
+            jumbo_chunks,
+        };
+        Response::new("操作成功", Some(status))
+    }
+
+    /// 打开 change stream 并在后台任务中逐条回调变更事件，可从 resume token 继续；
+    /// 出错结束前回调一条带最后 resume token 的 error 事件
+    pub async fn watch<F>(
+        &self,
+        session_id: String,
+        options: &MongoWatchOptions,
+        resume_token: Option<serde_json::Value>,
+        mut on_event: F,
+    ) -> Result<MongoWatchHandle, String>
+    where
+        F: FnMut(MongoChangeEvent) + Send + 'static,
+    {
+        let pipeline = if options.pipeline.trim().is_empty() {
+            Vec::new()
+        } else {
+            parse_ext_json_documents(&options.pipeline)
+                .map_err(|err| format!("pipeline 格式错误: {}", err))?
+        };
+        let mut change_options = ChangeStreamOptions::default();
+        if options.full_document {
+            change_options.full_document = Some(FullDocumentType::UpdateLookup);
+        }
+        if let Some(token) = resume_token {
+            change_options.resume_after = Some(
+                resume_token_from_json(token)
+                    .map_err(|err| format!("resume token 无效: {}", err))?,
+            );
+        }
+
+        let stream = if options.database.is_empty() {
+            self.client.watch(pipeline, change_options).await
+        } else if options.collection.is_empty() {
+            self.client
+                .database(&options.database)
+                .watch(pipeline, change_options)
+                .await
+        } else {
+            self.client
+                .database(&options.database)
+                .collection::<Document>(&options.collection)
+                .watch(pipeline, change_options)
+                .await
+        };
+        let mut stream = stream.map_err(|err| format!("打开 change stream 时出错: {}", err))?;
+
+        let (stop_tx, mut stop_rx) = oneshot::channel::<()>();
+        let join = tokio::spawn(async move {
+            loop {
+                tokio::select! {
+                    _ = &mut stop_rx => break,
+                    item = stream.next() => match item {
+                        Some(Ok(event)) => on_event(MongoChangeEvent::from_event(&session_id, event)),
+                        Some(Err(err)) => {
+                            let token = stream
+                                .resume_token()
+                                .and_then(|token| resume_token_to_json(&token));
+                            on_event(MongoChangeEvent::error(
+                                &session_id,
+                                format!("change stream 中断: {}", err),
+                                token,
+                            ));
+                            break;
+                        }
+                        None => break,
+                    },
+                }
+            }
+            stream
+                .resume_token()
+                .and_then(|token| resume_token_to_json(&token))
+        });
+        Ok(MongoWatchHandle::new(stop_tx, join))
+    }
+
+    /// 导出集合到文件，支持 JSON 数组、JSON Lines（canonical 扩展 JSON）和按字段路径展开的 CSV
+    pub async fn export_collection(
+        &self,
+        database_name: &str,
+        collection_name: &str,
+        options: &MongoExportOptions,
+        file_path: &str,
+    ) -> Response<MongoExportResult> {
+        let filter = match parse_ext_json_document(&options.filter) {
+            Ok(document) => document,
+            Err(err) => return Response::from_error(format!("filter 格式错误: {}", err)),
+        };
+        let projection = match parse_ext_json_document(&options.projection) {
+            Ok(document) => document,
+            Err(err) => return Response::from_error(format!("projection 格式错误: {}", err)),
+        };
+        let sort = match parse_ext_json_document(&options.sort) {
+            Ok(document) => document,
+            Err(err) => return Response::from_error(format!("sort 格式错误: {}", err)),
+        };
+        let mut find_options = FindOptions::default();
+        if !projection.is_empty() {
+            find_options.projection = Some(projection);
+        }
+        if !sort.is_empty() {
+            find_options.sort = Some(sort);
+        }
+        if options.limit > 0 {
+            find_options.limit = Some(options.limit);
+        }
+
+        let mut cursor = match self
+            .client
+            .database(database_name)
+            .collection::<Document>(collection_name)
+            .find(filter, find_options)
+            .await
+        {
+            Ok(cursor) => cursor,
+            Err(err) => return Response::from_error(format!("查询文档时出错: {}", err)),
+        };
+        let mut writer = match File::create(file_path) {
+            Ok(file) => BufWriter::new(file),
+            Err(err) => return Response::from_error(format!("创建导出文件失败: {}", err)),
+        };
+
+        // CSV 未指定字段时先读取前若干条文档推断列
+        let mut fields = options.fields.clone();
+        let mut pending = Vec::new();
+        if options.format == MongoTransferFormat::Csv && fields.is_empty() {
+            while pending.len() < CSV_FIELD_SAMPLE_SIZE {
+                match cursor.next().await {
+                    Some(Ok(document)) => pending.push(document),
+                    Some(Err(err)) => {
+                        return Response::from_error(format!("读取文档时出错: {}", err))
+                    }
+                    None => break,
+                }
+            }
+            for document in &pending {
+                collect_leaf_paths(document, "", &mut fields);
+            }
+        }
+        let header = match options.format {
+            MongoTransferFormat::JsonArray => "[".to_string(),
+            MongoTransferFormat::JsonLines => String::new(),
+            MongoTransferFormat::Csv => fields
+                .iter()
+                .map(|field| csv_escape(field))
+                .collect::<Vec<String>>()
+                .join(","),
+        };
+        if !header.is_empty() {
+            if let Err(err) = writeln!(writer, "{}", header) {
+                return Response::from_error(format!("写入导出文件失败: {}", err));
+            }
+        }
+
+        let mut exported = 0;
+        let mut pending = pending.into_iter();
+        loop {
+            let document = match pending.next() {
+                Some(document) => document,
+                None => match cursor.next().await {
+                    Some(Ok(document)) => document,
+                    Some(Err(err)) => {
+                        return Response::from_error(format!("读取文档时出错: {}", err))
+                    }
+                    None => break,
+                },
+            };
+            let line = match options.format {
+                MongoTransferFormat::JsonArray => {
+                    let json = Bson::Document(document).into_relaxed_extjson().to_string();
+                    if exported == 0 {
+                        json
+                    } else {
+                        format!(",{}", json)
+                    }
+                }
+                MongoTransferFormat::JsonLines => document_to_ext_json(document).to_string(),
+                MongoTransferFormat::Csv => flatten_csv_row(&document, &fields)
+                    .iter()
+                    .map(|value| csv_escape(value))
+                    .collect::<Vec<String>>()
+                    .join(","),
+            };
+            if let Err(err) = writeln!(writer, "{}", line) {
+                return Response::from_error(format!("写入导出文件失败: {}", err));
+            }
+            exported += 1;
+        }
+        if options.format == MongoTransferFormat::JsonArray {
+            if let Err(err) = writeln!(writer, "]") {
+                return Response::from_error(format!("写入导出文件失败: {}", err));
+            }
+        }
+        if let Err(err) = writer.flush() {
+            return Response::from_error(format!("写入导出文件失败: {}", err));
+        }
+
+        let result = MongoExportResult {
+            file_path: file_path.to_string(),
+            exported,
+            fields,
+        };
+        Response::new("导出成功", Some(result))
+    }
+
+    /// 从文件导入文档，批量插入或按 upsert_fields 匹配后替换，逐条记录失败原因
+    pub async fn import_collection(
+        &self,
+        database_name: &str,
+        collection_name: &str,
+        options: &MongoImportOptions,
+        file_path: &str,
+    ) -> Response<MongoImportResult> {
+        let content = match fs::read_to_string(file_path) {
+            Ok(content) => content,
+            Err(err) => return Response::from_error(format!("读取导入文件失败: {}", err)),
+        };
+        let (documents, failed) = match parse_import_documents(options.format, &content) {
+            Ok(parsed) => parsed,
+            Err(err) => return Response::from_error(err),
+        };
+        let mut result = MongoImportResult {
+            total: documents.len() + failed.len(),
+            failed,
+            ..Default::default()
+        };
+        let collection = self
+            .client
+            .database(database_name)
+            .collection::<Document>(collection_name);
+
+        if options.upsert_fields.is_empty() {
+            let batch_size = if options.batch_size > 0 {
+                options.batch_size
+            } else {
+                IMPORT_BATCH_SIZE
+            };
+            let mut insert_options = InsertManyOptions::default();
+            // 无序插入，单条失败不影响同批其他文档
+            insert_options.ordered = Some(false);
+            for batch in documents.chunks(batch_size) {
+                let batch_documents: Vec<&Document> =
+                    batch.iter().map(|(_, document)| document).collect();
+                match collection
+                    .insert_many(batch_documents, insert_options.clone())
+                    .await
+                {
+                    Ok(inserted) => result.inserted += inserted.inserted_ids.len(),
+                    Err(err) => match err.kind.as_ref() {
+                        ErrorKind::BulkWrite(BulkWriteFailure {
+                            write_errors: Some(write_errors),
+                            ..
+                        }) => {
+                            result.inserted += batch.len().saturating_sub(write_errors.len());
+                            for write_error in write_errors {
+                                if let Some((index, _)) = batch.get(write_error.index) {
+                                    result.failed.push(MongoDocumentError {
+                                        index: *index,
+                                        error: write_error.message.clone(),
+                                    });
+                                }
+                            }
+                        }
+                        _ => {
+                            for (index, _) in batch {
+                                result.failed.push(MongoDocumentError {
+                                    index: *index,
+                                    error: err.to_string(),
+                                });
+                            }
+                        }
+                    },
+                }
+            }
+        } else {
+            let mut replace_options = ReplaceOptions::default();
+            replace_options.upsert = Some(true);
+            for (index, document) in &documents {
+                let mut filter = Document::new();
+                for field in &options.upsert_fields {
+                    if let Some(value) = get_path(document, field) {
+                        filter.insert(field.clone(), value.clone());
+                    }
+                }
+                if filter.len() != options.upsert_fields.len() {
+                    result.failed.push(MongoDocumentError {
+                        index: *index,
+                        error: format!("缺少匹配字段: {}", options.upsert_fields.join(", ")),
+                    });
+                    continue;
+                }
+                match collection
+                    .replace_one(filter, document, replace_options.clone())
+                    .await
+                {
+                    Ok(replaced) if replaced.upserted_id.is_some() => result.upserted += 1,
+                    Ok(_) => result.replaced += 1,
+                    Err(err) => result.failed.push(MongoDocumentError {
+                        index: *index,
+                        error: err.to_string(),
+                    }),
+                }
+            }
+        }
+        result.failed.sort_by_key(|failed| failed.index);
+        Response::new("导入完成", Some(result))
+    }
+
+    pub async fn collection_names(&self, database_name: &str) -> Response<Vec<String>> {
+        let db = self.client.database(database_name);
+
+        match db.list_collection_names(None).await {
+            Ok(collections) => Response::new("Operation successful", Some(collections)),
+            Err(err) => Response::from_error(format!("Error listing collections: {}", err)),
+        }
+    }
+    pub async fn get_server_info(&self) -> Response<MongoServerInfoCol> {
+        let admin_db = self.client.database("admin");
+        let command = doc! {
+            "serverStatus": 1,
+            "recordStats": 0,
+            "repl": 0,
+            "metrics": 1,
+            "locks": 0,
+            "backgroundFlushing": 0,
+            "asserts": 0,
+            "connections": 1,
+            "extra_info": 0,
+            "index_counters": 0,
+            "network": 1,
+            "opcounters": 1,
+            "opcountersRepl": 0,
+            "mem": 1,
+            "wiredTiger": 1,
+            "ft": 0,
+            "uptime": 1,
+            "uptimeEstimate": 1,
+        };
+
+        match admin_db.run_command(command, None).await {
+            Ok(result) => Response::new(
+                "Server info retrieved successfully",
+                Some(MongoServerInfoCol::from_server_status(&result)),
+            ),
+            Err(err) => Response::from_error(format!("Failed to retrieve server info: {}", err)),
+        }
+    }
+
+    /// 当前正在执行的操作，按运行时间倒序
+    pub async fn current_op(&self, filter: &MongoCurrentOpFilter) -> Response<Vec<MongoCurrentOp>> {
+        let mut command = doc! { "currentOp": 1, "$all": filter.all };
+        if !filter.ns.is_empty() {
+            command.insert(
+                "ns",
+                // 按命名空间整段匹配，db 不会匹配到 db2 的操作
+                doc! { "$regex": format!("^{}(\\.|$)", regex_escape(&filter.ns)) },
+            );
+        }
+        if filter.min_secs_running > 0 {
+            command.insert("secs_running", doc! { "$gte": filter.min_secs_running });
+        }
+
+        match self
+            .client
+            .database("admin")
+            .run_command(command, None)
+            .await
+        {
+            Ok(result) => {
+                let mut operations: Vec<MongoCurrentOp> = result
+                    .get_array("inprog")
+                    .map(|inprog| {
+                        inprog
+                            .iter()
+                            .filter_map(|item| item.as_document())
+                            .map(MongoCurrentOp::from_document)
+                            .collect()
+                    })
+                    .unwrap_or_default();
+                operations.sort_by_key(|operation| std::cmp::Reverse(operation.microsecs_running));
+                Response::new("操作成功", Some(operations))
+            }
+            Err(err) => Response::from_error(format!("获取当前操作时出错: {}", err)),
+        }
+    }
+
+    /// 终止操作，opid 为数字或分片集群下的 `shard:opid`
+    pub async fn kill_op(&self, opid: &str) -> Response<bool> {
+        let op = match opid.parse::<i64>() {
+            Ok(opid) => match i32::try_from(opid) {
+                Ok(opid) => Bson::Int32(opid),
+                Err(_) => Bson::Int64(opid),
+            },
+            Err(_) => Bson::String(opid.to_string()),
+        };
+        match self
+            .client
+            .database("admin")
+            .run_command(doc! { "killOp": 1, "op": op }, None)
+            .await
+        {
+            Ok(_) => Response::new("操作成功", Some(true)),
+            Err(err) => Response::from_error(format!("终止操作时出错: {}", err)),
+        }
+    }
+
+    /// 查询数据库 profiler 级别和慢操作阈值
+    pub async fn profiling_status(&self, database_name: &str) -> Response<MongoProfilingStatus> {
+        match self
+            .client
+            .database(database_name)
+            .run_command(doc! { "profile": -1 }, None)
+            .await
+        {
+            Ok(result) => Response::new(
+                "操作成功",
+                Some(MongoProfilingStatus::from_document(&result)),
+            ),
+            Err(err) => Response::from_error(format!("获取 profiler 状态时出错: {}", err)),
+        }
+    }
+
+    /// 设置数据库 profiler 级别（0/1/2），slowms 为空时保持原阈值，返回设置后的状态
+    pub async fn set_profiling_level(
+        &self,
+        database_name: &str,
+        level: i32,
+        slowms: Option<i64>,
+    ) -> Response<MongoProfilingStatus> {
+        if !(0..=2).contains(&level) {
+            return Response::from_error(format!("profiler 级别必须是 0、1 或 2: {}", level));
+        }
+        let mut command = doc! { "profile": level };
+        if let Some(slowms) = slowms {
+            command.insert("slowms", slowms);
+        }
+        let database = self.client.database(database_name);
+        if let Err(err) = database.run_command(command, None).await {
+            return Response::from_error(format!("设置 profiler 级别时出错: {}", err));
+        }
+        self.profiling_status(database_name).await
+    }
+
+    /// 读取 system.profile 中的记录，按时间倒序
+    pub async fn profile_entries(
+        &self,
+        database_name: &str,
+        query: &MongoProfileQuery,
+    ) -> Response<Vec<MongoProfileEntry>> {
+        let mut filter = Document::new();
+        if !query.ns.is_empty() {
+            filter.insert("ns", query.ns.as_str());
+        }
+        if query.min_millis > 0 {
+            filter.insert("millis", doc! { "$gte": query.min_millis });
+        }
+        let mut find_options = FindOptions::default();
+        find_options.sort = Some(doc! { "ts": -1 });
+        find_options.limit = Some(if query.limit > 0 {
+            query.limit
+        } else {
+            DEFAULT_PROFILE_LIMIT
+        });
+
+        let cursor = match self
+            .client
+            .database(database_name)
+            .collection::<Document>("system.profile")
+            .find(filter, find_options)
+            .await
+        {
+            Ok(cursor) => cursor,
+            Err(err) => {
+                return Response::from_error(format!("读取 system.profile 时出错: {}", err))
+            }
+        };
+        match collect_documents(cursor).await {
+            Ok(documents) => Response::new(
+                "操作成功",
+                Some(
+                    documents
+                        .iter()
+                        .map(MongoProfileEntry::from_document)
+                        .collect(),
+                ),
+            ),
+            Err(err) => Response::from_error(format!("读取 system.profile 时出错: {}", err)),
+        }
+    }
+
+    /// 列出数据库用户，database 为空时列出所有数据库的用户；role 不为空时只返回拥有该角色的用户
+    pub async fn list_users(
+        &self,
+        database_name: &str,
+        role: Option<&MongoRoleRef>,
+    ) -> Response<Vec<MongoUserInfo>> {
+        let (database_name, mut command) = if database_name.is_empty() {
+            ("admin", doc! { "usersInfo": { "forAllDBs": true } })
+        } else {
+            (database_name, doc! { "usersInfo": 1 })
+        };
+        if let Some(role) = role {
+            command.insert(
+                "filter",
+                doc! { "roles": { "$elemMatch": role.to_document() } },
+            );
+        }
+
+        match self
+            .client
+            .database(database_name)
+            .run_command(command, None)
+            .await
+        {
+            Ok(result) => {
+                let users = result
+                    .get_array("users")
+                    .map(|users| {
+                        users
+                            .iter()
+                            .filter_map(|user| user.as_document())
+                            .map(MongoUserInfo::from_document)
+                            .collect()
+                    })
+                    .unwrap_or_default();
+                Response::new("操作成功", Some(users))
+            }
+            Err(err) => Response::from_error(format!("获取用户列表时出错: {}", err)),
+        }
+    }
+
+    pub async fn create_user(
+        &self,
+        database_name: &str,
+        options: &MongoUserOptions,
+    ) -> Response<bool> {
+        if options.user.is_empty() || options.password.is_empty() {
+            return Response::from_error("用户名和密码不能为空".to_string());
+        }
+        let mut command = doc! {
+            "createUser": options.user.as_str(),
+            "pwd": options.password.as_str(),
+            "roles": role_documents(&options.roles),
+        };
+        if let Err(err) = apply_user_extras(&mut command, options) {
+            return Response::from_error(err);
+        }
+        self.run_admin_command(database_name, command, "创建用户")
+            .await
+    }
+
+    /// 修改用户，整体替换角色列表，password 为空时不修改密码
+    pub async fn update_user(
+        &self,
+        database_name: &str,
+        options: &MongoUserOptions,
+    ) -> Response<bool> {
+        let mut command = doc! {
+            "updateUser": options.user.as_str(),
+            "roles": role_documents(&options.roles),
+        };
+        if !options.password.is_empty() {
+            command.insert("pwd", options.password.as_str());
+        }
+        if let Err(err) = apply_user_extras(&mut command, options) {
+            return Response::from_error(err);
+        }
+        self.run_admin_command(database_name, command, "修改用户")
+            .await
+    }
+
+    pub async fn drop_user(&self, database_name: &str, user: &str) -> Response<bool> {
+        self.run_admin_command(database_name, doc! { "dropUser": user }, "删除用户")
+            .await
+    }
+
+    /// 为用户追加角色
+    pub async fn grant_roles(
+        &self,
+        database_name: &str,
+        user: &str,
+        roles: &[MongoRoleRef],
+    ) -> Response<bool> {
+        let command = doc! {
+            "grantRolesToUser": user,
+            "roles": role_documents(roles),
+        };
+        self.run_admin_command(database_name, command, "授予角色")
+            .await
+    }
+
+    /// 收回用户的角色
+    pub async fn revoke_roles(
+        &self,
+        database_name: &str,
+        user: &str,
+        roles: &[MongoRoleRef],
+    ) -> Response<bool> {
+        let command = doc! {
+            "revokeRolesFromUser": user,
+            "roles": role_documents(roles),
+        };
+        self.run_admin_command(database_name, command, "收回角色")
+            .await
+    }
+
+    /// 列出数据库角色并展开权限，show_builtin 为 true 时包含内置角色
+    pub async fn list_roles(
+        &self,
+        database_name: &str,
+        show_builtin: bool,
+    ) -> Response<Vec<MongoRoleInfo>> {
+        let command = doc! {
+            "rolesInfo": 1,
+            "showPrivileges": true,
+            "showBuiltinRoles": show_builtin,
+        };
+        match self
+            .client
+            .database(database_name)
+            .run_command(command, None)
+            .await
+        {
+            Ok(result) => {
+                let roles = result
+                    .get_array("roles")
+                    .map(|roles| {
+                        roles
+                            .iter()
+                            .filter_map(|role| role.as_document())
+                            .map(MongoRoleInfo::from_document)
+                            .collect()
+                    })
+                    .unwrap_or_default();
+                Response::new("操作成功", Some(roles))
+            }
+            Err(err) => Response::from_error(format!("获取角色列表时出错: {}", err)),
+        }
+    }
+
+    pub async fn create_role(
+        &self,
+        database_name: &str,
+        options: &MongoRoleOptions,
+    ) -> Response<bool> {
+        let command = doc! {
+            "createRole": options.role.as_str(),
+            "privileges": privilege_documents(&options.privileges),
+            "roles": role_documents(&options.roles),
+        };
+        self.run_admin_command(database_name, command, "创建角色")
+            .await
+    }
+
+    /// 修改自定义角色，整体替换权限和继承角色
+    pub async fn update_role(
+        &self,
+        database_name: &str,
+        options: &MongoRoleOptions,
+    ) -> Response<bool> {
+        let command = doc! {
+            "updateRole": options.role.as_str(),
+            "privileges": privilege_documents(&options.privileges),
+            "roles": role_documents(&options.roles),
+        };
+        self.run_admin_command(database_name, command, "修改角色")
+            .await
+    }
+
+    pub async fn drop_role(&self, database_name: &str, role: &str) -> Response<bool> {
+        self.run_admin_command(database_name, doc! { "dropRole": role }, "删除角色")
+            .await
+    }
+
+    /// 执行只关心成功与否的管理命令
+    async fn run_admin_command(
+        &self,
+        database_name: &str,
+        command: Document,
+        action: &str,
+    ) -> Response<bool> {
+        match self
+            .client
+            .database(database_name)
+            .run_command(command, None)
+            .await
+        {
+            Ok(_) => Response::new("操作成功", Some(true)),
+            Err(err) => Response::from_error(format!("{}时出错: {}", action, err)),
+        }
+    }
+    pub async fn db_names(&self) -> Response<Vec<String>> {
+        match self.client.list_database_names(None, None).await {
+            Ok(databases) => Response::new("Operation successful", Some(databases)),
+            Err(err) => Response::from_error(format!("Error listing database names: {}", err)),
+        }
+    }
+    pub async fn new(
+        mongo_user_password: &MongoUserPassword,
+    ) -> Result<Self, Box<dyn std::error::Error>> {
+        let mongodb_url = mongo_user_password.connection_url();
+
+        // 创建客户端选项
+        let mut client_options = ClientOptions::parse(&mongodb_url).await?;
+
+        // 设置连接池的最大大小
+        client_options.max_pool_size = Some(5);
+
+        // 连接MongoDB
+        let client = Client::with_options(client_options)?;
+        Ok(Self { client })
+    }
+}
+
+/// 将扩展 JSON 字符串解析为 Document，空字符串视为空文档
+pub fn parse_ext_json_document(text: &str) -> Result<Document, String> {
+    if text.trim().is_empty() {
+        return Ok(Document::new());
+    }
+    let value: serde_json::Value = serde_json::from_str(text).map_err(|err| err.to_string())?;
+    match Bson::try_from(value) {
+        Ok(Bson::Document(document)) => Ok(document),
+        Ok(_) => Err("必须是 JSON 对象".to_string()),
+        Err(err) => Err(err.to_string()),
+    }
+}
+
+/// 将扩展 JSON 对象或对象数组解析为 Document 列表
+pub fn parse_ext_json_documents(text: &str) -> Result<Vec<Document>, String> {
+    let value: serde_json::Value = serde_json::from_str(text).map_err(|err| err.to_string())?;
+    let values = match value {
+        serde_json::Value::Array(values) => values,
+        value => vec![value],
+    };
+    values
+        .into_iter()
+        .map(|value| match Bson::try_from(value) {
+            Ok(Bson::Document(document)) => Ok(document),
+            Ok(_) => Err("必须是 JSON 对象".to_string()),
+            Err(err) => Err(err.to_string()),
+        })
+        .collect()
+}
+
+/// 将 Document 转换为 canonical 扩展 JSON
+pub fn document_to_ext_json(document: Document) -> serde_json::Value {
+    Bson::Document(document).into_canonical_extjson()
+}
+
+/// 解析聚合管道，按 preview_stage 截断并追加 $limit，返回 (管道, 总阶段数, 执行阶段数)
+fn build_pipeline(
+    options: &MongoAggregateOptions,
+) -> Result<(Vec<Document>, usize, usize), String> {
+    let mut pipeline = match parse_ext_json_documents(&options.pipeline) {
+        Ok(pipeline) => pipeline,
+        Err(err) => return Err(format!("pipeline 格式错误: {}", err)),
+    };
+    for (index, stage) in pipeline.iter().enumerate() {
+        if stage.len() != 1 || !stage.keys().all(|key| key.starts_with('$')) {
+            return Err(format!("第 {} 个阶段必须是单个 $ 操作符", index + 1));
+        }
+    }
+    let stage_count = pipeline.len();
+
+    match options.preview_stage {
+        Some(stage) => {
+            if stage >= stage_count {
+                return Err(format!("预览阶段超出范围，管道共 {} 个阶段", stage_count));
+            }
+            pipeline.truncate(stage + 1);
+            if pipeline.iter().any(is_output_stage) {
+                return Err("预览不能包含 $out 或 $merge 阶段".to_string());
+            }
+            let limit = if options.limit > 0 {
+                options.limit
+            } else {
+                DEFAULT_PAGE_SIZE
+            };
+            pipeline.push(doc! { "$limit": limit });
+            Ok((pipeline, stage_count, stage + 1))
+        }
+        None => {
+            // $out / $merge 必须是最后一个阶段，此时不能再追加 $limit
+            if options.limit > 0 && !pipeline.last().map(is_output_stage).unwrap_or(false) {
+                pipeline.push(doc! { "$limit": options.limit });
+            }
+            Ok((pipeline, stage_count, stage_count))
+        }
+    }
+}
+
+fn is_output_stage(stage: &Document) -> bool {
+    stage.contains_key("$out") || stage.contains_key("$merge")
+}
+
+/// 导入文件解析结果：(序号, 文档) 列表和无法解析的记录
+pub type ParsedImport = (Vec<(usize, Document)>, Vec<MongoDocumentError>);
+
+/// 解析导入文件，序号从 1 开始，JSON Lines 为行号
+pub fn parse_import_documents(
+    format: MongoTransferFormat,
+    content: &str,
+) -> Result<ParsedImport, String> {
+    let mut documents = Vec::new();
+    let mut failed = Vec::new();
+    let mut push = |index: usize, parsed: Result<Document, String>| match parsed {
+        Ok(document) => documents.push((index, document)),
+        Err(error) => failed.push(MongoDocumentError { index, error }),
+    };
+    let to_document = |value: serde_json::Value| match Bson::try_from(value) {
+        Ok(Bson::Document(document)) => Ok(document),
+        Ok(_) => Err("必须是 JSON 对象".to_string()),
+        Err(err) => Err(err.to_string()),
+    };
+
+    match format {
+        MongoTransferFormat::JsonArray => {
+            let values = match serde_json::from_str::<serde_json::Value>(content) {
+                Ok(serde_json::Value::Array(values)) => values,
+                Ok(_) => return Err("JSON 文件必须是数组".to_string()),
+                Err(err) => return Err(format!("JSON 格式错误: {}", err)),
+            };
+            for (index, value) in values.into_iter().enumerate() {
+                push(index + 1, to_document(value));
+            }
+        }
+        MongoTransferFormat::JsonLines => {
+            for (index, line) in content.lines().enumerate() {
+                if line.trim().is_empty() {
+                    continue;
+                }
+                let parsed = serde_json::from_str::<serde_json::Value>(line)
+                    .map_err(|err| err.to_string())
+                    .and_then(to_document);
+                push(index + 1, parsed);
+            }
+        }
+        MongoTransferFormat::Csv => {
+            let mut records = parse_csv_records(content).into_iter();
+            let header: Vec<String> = match records.next() {
+                Some(header) => header
+                    .iter()
+                    .map(|field| field.trim().to_string())
+                    .collect(),
+                None => return Ok((documents, failed)),
+            };
+            for (index, record) in records.enumerate() {
+                if record.len() == 1 && record[0].is_empty() {
+                    continue;
+                }
+                if record.len() != header.len() {
+                    push(
+                        index + 1,
+                        Err(format!(
+                            "列数 {} 与表头列数 {} 不一致",
+                            record.len(),
+                            header.len()
+                        )),
+                    );
+                    continue;
+                }
+                let mut document = Document::new();
+                let parsed = header
+                    .iter()
+                    .zip(record.iter())
+                    .try_for_each(|(path, value)| match parse_csv_value(value) {
+                        Some(value) => set_path(&mut document, path, value),
+                        None => Ok(()),
+                    })
+                    .map(|_| document);
+                push(index + 1, parsed);
+            }
+        }
+    }
+    Ok((documents, failed))
+}
+
+/// 按字段路径展开为 CSV 列值，嵌套文档、数组、ObjectId、日期等以 relaxed 扩展 JSON 输出
+pub fn flatten_csv_row(document: &Document, fields: &[String]) -> Vec<String> {
+    fields
+        .iter()
+        .map(|field| match get_path(document, field) {
+            None | Some(Bson::Null) => String::new(),
+            Some(Bson::String(value)) => value.clone(),
+            Some(Bson::Int32(value)) => value.to_string(),
+            Some(Bson::Int64(value)) => value.to_string(),
+            Some(Bson::Double(value)) => value.to_string(),
+            Some(Bson::Boolean(value)) => value.to_string(),
+            Some(value) => value.clone().into_relaxed_extjson().to_string(),
+        })
+        .collect()
+}
+
+/// 按 a.b.0.c 形式的路径读取字段，数字段用于数组下标
+fn get_path<'a>(document: &'a Document, path: &str) -> Option<&'a Bson> {
+    let mut segments = path.split('.');
+    let mut current = document.get(segments.next()?)?;
+    for segment in segments {
+        current = match current {
+            Bson::Document(document) => document.get(segment)?,
+            Bson::Array(items) => items.get(segment.parse::<usize>().ok()?)?,
+            _ => return None,
+        };
+    }
+    Some(current)
+}
+
+/// 按 a.b.c 形式的路径写入字段，中间层级不存在时自动创建
+fn set_path(document: &mut Document, path: &str, value: Bson) -> Result<(), String> {
+    match path.split_once('.') {
+        None => {
+            document.insert(path, value);
+            Ok(())
+        }
+        Some((head, rest)) => {
+            if !document.contains_key(head) {
+                document.insert(head, Document::new());
+            }
+            match document.get_document_mut(head) {
+                Ok(child) => set_path(child, rest, value),
+                Err(_) => Err(format!("字段 {} 不是对象", head)),
+            }
+        }
+    }
+}
+
+/// 收集文档中所有叶子字段的路径，数组视为叶子
+fn collect_leaf_paths(document: &Document, prefix: &str, paths: &mut Vec<String>) {
+    for (key, value) in document {
+        let path = if prefix.is_empty() {
+            key.to_string()
+        } else {
+            format!("{}.{}", prefix, key)
+        };
+        match value {
+            Bson::Document(child) if !child.is_empty() => collect_leaf_paths(child, &path, paths),
+            _ => {
+                if !paths.contains(&path) {
+                    paths.push(path);
+                }
+            }
+        }
+    }
+}
+
+/// 推断 CSV 单元格类型：空值忽略，布尔、整数、浮点数按类型写入，扩展 JSON 对象按 BSON 解析，其余为字符串
+fn parse_csv_value(text: &str) -> Option<Bson> {
+    if text.is_empty() {
+        return None;
+    }
+    match text {
+        "true" => return Some(Bson::Boolean(true)),
+        "false" => return Some(Bson::Boolean(false)),
+        _ => {}
+    }
+    // 带前导 0 的数字（编号、邮编等）保持字符串
+    let leading_zero = text.len() > 1 && text.starts_with('0') && !text.starts_with("0.");
+    if !leading_zero {
+        if let Ok(value) = text.parse::<i64>() {
+            return Some(match i32::try_from(value) {
+                Ok(value) => Bson::Int32(value),
+                Err(_) => Bson::Int64(value),
+            });
+        }
+        let numeric = text
+            .chars()
+            .all(|c| c.is_ascii_digit() || matches!(c, '.' | '-' | '+' | 'e' | 'E'));
+        if numeric {
+            if let Ok(value) = text.parse::<f64>() {
+                return Some(Bson::Double(value));
+            }
+        }
+    }
+    if text.starts_with('{') || text.starts_with('[') {
+        if let Ok(value) = serde_json::from_str::<serde_json::Value>(text) {
+            if let Ok(value) = Bson::try_from(value) {
+                return Some(value);
+            }
+        }
+    }
+    Some(Bson::String(text.to_string()))
+}
+
+/// 将 resume token 转换为 canonical 扩展 JSON 以便持久化
+pub fn resume_token_to_json(token: &ResumeToken) -> Option<serde_json::Value> {
+    mongodb::bson::to_bson(token)
+        .ok()
+        .map(Bson::into_canonical_extjson)
+}
+
+/// 从持久化的扩展 JSON 恢复 resume token
+pub fn resume_token_from_json(token: serde_json::Value) -> Result<ResumeToken, String> {
+    let token = Bson::try_from(token).map_err(|err| err.to_string())?;
+    mongodb::bson::from_bson(token).map_err(|err| err.to_string())
+}
+
+/// 读取游标中的全部文档
+async fn collect_documents(mut cursor: Cursor<Document>) -> mongodb::error::Result<Vec<Document>> {
+    let mut documents = Vec::new();
+    while let Some(document) = cursor.next().await {
+        documents.push(document?);
+    }
+    Ok(documents)
+}
+
+fn role_documents(roles: &[MongoRoleRef]) -> Vec<Document> {
+    roles.iter().map(MongoRoleRef::to_document).collect()
+}
+
+fn privilege_documents(privileges: &[MongoPrivilege]) -> Vec<Document> {
+    privileges.iter().map(MongoPrivilege::to_document).collect()
+}
+
+/// 写入用户的 customData 和 mechanisms
+fn apply_user_extras(command: &mut Document, options: &MongoUserOptions) -> Result<(), String> {
+    let custom_data = parse_ext_json_document(&options.custom_data)
+        .map_err(|err| format!("customData 格式错误: {}", err))?;
+    if !custom_data.is_empty() {
+        command.insert("customData", custom_data);
+    }
+    if !options.mechanisms.is_empty() {
+        command.insert("mechanisms", options.mechanisms.clone());
+    }
+    Ok(())
+}
+
+/// 转义正则元字符，用于按命名空间前缀匹配
+fn regex_escape(text: &str) -> String {
+    let mut escaped = String::with_capacity(text.len());
+    for c in text.chars() {
+        if !c.is_alphanumeric() && c != '_' && c != '-' {
+            escaped.push('\\');
+        }
+        escaped.push(c);
+    }
+    escaped
+}
+
+fn is_descending(direction: &Bson) -> bool {
+    match direction {
+        Bson::Int32(value) => *value < 0,
+        Bson::Int64(value) => *value < 0,
+        Bson::Double(value) => *value < 0.0,
+        _ => false,
     }
 }
@@ -0,0 +1,1125 @@
+use std::collections::BTreeMap;
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::sync::Arc;
+use std::thread::JoinHandle;
+
+use serde::{Deserialize, Serialize};
+
+use crate::op::redis_op::{KeyType, ZSetEnt};
+
+/// Pub/Sub 收到的一条消息
+#[derive(Serialize, Deserialize, Debug, Clone)]
+pub struct PubSubMessage {
+    /// 所属订阅会话 id
+    pub session_id: String,
+    /// 消息所在的频道
+    pub channel: String,
+    /// 通过 PSUBSCRIBE 匹配时的模式
+    pub pattern: Option<String>,
+    pub payload: String,
+    /// 收到消息的时间（毫秒时间戳）
+    pub timestamp: i64,
+}
+
+/// 后台监听推送的事件，连接中断时推送 Closed 后结束监听
+#[derive(Serialize, Deserialize, Debug, Clone)]
+#[serde(tag = "kind", rename_all = "snake_case")]
+pub enum RedisListenEvent<T> {
+    Message(T),
+    Closed { session_id: String, error: String },
+}
+
+/// PUBSUB NUMSUB 的单个频道订阅数
+#[derive(Serialize, Deserialize, Debug)]
+pub struct PubSubChannelCount {
+    pub channel: String,
+    pub subscribers: i64,
+}
+
+/// 后台监听会话（订阅、MONITOR 等）的描述信息
+#[derive(Serialize, Deserialize, Debug, Clone)]
+pub struct RedisSessionInfo {
+    pub session_id: String,
+    /// 对应 RedisStorageEntity 的 id
+    pub config_id: String,
+    /// 会话类型，例如 pubsub
+    pub kind: String,
+    pub channels: Vec<String>,
+    pub patterns: Vec<String>,
+    /// 会话开始时间（毫秒时间戳）
+    pub started_at: i64,
+}
+
+/// 后台监听线程的句柄，调用 stop 或 drop 时结束监听
+pub struct RedisListenHandle {
+    stop: Arc<AtomicBool>,
+    join: Option<JoinHandle<()>>,
+}
+
+impl RedisListenHandle {
+    pub fn new(stop: Arc<AtomicBool>, join: JoinHandle<()>) -> Self {
+        Self {
+            stop,
+            join: Some(join),
+        }
+    }
+
+    pub fn is_running(&self) -> bool {
+        !self.stop.load(Ordering::SeqCst)
+    }
+
+    pub fn stop(&mut self) {
+        self.stop.store(true, Ordering::SeqCst);
+        if let Some(join) = self.join.take() {
+            let _ = join.join();
+        }
+    }
+}
+
+impl Drop for RedisListenHandle {
+    fn drop(&mut self) {
+        self.stop.store(true, Ordering::SeqCst);
+    }
+}
+
+/// MONITOR 输出的一行命令
+#[derive(Serialize, Deserialize, Debug, Clone)]
+pub struct MonitorEntry {
+    /// 所属监控会话 id
+    pub session_id: String,
+    /// 服务端记录的 unix 时间戳（秒，含微秒）
+    pub timestamp: f64,
+    pub db: i64,
+    /// 客户端地址，例如 127.0.0.1:60866、lua、unix:/tmp/redis.sock
+    pub client: String,
+    /// 大写的命令名
+    pub command: String,
+    pub args: Vec<String>,
+    /// MONITOR 原始输出
+    pub raw: String,
+}
+
+impl MonitorEntry {
+    /// 解析 MONITOR 输出，格式如
+    /// `1339518083.107412 [0 127.0.0.1:60866] "keys" "*"`
+    pub fn parse(line: &str) -> Option<Self> {
+        let (timestamp, rest) = line.split_once(' ')?;
+        let timestamp: f64 = timestamp.parse().ok()?;
+        let rest = rest.strip_prefix('[')?;
+        let (source, rest) = rest.split_once(']')?;
+        let (db, client) = source.split_once(' ')?;
+        let mut parts = parse_quoted_args(rest.trim_start())?.into_iter();
+        let command = parts.next()?.to_uppercase();
+
+        Some(Self {
+            session_id: String::new(),
+            timestamp,
+            db: db.parse().ok()?,
+            client: client.to_string(),
+            command,
+            args: parts.collect(),
+            raw: line.to_string(),
+        })
+    }
+
+    /// 命令操作的 key，MONITOR 中默认取第一个参数
+    pub fn key(&self) -> Option<&str> {
+        self.args.first().map(|arg| arg.as_str())
+    }
+}
+
+/// 解析 MONITOR 中以双引号包裹、带转义的参数列表
+fn parse_quoted_args(input: &str) -> Option<Vec<String>> {
+    let mut args = Vec::new();
+    let mut chars = input.chars().peekable();
+
+    loop {
+        while chars.peek() == Some(&' ') {
+            chars.next();
+        }
+        match chars.next() {
+            None => return Some(args),
+            Some('"') => {}
+            Some(_) => return None,
+        }
+
+        let mut bytes = Vec::new();
+        loop {
+            match chars.next()? {
+                '"' => break,
+                '\\' => match chars.next()? {
+                    'n' => bytes.push(b'\n'),
+                    'r' => bytes.push(b'\r'),
+                    't' => bytes.push(b'\t'),
+                    'a' => bytes.push(0x07),
+                    'b' => bytes.push(0x08),
+                    'x' => {
+                        let hex: String = [chars.next()?, chars.next()?].iter().collect();
+                        bytes.push(u8::from_str_radix(&hex, 16).ok()?);
+                    }
+                    other => {
+                        let mut buf = [0; 4];
+                        bytes.extend_from_slice(other.encode_utf8(&mut buf).as_bytes());
+                    }
+                },
+                other => {
+                    let mut buf = [0; 4];
+                    bytes.extend_from_slice(other.encode_utf8(&mut buf).as_bytes());
+                }
+            }
+        }
+        args.push(String::from_utf8_lossy(&bytes).to_string());
+    }
+}
+
+/// MONITOR 过滤条件，字段为空表示不过滤
+#[derive(Serialize, Deserialize, Debug, Clone, Default)]
+pub struct MonitorFilter {
+    /// 命令名，不区分大小写
+    #[serde(default)]
+    pub commands: Vec<String>,
+    /// key 的 glob 模式，例如 user:*
+    #[serde(default)]
+    pub key_pattern: String,
+    /// 客户端地址包含的字符串
+    #[serde(default)]
+    pub client: String,
+}
+
+/// MONITOR 录制到本地文件的配置
+#[derive(Serialize, Deserialize, Debug, Clone)]
+pub struct MonitorCapture {
+    pub file_path: String,
+    /// 最多录制的条数，达到后不再写入
+    pub max_entries: usize,
+}
+
+/// 导出/导入的文件格式
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq)]
+pub enum RedisTransferFormat {
+    /// 每行一个 ExportedKey 的 JSON
+    JsonLines,
+    /// key,type,ttl,field,value，不支持 stream
+    Csv,
+    /// RESP 命令文件，可以用 redis-cli --pipe 回放
+    Resp,
+}
+
+/// 导入时 key 已存在的处理方式
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq)]
+pub enum ImportConflictPolicy {
+    /// 跳过已存在的 key
+    Skip,
+    /// 删除后重新写入
+    Overwrite,
+    /// 在 key 后追加后缀写入
+    Rename,
+}
+
+/// Stream 中的一条消息
+#[derive(Serialize, Deserialize, Debug, Clone)]
+pub struct StreamEnt {
+    pub id: String,
+    pub fields: BTreeMap<String, String>,
+}
+
+/// 单个 key 的值，set 与 list 共用 Members
+#[derive(Serialize, Deserialize, Debug, Clone)]
+#[serde(untagged)]
+pub enum RedisKeyValue {
+    String(String),
+    Members(Vec<String>),
+    Hash(BTreeMap<String, String>),
+    ZSet(Vec<ZSetEnt>),
+    Stream(Vec<StreamEnt>),
+}
+
+/// 导出文件中的一个 key
+#[derive(Serialize, Deserialize, Debug, Clone)]
+pub struct ExportedKey {
+    pub key: String,
+    pub key_type: KeyType,
+    /// 剩余过期时间（秒），-1 表示不过期
+    pub ttl: i64,
+    pub value: RedisKeyValue,
+    /// 值不是 UTF-8 时为 true，此时成员、字段名和值均为 base64 编码
+    #[serde(default)]
+    pub base64: bool,
+}
+
+/// 单个 key 的处理失败原因
+#[derive(Serialize, Deserialize, Debug, Clone)]
+pub struct RedisKeyError {
+    pub key: String,
+    pub error: String,
+}
+
+#[derive(Serialize, Deserialize, Debug)]
+pub struct RedisExportResult {
+    pub file_path: String,
+    pub exported: usize,
+    /// 当前格式不支持而跳过的 key
+    pub skipped: Vec<String>,
+    /// 读取失败的 key
+    pub failed: Vec<RedisKeyError>,
+}
+
+#[derive(Serialize, Deserialize, Debug, Default)]
+pub struct RedisImportResult {
+    pub total: usize,
+    pub imported: usize,
+    pub skipped: usize,
+    pub failed: Vec<RedisKeyError>,
+}
+
+/// key 迁移选项
+#[derive(Serialize, Deserialize, Debug, Clone, Default)]
+pub struct RedisMigrateOptions {
+    /// 保留源 key 的过期时间
+    #[serde(default)]
+    pub keep_ttl: bool,
+    /// 目标已存在时覆盖，否则跳过
+    #[serde(default)]
+    pub replace: bool,
+    /// 需要替换的源 key 前缀
+    #[serde(default)]
+    pub source_prefix: String,
+    /// 替换后的目标 key 前缀
+    #[serde(default)]
+    pub target_prefix: String,
+}
+
+#[derive(Serialize, Deserialize, Debug, Default)]
+pub struct RedisMigrateResult {
+    pub total: usize,
+    /// 通过 DUMP/RESTORE 迁移的数量
+    pub migrated: usize,
+    /// RESTORE 失败后按类型复制的数量
+    pub copied: usize,
+    pub skipped: usize,
+    pub failed: Vec<RedisKeyError>,
+}
+
+/// EVAL、FCALL 等命令的返回值，保留 Redis 的回复类型
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq)]
+#[serde(tag = "type", content = "value")]
+pub enum RedisReply {
+    Nil,
+    Integer(i64),
+    /// 批量字符串，非 UTF-8 内容按有损方式转换
+    Bulk(String),
+    Array(Vec<RedisReply>),
+    Status(String),
+    Okay,
+}
+
+impl From<&redis::Value> for RedisReply {
+    fn from(value: &redis::Value) -> Self {
+        match value {
+            redis::Value::Nil => RedisReply::Nil,
+            redis::Value::Int(v) => RedisReply::Integer(*v),
+            redis::Value::Data(bytes) => {
+                RedisReply::Bulk(String::from_utf8_lossy(bytes).to_string())
+            }
+            redis::Value::Bulk(items) => RedisReply::Array(items.iter().map(Self::from).collect()),
+            redis::Value::Status(status) => RedisReply::Status(status.clone()),
+            redis::Value::Okay => RedisReply::Okay,
+        }
+    }
+}
+
+/// SCRIPT EXISTS 的单个脚本结果
+#[derive(Serialize, Deserialize, Debug)]
+pub struct ScriptExistence {
+    pub sha: String,
+    pub exists: bool,
+}
+
+/// FUNCTION LIST 中的一个函数
+#[derive(Serialize, Deserialize, Debug, Clone)]
+pub struct RedisFunctionInfo {
+    pub name: String,
+    pub description: Option<String>,
+    /// 例如 no-writes、allow-oom
+    pub flags: Vec<String>,
+}
+
+/// FUNCTION LIST 中的一个函数库（Redis 7+）
+#[derive(Serialize, Deserialize, Debug, Clone)]
+pub struct RedisFunctionLibrary {
+    pub library_name: String,
+    pub engine: String,
+    pub functions: Vec<RedisFunctionInfo>,
+    /// 仅在 WITHCODE 时返回
+    pub library_code: Option<String>,
+}
+
+impl RedisFunctionLibrary {
+    /// 解析 FUNCTION LIST 返回的单个函数库，RESP2 下为键值交替的数组
+    pub fn from_value(value: &redis::Value) -> Option<Self> {
+        let fields = reply_map(value)?;
+        let functions = match fields.get("functions") {
+            Some(redis::Value::Bulk(items)) => items
+                .iter()
+                .filter_map(|item| {
+                    let function = reply_map(item)?;
+                    Some(RedisFunctionInfo {
+                        name: reply_string(function.get("name")?)?,
+                        description: function.get("description").and_then(|v| reply_string(v)),
+                        flags: match function.get("flags") {
+                            Some(redis::Value::Bulk(flags)) => {
+                                flags.iter().filter_map(reply_string).collect()
+                            }
+                            _ => Vec::new(),
+                        },
+                    })
+                })
+                .collect(),
+            _ => Vec::new(),
+        };
+
+        Some(Self {
+            library_name: reply_string(fields.get("library_name")?)?,
+            engine: fields
+                .get("engine")
+                .and_then(|v| reply_string(v))
+                .unwrap_or_default(),
+            functions,
+            library_code: fields.get("library_code").and_then(|v| reply_string(v)),
+        })
+    }
+}
+
+/// 将键值交替的数组转换为 map
+fn reply_map(value: &redis::Value) -> Option<BTreeMap<String, &redis::Value>> {
+    match value {
+        redis::Value::Bulk(items) => Some(
+            items
+                .chunks(2)
+                .filter_map(|pair| match pair {
+                    [k, v] => Some((reply_string(k)?, v)),
+                    _ => None,
+                })
+                .collect(),
+        ),
+        _ => None,
+    }
+}
+
+fn reply_string(value: &redis::Value) -> Option<String> {
+    match value {
+        redis::Value::Data(bytes) => Some(String::from_utf8_lossy(bytes).to_string()),
+        redis::Value::Status(status) => Some(status.clone()),
+        _ => None,
+    }
+}
+
+/// CONFIG GET 返回的单个配置项
+#[derive(Serialize, Deserialize, Debug, Clone)]
+pub struct RedisConfigItem {
+    pub name: String,
+    pub value: String,
+    /// 对应 redis.conf 中的章节，例如 network、memory
+    pub category: String,
+    /// 服务端版本对应的默认值，未收录的配置或版本为 None
+    pub default_value: Option<String>,
+    /// 与默认值不同，默认值未知时为 false
+    pub modified: bool,
+}
+
+impl RedisConfigItem {
+    /// `redis_version` 为 INFO 中的版本号，主版本没有默认值表时不标记修改
+    pub fn new(name: String, value: String, redis_version: &str) -> Self {
+        let default_value = config_default(name.as_str(), redis_version).map(|v| v.to_string());
+        let modified = match &default_value {
+            Some(default_value) => default_value != &value,
+            None => false,
+        };
+        Self {
+            category: config_category(name.as_str()).to_string(),
+            name,
+            value,
+            default_value,
+            modified,
+        }
+    }
+}
+
+/// 同一分类下的配置项
+#[derive(Serialize, Deserialize, Debug)]
+pub struct RedisConfigGroup {
+    pub category: String,
+    pub items: Vec<RedisConfigItem>,
+}
+
+/// 按名称前缀划分配置分类，顺序即匹配优先级
+const CONFIG_CATEGORIES: &[(&str, &str)] = &[
+    ("bind", "network"),
+    ("protected-mode", "network"),
+    ("port", "network"),
+    ("tcp-", "network"),
+    ("unixsocket", "network"),
+    ("timeout", "network"),
+    ("socket-mark-id", "network"),
+    ("tls-", "tls"),
+    ("save", "snapshotting"),
+    ("stop-writes-on-bgsave-error", "snapshotting"),
+    ("rdb", "snapshotting"),
+    ("dbfilename", "snapshotting"),
+    ("dir", "snapshotting"),
+    ("replica", "replication"),
+    ("slave", "replication"),
+    ("repl-", "replication"),
+    ("master", "replication"),
+    ("min-replicas", "replication"),
+    ("min-slaves", "replication"),
+    ("requirepass", "security"),
+    ("acl", "security"),
+    ("rename-command", "security"),
+    ("maxclients", "clients"),
+    ("client-", "clients"),
+    ("maxmemory", "memory"),
+    ("active-expire-effort", "memory"),
+    ("lazyfree", "lazyfree"),
+    ("io-threads", "threaded-io"),
+    ("oom-score", "kernel"),
+    ("disable-thp", "kernel"),
+    ("append", "append-only"),
+    ("aof-", "append-only"),
+    ("no-appendfsync-on-rewrite", "append-only"),
+    ("auto-aof-rewrite", "append-only"),
+    ("shutdown", "shutdown"),
+    ("lua-", "scripting"),
+    ("busy-reply-threshold", "scripting"),
+    ("cluster-", "cluster"),
+    ("slowlog", "slowlog"),
+    ("latency", "latency"),
+    ("notify-keyspace-events", "event-notification"),
+    ("hash-max", "advanced"),
+    ("list-", "advanced"),
+    ("set-max", "advanced"),
+    ("zset-max", "advanced"),
+    ("hll-", "advanced"),
+    ("stream-", "advanced"),
+    ("activerehashing", "advanced"),
+    ("proto-max-bulk-len", "advanced"),
+    ("hz", "advanced"),
+    ("dynamic-hz", "advanced"),
+    ("lfu-", "advanced"),
+    ("jemalloc", "advanced"),
+    ("active", "defragmentation"),
+    ("loadmodule", "modules"),
+];
+
+fn config_category(name: &str) -> &'static str {
+    CONFIG_CATEGORIES
+        .iter()
+        .find(|(prefix, _)| name.starts_with(prefix))
+        .map(|(_, category)| *category)
+        .unwrap_or("general")
+}
+
+/// 按主版本查找配置默认值表
+fn config_defaults(redis_version: &str) -> Option<&'static [(&'static str, &'static str)]> {
+    let major: u32 = redis_version.split('.').next()?.parse().ok()?;
+    CONFIG_DEFAULTS
+        .iter()
+        .find(|(version, _)| *version == major)
+        .map(|(_, defaults)| *defaults)
+}
+
+/// 是否收录了该版本的配置默认值
+pub fn has_config_defaults(redis_version: &str) -> bool {
+    config_defaults(redis_version).is_some()
+}
+
+fn config_default(name: &str, redis_version: &str) -> Option<&'static str> {
+    config_defaults(redis_version)?
+        .iter()
+        .find(|(n, _)| *n == name)
+        .map(|(_, v)| *v)
+}
+
+/// 主版本 -> 该版本的配置默认值表
+const CONFIG_DEFAULTS: &[(u32, &[(&str, &str)])] = &[(7, REDIS_7_CONFIG_DEFAULTS)];
+
+/// Redis 7 的常用配置默认值，值与 CONFIG GET 的输出格式一致
+const REDIS_7_CONFIG_DEFAULTS: &[(&str, &str)] = &[
+    ("daemonize", "no"),
+    ("loglevel", "notice"),
+    ("logfile", ""),
+    ("databases", "16"),
+    ("always-show-logo", "no"),
+    ("set-proc-title", "yes"),
+    ("supervised", "no"),
+    ("syslog-enabled", "no"),
+    ("syslog-ident", "redis"),
+    ("crash-log-enabled", "yes"),
+    ("crash-memcheck-enabled", "yes"),
+    ("enable-protected-configs", "no"),
+    ("enable-debug-command", "no"),
+    ("enable-module-command", "no"),
+    ("bind", "* -::*"),
+    ("protected-mode", "yes"),
+    ("port", "6379"),
+    ("tcp-backlog", "511"),
+    ("timeout", "0"),
+    ("tcp-keepalive", "300"),
+    ("tls-port", "0"),
+    ("save", "3600 1 300 100 60 10000"),
+    ("stop-writes-on-bgsave-error", "yes"),
+    ("rdbcompression", "yes"),
+    ("rdbchecksum", "yes"),
+    ("dbfilename", "dump.rdb"),
+    ("rdb-del-sync-files", "no"),
+    ("replica-serve-stale-data", "yes"),
+    ("replica-read-only", "yes"),
+    ("repl-diskless-sync", "yes"),
+    ("repl-diskless-sync-delay", "5"),
+    ("repl-diskless-sync-max-replicas", "0"),
+    ("repl-diskless-load", "disabled"),
+    ("repl-ping-replica-period", "10"),
+    ("repl-timeout", "60"),
+    ("repl-disable-tcp-nodelay", "no"),
+    ("repl-backlog-size", "1048576"),
+    ("repl-backlog-ttl", "3600"),
+    ("replica-priority", "100"),
+    ("replica-announced", "yes"),
+    ("min-replicas-to-write", "0"),
+    ("min-replicas-max-lag", "10"),
+    ("replica-lazy-flush", "no"),
+    ("replica-ignore-maxmemory", "yes"),
+    ("requirepass", ""),
+    ("acllog-max-len", "128"),
+    ("aclfile", ""),
+    ("acl-pubsub-default", "resetchannels"),
+    ("maxclients", "10000"),
+    ("maxmemory", "0"),
+    ("maxmemory-policy", "noeviction"),
+    ("maxmemory-samples", "5"),
+    ("maxmemory-eviction-tenacity", "10"),
+    ("active-expire-effort", "1"),
+    ("lazyfree-lazy-eviction", "no"),
+    ("lazyfree-lazy-expire", "no"),
+    ("lazyfree-lazy-server-del", "no"),
+    ("lazyfree-lazy-user-del", "no"),
+    ("lazyfree-lazy-user-flush", "no"),
+    ("io-threads", "1"),
+    ("io-threads-do-reads", "no"),
+    ("oom-score-adj", "no"),
+    ("oom-score-adj-values", "0 200 800"),
+    ("disable-thp", "yes"),
+    ("appendonly", "no"),
+    ("appendfilename", "appendonly.aof"),
+    ("appenddirname", "appendonlydir"),
+    ("appendfsync", "everysec"),
+    ("no-appendfsync-on-rewrite", "no"),
+    ("auto-aof-rewrite-percentage", "100"),
+    ("auto-aof-rewrite-min-size", "67108864"),
+    ("aof-load-truncated", "yes"),
+    ("aof-use-rdb-preamble", "yes"),
+    ("aof-timestamp-enabled", "no"),
+    ("aof-rewrite-incremental-fsync", "yes"),
+    ("shutdown-timeout", "10"),
+    ("shutdown-on-sigint", "default"),
+    ("shutdown-on-sigterm", "default"),
+    ("lua-time-limit", "5000"),
+    ("busy-reply-threshold", "5000"),
+    ("cluster-enabled", "no"),
+    ("cluster-node-timeout", "15000"),
+    ("cluster-port", "0"),
+    ("cluster-replica-validity-factor", "10"),
+    ("cluster-migration-barrier", "1"),
+    ("cluster-allow-replica-migration", "yes"),
+    ("cluster-require-full-coverage", "yes"),
+    ("cluster-replica-no-failover", "no"),
+    ("cluster-allow-reads-when-down", "no"),
+    ("cluster-allow-pubsubshard-when-down", "yes"),
+    ("cluster-link-sendbuf-limit", "0"),
+    ("cluster-preferred-endpoint-type", "ip"),
+    ("slowlog-log-slower-than", "10000"),
+    ("slowlog-max-len", "128"),
+    ("latency-monitor-threshold", "0"),
+    ("latency-tracking", "yes"),
+    ("latency-tracking-info-percentiles", "50 99 99.9"),
+    ("notify-keyspace-events", ""),
+    ("hash-max-listpack-entries", "128"),
+    ("hash-max-listpack-value", "64"),
+    ("hash-max-ziplist-entries", "128"),
+    ("hash-max-ziplist-value", "64"),
+    ("list-max-listpack-size", "-2"),
+    ("list-max-ziplist-size", "-2"),
+    ("list-compress-depth", "0"),
+    ("set-max-intset-entries", "512"),
+    ("zset-max-listpack-entries", "128"),
+    ("zset-max-listpack-value", "64"),
+    ("zset-max-ziplist-entries", "128"),
+    ("zset-max-ziplist-value", "64"),
+    ("hll-sparse-max-bytes", "3000"),
+    ("stream-node-max-bytes", "4096"),
+    ("stream-node-max-entries", "100"),
+    ("activerehashing", "yes"),
+    (
+        "client-output-buffer-limit",
+        "normal 0 0 0 slave 268435456 67108864 60 pubsub 33554432 8388608 60",
+    ),
+    ("client-query-buffer-limit", "1073741824"),
+    ("proto-max-bulk-len", "536870912"),
+    ("hz", "10"),
+    ("dynamic-hz", "yes"),
+    ("rdb-save-incremental-fsync", "yes"),
+    ("lfu-log-factor", "10"),
+    ("lfu-decay-time", "1"),
+    ("jemalloc-bg-thread", "yes"),
+    ("activedefrag", "no"),
+];
+
+/// ACL 用户及其规则
+#[derive(Serialize, Deserialize, Debug, Clone, Default)]
+pub struct AclUser {
+    pub name: String,
+    pub enabled: bool,
+    pub nopass: bool,
+    /// 密码的 SHA256，保存时以 #<hash> 写回
+    #[serde(default)]
+    pub passwords: Vec<String>,
+    /// 例如 ~*、%R~cache:*
+    #[serde(default)]
+    pub key_patterns: Vec<String>,
+    /// 例如 &*
+    #[serde(default)]
+    pub channel_patterns: Vec<String>,
+    /// 命令规则，例如 +@all、-flushdb
+    #[serde(default)]
+    pub commands: Vec<String>,
+    /// Redis 7 的选择器，包含括号
+    #[serde(default)]
+    pub selectors: Vec<String>,
+    /// 其他标记，例如 sanitize-payload
+    #[serde(default)]
+    pub flags: Vec<String>,
+}
+
+impl AclUser {
+    /// 解析 ACL LIST 的一行，例如 `user default on nopass ~* &* +@all`
+    pub fn parse(line: &str) -> Option<Self> {
+        let mut tokens = split_acl_rules(line).into_iter();
+        if tokens.next()? != "user" {
+            return None;
+        }
+        let mut user = AclUser {
+            name: tokens.next()?,
+            ..Default::default()
+        };
+        for token in tokens {
+            user.apply_rule(token);
+        }
+        Some(user)
+    }
+
+    /// 由 ACL GETUSER 的返回值构建
+    pub fn from_getuser(name: &str, value: &redis::Value) -> Option<Self> {
+        let fields = reply_map(value)?;
+        let mut user = AclUser {
+            name: name.to_string(),
+            ..Default::default()
+        };
+        for flag in reply_strings(fields.get("flags")) {
+            match flag.as_str() {
+                "allkeys" => user.apply_rule("~*".to_string()),
+                "allchannels" => user.apply_rule("&*".to_string()),
+                "allcommands" => user.apply_rule("+@all".to_string()),
+                _ => user.apply_rule(flag),
+            }
+        }
+        user.passwords = reply_strings(fields.get("passwords"));
+        // Redis 6 的 keys、channels 为不带 ~、& 前缀的模式列表
+        for rule in reply_strings(fields.get("commands")) {
+            user.apply_rule(rule);
+        }
+        for rule in reply_strings(fields.get("keys")) {
+            if rule.starts_with('~') || rule.starts_with('%') {
+                user.apply_rule(rule);
+            } else {
+                user.apply_rule(format!("~{}", rule));
+            }
+        }
+        for rule in reply_strings(fields.get("channels")) {
+            if rule.starts_with('&') {
+                user.apply_rule(rule);
+            } else {
+                user.apply_rule(format!("&{}", rule));
+            }
+        }
+        if let Some(redis::Value::Bulk(selectors)) = fields.get("selectors") {
+            for selector in selectors {
+                let selector = match reply_map(selector) {
+                    Some(selector) => selector,
+                    None => continue,
+                };
+                let rules: Vec<String> = ["commands", "keys", "channels"]
+                    .iter()
+                    .flat_map(|field| reply_strings(selector.get(*field)))
+                    .collect();
+                user.selectors.push(format!("({})", rules.join(" ")));
+            }
+        }
+        Some(user)
+    }
+
+    /// 应用一条规则，flags 和字段中重复出现的 key、频道、命令规则只保留一次
+    fn apply_rule(&mut self, rule: String) {
+        match rule.as_str() {
+            "on" => self.enabled = true,
+            "off" => self.enabled = false,
+            "nopass" => self.nopass = true,
+            "allkeys" => push_unique(&mut self.key_patterns, "~*".to_string()),
+            "allchannels" => push_unique(&mut self.channel_patterns, "&*".to_string()),
+            "allcommands" => push_unique(&mut self.commands, "+@all".to_string()),
+            "nocommands" => push_unique(&mut self.commands, "-@all".to_string()),
+            "resetkeys" | "resetchannels" | "resetpass" | "" => {}
+            _ if rule.starts_with('#') => self.passwords.push(rule[1..].to_string()),
+            _ if rule.starts_with('~') || rule.starts_with('%') => {
+                push_unique(&mut self.key_patterns, rule)
+            }
+            _ if rule.starts_with('&') => push_unique(&mut self.channel_patterns, rule),
+            _ if rule.starts_with('+') || rule.starts_with('-') => {
+                push_unique(&mut self.commands, rule)
+            }
+            _ if rule.starts_with('(') => self.selectors.push(rule),
+            _ => self.flags.push(rule),
+        }
+    }
+
+    /// 转换为 ACL SETUSER 的规则，配合 reset 使用即完整覆盖用户
+    pub fn to_rules(&self) -> Vec<String> {
+        let mut rules = vec![if self.enabled { "on" } else { "off" }.to_string()];
+        if self.nopass {
+            rules.push("nopass".to_string());
+        }
+        rules.extend(self.passwords.iter().map(|hash| format!("#{}", hash)));
+        rules.extend(self.key_patterns.iter().cloned());
+        rules.extend(self.channel_patterns.iter().cloned());
+        rules.extend(self.commands.iter().cloned());
+        rules.extend(self.selectors.iter().cloned());
+        rules.extend(self.flags.iter().cloned());
+        rules
+    }
+}
+
+fn push_unique(rules: &mut Vec<String>, rule: String) {
+    if !rules.contains(&rule) {
+        rules.push(rule);
+    }
+}
+
+/// 按空格拆分 ACL 规则，括号内的选择器保持完整
+fn split_acl_rules(line: &str) -> Vec<String> {
+    let mut rules = Vec::new();
+    let mut current = String::new();
+    let mut depth = 0;
+    for c in line.chars() {
+        match c {
+            '(' => {
+                depth += 1;
+                current.push(c);
+            }
+            ')' => {
+                depth -= 1;
+                current.push(c);
+            }
+            ' ' if depth == 0 => {
+                if !current.is_empty() {
+                    rules.push(std::mem::take(&mut current));
+                }
+            }
+            _ => current.push(c),
+        }
+    }
+    if !current.is_empty() {
+        rules.push(current);
+    }
+    rules
+}
+
+/// ACL LOG 中的一条记录
+#[derive(Serialize, Deserialize, Debug, Clone, Default)]
+pub struct AclLogEntry {
+    /// 相同事件在一段时间内合并的次数
+    pub count: i64,
+    /// command、key、channel 或 auth
+    pub reason: String,
+    /// toplevel、multi、lua 或 module
+    pub context: String,
+    /// 被拒绝的命令、key 或频道
+    pub object: String,
+    pub username: String,
+    pub age_seconds: f64,
+    pub client_info: String,
+    /// Redis 7.2 起返回，毫秒时间戳
+    pub timestamp_created: Option<i64>,
+    pub timestamp_last_updated: Option<i64>,
+}
+
+impl AclLogEntry {
+    pub fn from_value(value: &redis::Value) -> Option<Self> {
+        let fields = reply_map(value)?;
+        let int = |name: &str| match fields.get(name) {
+            Some(redis::Value::Int(v)) => Some(*v),
+            Some(v) => reply_string(v).and_then(|s| s.parse().ok()),
+            None => None,
+        };
+        let string = |name: &str| {
+            fields
+                .get(name)
+                .and_then(|v| reply_string(v))
+                .unwrap_or_default()
+        };
+        Some(Self {
+            count: int("count").unwrap_or_default(),
+            reason: string("reason"),
+            context: string("context"),
+            object: string("object"),
+            username: string("username"),
+            age_seconds: string("age-seconds").parse().unwrap_or_default(),
+            client_info: string("client-info"),
+            timestamp_created: int("timestamp-created"),
+            timestamp_last_updated: int("timestamp-last-updated"),
+        })
+    }
+}
+
+/// 字符串按空格拆分，数组逐项转换（Redis 6 与 7 的 GETUSER 格式不同）
+fn reply_strings(value: Option<&&redis::Value>) -> Vec<String> {
+    match value {
+        Some(redis::Value::Bulk(items)) => items.iter().filter_map(reply_string).collect(),
+        Some(value) => reply_string(value)
+            .map(|s| split_acl_rules(s.as_str()))
+            .unwrap_or_default(),
+        None => Vec::new(),
+    }
+}
+
+/// INFO replication 中的一个副本，来自 slaveN 字段
+#[derive(Serialize, Deserialize, Debug, Clone)]
+pub struct ReplicaInfo {
+    /// 字段名，例如 slave0
+    pub id: String,
+    pub ip: String,
+    pub port: u16,
+    /// online、wait_bgsave、send_bulk 等
+    pub state: String,
+    pub offset: i64,
+    /// 距离上次 ACK 的秒数
+    pub lag: i64,
+    /// 落后主节点的字节数
+    pub offset_behind: i64,
+}
+
+impl ReplicaInfo {
+    /// 解析 `ip=127.0.0.1,port=6380,state=online,offset=1234,lag=0`
+    pub fn parse(id: &str, value: &str, master_repl_offset: i64) -> Option<Self> {
+        let fields: BTreeMap<&str, &str> = value
+            .split(',')
+            .filter_map(|field| field.split_once('='))
+            .collect();
+        let offset = fields.get("offset")?.parse().ok()?;
+        Some(Self {
+            id: id.to_string(),
+            ip: fields.get("ip")?.to_string(),
+            port: fields.get("port")?.parse().ok()?,
+            state: fields.get("state").unwrap_or(&"").to_string(),
+            offset,
+            lag: fields
+                .get("lag")
+                .and_then(|lag| lag.parse().ok())
+                .unwrap_or_default(),
+            offset_behind: (master_repl_offset - offset).max(0),
+        })
+    }
+}
+
+/// 副本所连接的主节点，仅在 role 为 slave 时存在
+#[derive(Serialize, Deserialize, Debug, Clone)]
+pub struct MasterLinkInfo {
+    pub host: String,
+    pub port: u16,
+    /// up 或 down
+    pub link_status: String,
+    pub last_io_seconds_ago: i64,
+    pub sync_in_progress: bool,
+    pub slave_repl_offset: i64,
+    pub slave_read_only: bool,
+    /// 链路断开的秒数，链路正常时为 None
+    pub link_down_since_seconds: Option<i64>,
+}
+
+/// 副本落后超过阈值的告警
+#[derive(Serialize, Deserialize, Debug, Clone)]
+pub struct ReplicationLagAlert {
+    /// ip:port
+    pub replica: String,
+    pub offset_behind: i64,
+    pub lag: i64,
+    pub message: String,
+}
+
+/// 复制状态
+#[derive(Serialize, Deserialize, Debug, Clone)]
+pub struct RedisReplicationStatus {
+    /// master 或 slave
+    pub role: String,
+    pub master_replid: String,
+    pub master_repl_offset: i64,
+    pub replicas: Vec<ReplicaInfo>,
+    pub master: Option<MasterLinkInfo>,
+    pub repl_backlog_active: bool,
+    pub repl_backlog_size: i64,
+    /// 告警阈值（落后字节数）
+    pub lag_threshold: i64,
+    pub alerts: Vec<ReplicationLagAlert>,
+}
+
+impl RedisReplicationStatus {
+    /// 由 INFO replication 构建，副本落后超过 lag_threshold 字节或主从链路断开时产生告警
+    pub fn from_info(info: &redis::InfoDict, lag_threshold: i64) -> Self {
+        let master_repl_offset = info_i64(info, "master_repl_offset");
+        let connected = info_i64(info, "connected_slaves");
+        let replicas: Vec<ReplicaInfo> = (0..connected)
+            .filter_map(|index| {
+                let id = format!("slave{}", index);
+                let value = info.get::<String>(id.as_str())?;
+                ReplicaInfo::parse(id.as_str(), value.as_str(), master_repl_offset)
+            })
+            .collect();
+
+        let role = info.get::<String>("role").unwrap_or_default();
+        let master = if role == "slave" {
+            Some(MasterLinkInfo {
+                host: info.get::<String>("master_host").unwrap_or_default(),
+                port: info_i64(info, "master_port") as u16,
+                link_status: info.get::<String>("master_link_status").unwrap_or_default(),
+                last_io_seconds_ago: info_i64(info, "master_last_io_seconds_ago"),
+                sync_in_progress: info_bool(info, "master_sync_in_progress"),
+                slave_repl_offset: info_i64(info, "slave_repl_offset"),
+                slave_read_only: info_bool(info, "slave_read_only"),
+                link_down_since_seconds: info.get::<i64>("master_link_down_since_seconds"),
+            })
+        } else {
+            None
+        };
+
+        let mut alerts: Vec<ReplicationLagAlert> = replicas
+            .iter()
+            .filter(|replica| replica.offset_behind > lag_threshold)
+            .map(|replica| ReplicationLagAlert {
+                replica: format!("{}:{}", replica.ip, replica.port),
+                offset_behind: replica.offset_behind,
+                lag: replica.lag,
+                message: format!(
+                    "副本落后 {} 字节，超过阈值 {}",
+                    replica.offset_behind, lag_threshold
+                ),
+            })
+            .collect();
+        if let Some(master) = master.as_ref().filter(|m| m.link_status != "up") {
+            alerts.push(ReplicationLagAlert {
+                replica: "self".to_string(),
+                offset_behind: 0,
+                lag: master.last_io_seconds_ago,
+                message: format!("与主节点 {}:{} 的链路已断开", master.host, master.port),
+            });
+        }
+
+        Self {
+            role,
+            master_replid: info.get::<String>("master_replid").unwrap_or_default(),
+            master_repl_offset,
+            replicas,
+            master,
+            repl_backlog_active: info_bool(info, "repl_backlog_active"),
+            repl_backlog_size: info_i64(info, "repl_backlog_size"),
+            lag_threshold,
+            alerts,
+        }
+    }
+}
+
+/// 持久化状态
+#[derive(Serialize, Deserialize, Debug, Clone)]
+pub struct RedisPersistenceStatus {
+    pub loading: bool,
+    pub rdb_changes_since_last_save: i64,
+    pub rdb_bgsave_in_progress: bool,
+    /// 上次成功保存的 unix 时间戳（秒）
+    pub rdb_last_save_time: i64,
+    /// ok 或 err
+    pub rdb_last_bgsave_status: String,
+    pub rdb_last_bgsave_time_sec: i64,
+    pub rdb_current_bgsave_time_sec: i64,
+    pub aof_enabled: bool,
+    pub aof_rewrite_in_progress: bool,
+    pub aof_rewrite_scheduled: bool,
+    pub aof_last_rewrite_time_sec: i64,
+    pub aof_current_rewrite_time_sec: i64,
+    pub aof_last_bgrewrite_status: String,
+    pub aof_last_write_status: String,
+    /// 仅在开启 AOF 时返回
+    pub aof_current_size: Option<i64>,
+    pub aof_base_size: Option<i64>,
+}
+
+impl RedisPersistenceStatus {
+    pub fn from_info(info: &redis::InfoDict) -> Self {
+        Self {
+            loading: info_bool(info, "loading"),
+            rdb_changes_since_last_save: info_i64(info, "rdb_changes_since_last_save"),
+            rdb_bgsave_in_progress: info_bool(info, "rdb_bgsave_in_progress"),
+            rdb_last_save_time: info_i64(info, "rdb_last_save_time"),
+            rdb_last_bgsave_status: info
+                .get::<String>("rdb_last_bgsave_status")
+                .unwrap_or_default(),
+            rdb_last_bgsave_time_sec: info_i64(info, "rdb_last_bgsave_time_sec"),
+            rdb_current_bgsave_time_sec: info_i64(info, "rdb_current_bgsave_time_sec"),
+            aof_enabled: info_bool(info, "aof_enabled"),
+            aof_rewrite_in_progress: info_bool(info, "aof_rewrite_in_progress"),
+            aof_rewrite_scheduled: info_bool(info, "aof_rewrite_scheduled"),
+            aof_last_rewrite_time_sec: info_i64(info, "aof_last_rewrite_time_sec"),
+            aof_current_rewrite_time_sec: info_i64(info, "aof_current_rewrite_time_sec"),
+            aof_last_bgrewrite_status: info
+                .get::<String>("aof_last_bgrewrite_status")
+                .unwrap_or_default(),
+            aof_last_write_status: info
+                .get::<String>("aof_last_write_status")
+                .unwrap_or_default(),
+            aof_current_size: info.get::<i64>("aof_current_size"),
+            aof_base_size: info.get::<i64>("aof_base_size"),
+        }
+    }
+}
+
+/// FAILOVER 参数
+#[derive(Serialize, Deserialize, Debug, Clone, Default)]
+pub struct RedisFailoverOptions {
+    /// 指定切换到的副本，为空时由主节点选择
+    #[serde(default)]
+    pub host: String,
+    #[serde(default)]
+    pub port: u16,
+    /// 超时后不等待副本追平，直接切换，需要同时指定 host 和 timeout_ms
+    #[serde(default)]
+    pub force: bool,
+    #[serde(default)]
+    pub timeout_ms: Option<u64>,
+    /// 取消正在进行的 FAILOVER
+    #[serde(default)]
+    pub abort: bool,
+}
+
+fn info_i64(info: &redis::InfoDict, key: &str) -> i64 {
+    info.get::<i64>(key).unwrap_or_default()
+}
+
+fn info_bool(info: &redis::InfoDict, key: &str) -> bool {
+    info.get::<String>(key).map(|v| v == "1").unwrap_or(false)
+}
@@ -1,14 +1,42 @@
 use std::collections::{BTreeMap, HashMap, HashSet};
-
+use std::fs::{self, File};
+use std::io::{BufWriter, Write};
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::sync::{mpsc, Arc};
+use std::thread;
+use std::time::{Duration, Instant};
+
+use base64::engine::general_purpose::STANDARD as BASE64;
+use base64::Engine;
+use chrono::Local;
+use futures::future::join_all;
+use redis::aio::ConnectionManager;
 use redis::InfoDict;
 use regex::Regex;
 use serde::{Deserialize, Serialize};
+use tokio::sync::Mutex;
 
 use crate::config::redis_config::RedisUserPassword;
+use crate::op::redis_entity::{
+    has_config_defaults, AclLogEntry, AclUser, ExportedKey, ImportConflictPolicy, MonitorCapture,
+    MonitorEntry, MonitorFilter, PubSubChannelCount, PubSubMessage, RedisConfigGroup,
+    RedisConfigItem, RedisExportResult, RedisFailoverOptions, RedisFunctionLibrary,
+    RedisImportResult, RedisKeyError, RedisKeyValue, RedisListenEvent, RedisListenHandle,
+    RedisMigrateOptions, RedisMigrateResult, RedisPersistenceStatus, RedisReplicationStatus,
+    RedisReply, RedisTransferFormat, ScriptExistence, StreamEnt,
+};
 use crate::resp::resp::Response;
+use crate::util::csv::{csv_escape, parse_csv_records};
 
+/// Redis 操作，clone 后共享同一组连接
+///
+/// 普通命令走按数据库缓存的 ConnectionManager（多路复用、断线自动重连），
+/// 订阅和 MONITOR 会独占连接，仍然在独立线程上使用阻塞连接
+#[derive(Clone)]
 pub struct RedisOperation {
     client: redis::Client,
+    /// 每个数据库一个连接，避免在共享连接上 SELECT 影响其他请求
+    managers: Arc<Mutex<HashMap<i32, ConnectionManager>>>,
 }
 
 impl RedisOperation {
@@ -22,18 +50,36 @@ impl RedisOperation {
             )
         };
 
-        let client = redis::Client::open(connection_str).unwrap();
-        Ok(Self { client })
+        let client = redis::Client::open(connection_str)?;
+        Ok(Self {
+            client,
+            managers: Arc::new(Mutex::new(HashMap::new())),
+        })
+    }
+
+    /// 获取指定数据库的多路复用连接，首次使用时建立
+    async fn connection(&self, db_index: i32) -> redis::RedisResult<ConnectionManager> {
+        let mut managers = self.managers.lock().await;
+        if let Some(manager) = managers.get(&db_index) {
+            return Ok(manager.clone());
+        }
+        let mut info = self.client.get_connection_info().clone();
+        info.redis.db = db_index as i64;
+        let manager = ConnectionManager::new(redis::Client::open(info)?).await?;
+        managers.insert(db_index, manager.clone());
+        Ok(manager)
     }
-    pub fn get_db_size(&self) -> Response<i32> {
-        let result = self.client.get_connection();
+
+    pub async fn get_db_size(&self) -> Response<i32> {
+        let result = self.connection(0).await;
         match result {
             Ok(mut con) => {
                 // 使用 CONFIG get databases 命令
                 let result: Result<(String, String), redis::RedisError> = redis::cmd("CONFIG")
                     .arg("get")
                     .arg("databases")
-                    .query(&mut con);
+                    .query_async(&mut con)
+                    .await;
 
                 return match result {
                     Ok((_v1, v2)) => Response::new("获取服务端信息成功", Some(v2.parse().unwrap())),
@@ -47,26 +93,27 @@ impl RedisOperation {
         }
     }
     // 获取 Redis 服务端信息
-    pub fn get_server_info(&self) -> Response<RedisInfo> {
-        let result = self.client.get_connection();
+    pub async fn get_server_info(&self) -> Response<RedisInfo> {
+        let result = self.connection(0).await;
         match result {
             Ok(mut con) => {
-                // 使用 INFO 命令获取字符串格式的服务端信息
-                let info: String = redis::cmd("INFO")
+                // 一次往返获取 Keyspace 字符串和完整的字典格式信息
+                let result: Result<(String, redis::InfoDict), redis::RedisError> = redis::pipe()
+                    .cmd("INFO")
                     .arg("Keyspace")
-                    .query(&mut con)
-                    .expect("Failed to execute INFO command");
+                    .cmd("INFO")
+                    .query_async(&mut con)
+                    .await;
+                let (info, info_dict) = match result {
+                    Ok(values) => values,
+                    Err(err) => return Response::from_error(format!("Error: {:?}", err)),
+                };
 
                 let option = crate::op::redis_op::KeyspaceInfo::from_string(info.as_str());
 
-                // 使用 INFO 命令获取字典格式的服务端信息
-                let info_dict: redis::InfoDict = redis::cmd("INFO")
-                    .query(&mut con)
-                    .expect("Failed to execute INFO command");
-
                 // 将字典格式的服务端信息转换为你的 RedisInfo 结构体
                 let mut redis_info: RedisInfo = info_dict.into();
-                redis_info.keyspace = option.unwrap().dbs;
+                redis_info.keyspace = option.map(|keyspace| keyspace.dbs).unwrap_or_default();
                 // 构建一个成功的 Response 实例并包含 RedisInfo 数据
                 Response::new("获取服务端信息成功", Some(redis_info))
             }
@@ -77,22 +124,16 @@ impl RedisOperation {
         }
     }
 
-    pub fn get_keys_page(
+    pub async fn get_keys_page(
         &self,
         db_index: i32,
         page: usize,
         page_size: usize,
     ) -> Response<ScanKeyResult> {
-        let result = self.client.get_connection();
+        let result = self.connection(db_index).await;
 
         match result {
             Ok(mut con) => {
-                // 切换到指定的数据库
-                let _: () = redis::cmd("SELECT")
-                    .arg(db_index)
-                    .query(&mut con)
-                    .expect("Failed to SELECT database");
-
                 // 计算当前页的游标位置
                 let cursor: usize = page * page_size;
 
@@ -103,31 +144,32 @@ impl RedisOperation {
                     .arg("*")
                     .arg("COUNT")
                     .arg(page_size as isize)
-                    .query(&mut con);
+                    .query_async(&mut con)
+                    .await;
                 match result {
                     Ok((new_cursor, keys)) => {
-                        // 构建 HashMap<String, KeyType>，其中键为键名，值为键的类型
-                        let key_info_list: Vec<KeyInfo> = keys
-                            .iter()
-                            .map(|key| {
-                                let key_type_str: String = redis::cmd("TYPE")
-                                    .arg(key)
-                                    .query(&mut con)
-                                    .unwrap_or_else(|_| "unknown".to_string());
-
-                                let key_type = KeyType::from_string(&key_type_str);
-                                let ttl_result = self.get_ttl(key, db_index);
-                                let ttl = match ttl_result {
-                                    Ok(Some(value)) => value,
-                                    Ok(None) => -1, // Key does not exist
-                                    Err(_) => -2,   // Error in getting TTL
-                                };
-
-                                KeyInfo {
-                                    key_name: key.clone(),
-                                    key_type,
-                                    ttl,
+                        // 在同一个 pipeline 中获取所有 key 的类型和过期时间
+                        let mut pipe = redis::pipe();
+                        for key in &keys {
+                            pipe.cmd("TYPE").arg(key).cmd("TTL").arg(key);
+                        }
+                        let values: Vec<(String, i64)> = if keys.is_empty() {
+                            Vec::new()
+                        } else {
+                            match pipe.query_async(&mut con).await {
+                                Ok(values) => values,
+                                Err(err) => {
+                                    return Response::from_error(format!("Error: {:?}", err))
                                 }
+                            }
+                        };
+                        let key_info_list: Vec<KeyInfo> = keys
+                            .into_iter()
+                            .zip(values)
+                            .map(|(key, (key_type_str, ttl))| KeyInfo {
+                                key_name: key,
+                                key_type: KeyType::from_string(&key_type_str),
+                                ttl,
                             })
                             .collect();
                         // println!("new_cursor {}", new_cursor);
@@ -146,19 +188,14 @@ impl RedisOperation {
             Err(e) => Response::from_error(format!("Redis 链接异常: {}", e)),
         }
     }
-    pub fn get_db_key_count(&self, db_index: i32) -> Response<usize> {
-        let result = self.client.get_connection();
+    pub async fn get_db_key_count(&self, db_index: i32) -> Response<usize> {
+        let result = self.connection(db_index).await;
 
         match result {
             Ok(mut con) => {
-                // 切换到指定的数据库
-                let _: () = redis::cmd("SELECT")
-                    .arg(db_index)
-                    .query(&mut con)
-                    .expect("Failed to SELECT database");
-
                 // 使用 DBSIZE 命令获取键的数量
-                let result: Result<usize, redis::RedisError> = redis::cmd("DBSIZE").query(&mut con);
+                let result: Result<usize, redis::RedisError> =
+                    redis::cmd("DBSIZE").query_async(&mut con).await;
 
                 match result {
                     Ok(count) => Response::new("获取键数量成功", Some(count)),
@@ -173,217 +210,171 @@ impl RedisOperation {
     ///  1. 类型校验
     ///  2. 性能问题，这里都是直接获取所有，数据量大的话不合适
 
-    pub fn get_string_data(&self, db_index: i32, key: String) -> Response<String> {
-        let result = self.client.get_connection();
+    pub async fn get_string_data(&self, db_index: i32, key: String) -> Response<String> {
+        let result = self.connection(db_index).await;
 
         match result {
-            Ok(mut con) => {
-                // 切换到指定的数据库
-                let _: () = redis::cmd("SELECT")
-                    .arg(db_index)
-                    .query(&mut con)
-                    .expect("Failed to SELECT database");
-
-                match redis::cmd("GET").arg(&key).query(&mut con) {
-                    Ok(value) => Response::new("获取数据成功", Some(value)),
-                    Err(err) => Response::from_error(format!("Error: {:?}", err)),
-                }
-            }
+            Ok(mut con) => match redis::cmd("GET").arg(&key).query_async(&mut con).await {
+                Ok(value) => Response::new("获取数据成功", Some(value)),
+                Err(err) => Response::from_error(format!("Error: {:?}", err)),
+            },
             Err(e) => Response::from_error(format!("Redis 链接异常: {}", e)),
         }
     }
 
-    pub fn get_list_data(
+    pub async fn get_list_data(
         &self,
         db_index: i32,
         key: String,
         start: isize,
         stop: isize,
     ) -> Response<ListData> {
-        let result = self.client.get_connection();
+        let result = self.connection(db_index).await;
 
         match result {
             Ok(mut con) => {
-                // 切换到指定的数据库
-                let _: () = redis::cmd("SELECT")
-                    .arg(db_index)
-                    .query(&mut con)
-                    .expect("Failed to SELECT database");
-
-                // 获取 List 数据
-                let values: Vec<String> = match redis::cmd("LRANGE")
+                // 同时获取 List 数据和总长度
+                let result: Result<(Vec<String>, usize), redis::RedisError> = redis::pipe()
+                    .cmd("LRANGE")
                     .arg(&key)
                     .arg(start)
                     .arg(stop - 1)
-                    .query(&mut con)
-                {
-                    Ok(values) => values,
-                    Err(err) => {
-                        return Response::from_error(format!("Error: {:?}", err));
-                    }
-                };
+                    .cmd("LLEN")
+                    .arg(&key)
+                    .query_async(&mut con)
+                    .await;
 
-                // 获取 List 的总长度
-                let total_length: usize = match redis::cmd("LLEN").arg(&key).query(&mut con) {
-                    Ok(length) => length,
-                    Err(err) => {
-                        return Response::from_error(format!("Error: {:?}", err));
+                match result {
+                    // 返回包装在 ListData 中的结果
+                    Ok((values, total_length)) => {
+                        Response::new("获取数据成功", Some(ListData::new(values, total_length)))
                     }
-                };
-
-                // 返回包装在 ListData 中的结果
-                Response::new("获取数据成功", Some(ListData::new(values, total_length)))
+                    Err(err) => Response::from_error(format!("Error: {:?}", err)),
+                }
             }
             Err(e) => Response::from_error(format!("Redis 链接异常: {}", e)),
         }
     }
 
-    pub fn get_set_data(&self, db_index: i32, key: String) -> Response<SetData> {
-        let result = self.client.get_connection();
+    pub async fn get_set_data(&self, db_index: i32, key: String) -> Response<SetData> {
+        let result = self.connection(db_index).await;
 
         match result {
             Ok(mut con) => {
-                // 切换到指定的数据库
-                let _: () = redis::cmd("SELECT")
-                    .arg(db_index)
-                    .query(&mut con)
-                    .expect("Failed to SELECT database");
-
-                // 获取 Set 数据
-                let values: HashSet<String> = match redis::cmd("SMEMBERS").arg(&key).query(&mut con)
-                {
-                    Ok(values) => values,
-                    Err(err) => {
-                        return Response::from_error(format!("Error: {:?}", err));
-                    }
-                };
+                // 同时获取 Set 数据和总长度
+                let result: Result<(HashSet<String>, usize), redis::RedisError> = redis::pipe()
+                    .cmd("SMEMBERS")
+                    .arg(&key)
+                    .cmd("SCARD")
+                    .arg(&key)
+                    .query_async(&mut con)
+                    .await;
 
-                // 获取 Set 的总长度
-                let total_length: usize = match redis::cmd("SCARD").arg(&key).query(&mut con) {
-                    Ok(length) => length,
-                    Err(err) => {
-                        return Response::from_error(format!("Error: {:?}", err));
+                match result {
+                    // 返回包装在 SetData 中的结果
+                    Ok((values, total_length)) => {
+                        Response::new("获取数据成功", Some(SetData::new(values, total_length)))
                     }
-                };
-
-                // 返回包装在 SetData 中的结果
-                Response::new("获取数据成功", Some(SetData::new(values, total_length)))
+                    Err(err) => Response::from_error(format!("Error: {:?}", err)),
+                }
             }
             Err(e) => Response::from_error(format!("Redis 链接异常: {}", e)),
         }
     }
 
-    pub fn get_hash_data(&self, db_index: i32, key: String) -> Response<HashData> {
-        let result = self.client.get_connection();
+    pub async fn get_hash_data(&self, db_index: i32, key: String) -> Response<HashData> {
+        let result = self.connection(db_index).await;
 
         match result {
             Ok(mut con) => {
-                // 切换到指定的数据库
-                let _: () = redis::cmd("SELECT")
-                    .arg(db_index)
-                    .query(&mut con)
-                    .expect("Failed to SELECT database");
-
-                let values: Vec<HashEnt> = match redis::cmd("HGETALL")
+                // 同时获取 Hash 数据和总长度
+                let result: Result<(Vec<String>, usize), redis::RedisError> = redis::pipe()
+                    .cmd("HGETALL")
                     .arg(&key)
-                    .query::<Vec<String>>(&mut con)
-                {
-                    Ok(values) => {
+                    .cmd("HLEN")
+                    .arg(&key)
+                    .query_async(&mut con)
+                    .await;
+
+                match result {
+                    Ok((values, total_length)) => {
                         // Convert values to Vec<HashEnt>
-                        values
+                        let values: Vec<HashEnt> = values
                             .chunks(2)
                             .map(|chunk| HashEnt {
                                 key: chunk[0].clone(),
                                 val: chunk[1].clone(),
                             })
-                            .collect()
-                    }
-                    Err(err) => {
-                        return Response::from_error(format!("Error: {:?}", err));
-                    }
-                }; // 获取 Hash 的总长度
-                let total_length: usize = match redis::cmd("HLEN").arg(&key).query(&mut con) {
-                    Ok(length) => length,
-                    Err(err) => {
-                        return Response::from_error(format!("Error: {:?}", err));
+                            .collect();
+                        // 返回包装在 HashData 中的结果
+                        Response::new("获取数据成功", Some(HashData::new(values, total_length)))
                     }
-                };
-
-                // 返回包装在 HashData 中的结果
-                Response::new("获取数据成功", Some(HashData::new(values, total_length)))
+                    Err(err) => Response::from_error(format!("Error: {:?}", err)),
+                }
             }
             Err(e) => Response::from_error(format!("Redis 链接异常: {}", e)),
         }
     }
 
-    pub fn get_zset_data(
+    pub async fn get_zset_data(
         &self,
         db_index: i32,
         key: String,
         start: isize,
         stop: isize,
     ) -> Response<ZSetData> {
-        let result = self.client.get_connection();
+        let result = self.connection(db_index).await;
 
         match result {
             Ok(mut con) => {
-                // 切换到指定的数据库
-                let _: () = redis::cmd("SELECT")
-                    .arg(db_index)
-                    .query(&mut con)
-                    .expect("Failed to SELECT database");
-
-                // 获取 ZSet 数据
-                let values: Vec<ZSetEnt> = match redis::cmd("ZRANGE")
+                // 同时获取 ZSet 数据和总长度
+                let result: Result<(Vec<String>, usize), redis::RedisError> = redis::pipe()
+                    .cmd("ZRANGE")
                     .arg(&key)
                     .arg(start)
                     .arg(stop - 1)
                     .arg("WITHSCORES")
-                    .query::<Vec<String>>(&mut con)
-                {
-                    Ok(values) => {
+                    .cmd("ZCARD")
+                    .arg(&key)
+                    .query_async(&mut con)
+                    .await;
+
+                match result {
+                    Ok((values, total_length)) => {
                         // Convert values to Vec<ZSetEnt>
-                        values
+                        let values: Vec<ZSetEnt> = values
                             .chunks(2)
                             .map(|chunk| ZSetEnt {
                                 member: chunk[0].clone(),
                                 score: chunk[1].parse().unwrap(),
                             })
-                            .collect()
-                    }
-                    Err(err) => {
-                        return Response::from_error(format!("Error: {:?}", err));
-                    }
-                };
-
-                // 获取 ZSet 的总长度
-                let total_length: usize = match redis::cmd("ZCARD").arg(&key).query(&mut con) {
-                    Ok(length) => length,
-                    Err(err) => {
-                        return Response::from_error(format!("Error: {:?}", err));
+                            .collect();
+                        // 返回包装在 ZSetData 中的结果
+                        Response::new("获取数据成功", Some(ZSetData::new(values, total_length)))
                     }
-                };
-
-                // 返回包装在 ZSetData 中的结果
-                Response::new("获取数据成功", Some(ZSetData::new(values, total_length)))
+                    Err(err) => Response::from_error(format!("Error: {:?}", err)),
+                }
             }
             Err(e) => Response::from_error(format!("Redis 链接异常: {}", e)),
         }
     }
 
-    pub fn set_string_data(&self, db_index: i32, key: String, value: String) -> Response<bool> {
-        let result = self.client.get_connection();
+    pub async fn set_string_data(
+        &self,
+        db_index: i32,
+        key: String,
+        value: String,
+    ) -> Response<bool> {
+        let result = self.connection(db_index).await;
 
         match result {
             Ok(mut con) => {
-                // 切换到指定的数据库
-                let _: () = redis::cmd("SELECT")
-                    .arg(db_index)
-                    .query(&mut con)
-                    .expect("Failed to SELECT database");
                 // 使用 SET 命令设置 String 数据
-                let result: Result<(), redis::RedisError> =
-                    redis::cmd("SET").arg(&key).arg(&value).query(&mut con);
+                let result: Result<(), redis::RedisError> = redis::cmd("SET")
+                    .arg(&key)
+                    .arg(&value)
+                    .query_async(&mut con)
+                    .await;
 
                 match result {
                     Ok(_) => Response::new("设置数据成功", Some(true)),
@@ -394,7 +385,7 @@ impl RedisOperation {
         }
     }
 
-    pub fn set_list_data(
+    pub async fn set_list_data(
         &self,
         db_index: i32,
         key: String,
@@ -404,19 +395,16 @@ impl RedisOperation {
             return Response::from_error("值列表不能为空");
         }
 
-        let result = self.client.get_connection();
+        let result = self.connection(db_index).await;
 
         match result {
             Ok(mut con) => {
-                // 切换到指定的数据库
-                let _: () = redis::cmd("SELECT")
-                    .arg(db_index)
-                    .query(&mut con)
-                    .expect("Failed to SELECT database");
-
                 // 使用 RPUSH 命令将值推入列表的右端
-                let result: Result<usize, redis::RedisError> =
-                    redis::cmd("RPUSH").arg(&key).arg(values).query(&mut con);
+                let result: Result<usize, redis::RedisError> = redis::cmd("RPUSH")
+                    .arg(&key)
+                    .arg(values)
+                    .query_async(&mut con)
+                    .await;
 
                 match result {
                     Ok(length) => Response::new("设置数据成功", Some(length)),
@@ -427,7 +415,7 @@ impl RedisOperation {
         }
     }
 
-    pub fn set_set_data(
+    pub async fn set_set_data(
         &self,
         db_index: i32,
         key: String,
@@ -437,19 +425,16 @@ impl RedisOperation {
             return Response::from_error("成员列表不能为空");
         }
 
-        let result = self.client.get_connection();
+        let result = self.connection(db_index).await;
 
         match result {
             Ok(mut con) => {
-                // 切换到指定的数据库
-                let _: () = redis::cmd("SELECT")
-                    .arg(db_index)
-                    .query(&mut con)
-                    .expect("Failed to SELECT database");
-
                 // 使用 SADD 命令将成员添加到集合中
-                let result: Result<usize, redis::RedisError> =
-                    redis::cmd("SADD").arg(&key).arg(members).query(&mut con);
+                let result: Result<usize, redis::RedisError> = redis::cmd("SADD")
+                    .arg(&key)
+                    .arg(members)
+                    .query_async(&mut con)
+                    .await;
 
                 match result {
                     Ok(count) => Response::new("设置数据成功", Some(count)),
@@ -460,7 +445,7 @@ impl RedisOperation {
         }
     }
 
-    pub fn set_hash_data(
+    pub async fn set_hash_data(
         &self,
         db_index: i32,
         key: String,
@@ -470,16 +455,10 @@ impl RedisOperation {
             return Response::from_error("字段值映射不能为空");
         }
 
-        let result = self.client.get_connection();
+        let result = self.connection(db_index).await;
 
         match result {
             Ok(mut con) => {
-                // 切换到指定的数据库
-                let _: () = redis::cmd("SELECT")
-                    .arg(db_index)
-                    .query(&mut con)
-                    .expect("Failed to SELECT database");
-
                 // 将 HashMap 转换为 Vec<(String, String)>
                 let field_values_vec: Vec<(&str, &str)> = field_values
                     .iter()
@@ -490,7 +469,8 @@ impl RedisOperation {
                 let result: Result<(), redis::RedisError> = redis::cmd("HMSET")
                     .arg(&key)
                     .arg(field_values_vec)
-                    .query(&mut con);
+                    .query_async(&mut con)
+                    .await;
 
                 match result {
                     Ok(_) => Response::new("设置数据成功", Some("OK".to_string())),
@@ -500,36 +480,37 @@ impl RedisOperation {
             Err(e) => Response::from_error(format!("Redis 链接异常: {}", e)),
         }
     }
-    pub fn remove_hash_hk_data(&self, db_index: i32, key: String, hk: String) -> Response<bool> {
-        let result = self.client.get_connection();
+    pub async fn remove_hash_hk_data(
+        &self,
+        db_index: i32,
+        key: String,
+        hk: String,
+    ) -> Response<bool> {
+        let result = self.connection(db_index).await;
 
         match result {
             Ok(mut con) => {
-                // 切换到指定的数据库
-                let _: () = redis::cmd("SELECT")
-                    .arg(db_index)
-                    .query(&mut con)
-                    .expect("Failed to SELECT database");
-
                 // 从 Hash 中删除指定的字段
-                let removed_count: i64 = redis::cmd("HDEL")
+                let result: Result<i64, redis::RedisError> = redis::cmd("HDEL")
                     .arg(key.as_str())
                     .arg(hk.as_str())
-                    .query(&mut con)
-                    .expect("Failed to remove field from hash");
+                    .query_async(&mut con)
+                    .await;
 
                 // 根据删除结果判断是否成功
-                if removed_count > 0 {
-                    Response::new("成功删除字段", Some(true))
-                } else {
-                    Response::new("字段不存在或删除失败", Some(false))
+                match result {
+                    Ok(removed_count) if removed_count > 0 => {
+                        Response::new("成功删除字段", Some(true))
+                    }
+                    Ok(_) => Response::new("字段不存在或删除失败", Some(false)),
+                    Err(err) => Response::from_error(format!("Error: {:?}", err)),
                 }
             }
             Err(e) => Response::from_error(format!("Redis 链接异常: {}", e)),
         }
     }
 
-    pub fn set_zset_data(
+    pub async fn set_zset_data(
         &self,
         db_index: i32,
         key: String,
@@ -539,16 +520,10 @@ impl RedisOperation {
             return Response::from_error("成员分数映射不能为空");
         }
 
-        let result = self.client.get_connection();
+        let result = self.connection(db_index).await;
 
         match result {
             Ok(mut con) => {
-                // 切换到指定的数据库
-                let _: () = redis::cmd("SELECT")
-                    .arg(db_index)
-                    .query(&mut con)
-                    .expect("Failed to SELECT database");
-
                 // 将 BTreeMap 转换为 Vec<(f64, &str)>
                 let members_scores_vec: Vec<(f64, &str)> = members_scores
                     .iter()
@@ -559,7 +534,8 @@ impl RedisOperation {
                 let result: Result<usize, redis::RedisError> = redis::cmd("ZADD")
                     .arg(&key)
                     .arg(members_scores_vec)
-                    .query(&mut con);
+                    .query_async(&mut con)
+                    .await;
 
                 match result {
                     Ok(count) => Response::new("设置数据成功", Some(count)),
@@ -569,25 +545,22 @@ impl RedisOperation {
             Err(e) => Response::from_error(format!("Redis 链接异常: {}", e)),
         }
     }
-    pub fn remove_member_from_zset(
+    pub async fn remove_member_from_zset(
         &self,
         db_index: i32,
         key: String,
         member: String,
     ) -> Response<usize> {
-        let result = self.client.get_connection();
+        let result = self.connection(db_index).await;
 
         match result {
             Ok(mut con) => {
-                // 切换到指定的数据库
-                let _: () = redis::cmd("SELECT")
-                    .arg(db_index)
-                    .query(&mut con)
-                    .expect("Failed to SELECT database");
-
                 // 使用 ZREM 命令删除指定成员
-                let result: Result<usize, redis::RedisError> =
-                    redis::cmd("ZREM").arg(&key).arg(member).query(&mut con);
+                let result: Result<usize, redis::RedisError> = redis::cmd("ZREM")
+                    .arg(&key)
+                    .arg(member)
+                    .query_async(&mut con)
+                    .await;
 
                 match result {
                     Ok(count) => Response::new("成功删除成员", Some(count)),
@@ -598,306 +571,2202 @@ impl RedisOperation {
         }
     }
 
-    pub fn get_ttl(&self, key: &str, db_index: i32) -> Result<Option<i64>, String> {
-        let result = self.client.get_connection();
-
-        match result {
-            Ok(mut con) => {
-                // 切换到指定的数据库
-                let _: () = redis::cmd("SELECT")
-                    .arg(db_index)
-                    .query(&mut con)
-                    .map_err(|e| format!("Failed to SELECT database: {:?}", e))?;
-
-                // 获取键的过期时间
-                let ttl: i64 = redis::cmd("TTL")
-                    .arg(key)
-                    .query(&mut con)
-                    .map_err(|e| format!("Failed to get TTL for key '{}': {:?}", key, e))?;
-
-                // 返回过期时间（-1 表示键不存在，-2 表示键存在但没有设置过期时间）
-                Ok(Some(ttl))
-            }
-            Err(e) => Err(format!("Redis 链接异常: {}", e)),
-        }
+    pub async fn get_ttl(&self, key: &str, db_index: i32) -> Result<Option<i64>, String> {
+        let mut con = self
+            .connection(db_index)
+            .await
+            .map_err(|e| format!("Redis 链接异常: {}", e))?;
+
+        // 获取键的过期时间
+        let ttl: i64 = redis::cmd("TTL")
+            .arg(key)
+            .query_async(&mut con)
+            .await
+            .map_err(|e| format!("Failed to get TTL for key '{}': {:?}", key, e))?;
+
+        // 返回过期时间（-1 表示键不存在，-2 表示键存在但没有设置过期时间）
+        Ok(Some(ttl))
     }
 
-    pub fn change_set(
+    pub async fn change_set(
         &self,
         db_index: i32,
         set_key: String,
         old_value: String,
         new_value: String,
     ) -> Response<bool> {
-        let result = self.client.get_connection();
+        let result = self.connection(db_index).await;
 
         match result {
             Ok(mut con) => {
-                // 切换到指定的数据库
-                let _: () = redis::cmd("SELECT")
-                    .arg(db_index)
-                    .query(&mut con)
-                    .expect("Failed to SELECT database");
-
-                // 从Set中移除旧值
-                redis::cmd("SREM")
+                // 在事务中从Set中移除旧值并添加新值
+                let result: Result<(), redis::RedisError> = redis::pipe()
+                    .atomic()
+                    .cmd("SREM")
                     .arg(set_key.as_str())
                     .arg(old_value.as_str())
-                    .execute(&mut con);
-
-                // 将新值添加到Set中
-                redis::cmd("SADD")
+                    .ignore()
+                    .cmd("SADD")
                     .arg(set_key.as_str())
                     .arg(new_value.as_str())
-                    .execute(&mut con);
+                    .ignore()
+                    .query_async(&mut con)
+                    .await;
 
-                Response::new("设置数据成功", Some(true))
+                match result {
+                    Ok(_) => Response::new("设置数据成功", Some(true)),
+                    Err(err) => Response::from_error(format!("Error: {:?}", err)),
+                }
             }
             Err(e) => Response::from_error(format!("Redis 链接异常: {}", e)),
         }
     }
 
-    pub fn remove_set_value(
+    pub async fn remove_set_value(
         &self,
         db_index: i32,
         set_key: String,
         old_value: String,
     ) -> Response<bool> {
-        let result = self.client.get_connection();
+        let result = self.connection(db_index).await;
 
         match result {
             Ok(mut con) => {
-                // 切换到指定的数据库
-                let _: () = redis::cmd("SELECT")
-                    .arg(db_index)
-                    .query(&mut con)
-                    .expect("Failed to SELECT database");
-
                 // 从Set中移除旧值
-                redis::cmd("SREM")
+                let result: Result<(), redis::RedisError> = redis::cmd("SREM")
                     .arg(set_key.as_str())
                     .arg(old_value.as_str())
-                    .execute(&mut con);
+                    .query_async(&mut con)
+                    .await;
 
-                Response::new("设置数据成功", Some(true))
+                match result {
+                    Ok(_) => Response::new("设置数据成功", Some(true)),
+                    Err(err) => Response::from_error(format!("Error: {:?}", err)),
+                }
             }
             Err(e) => Response::from_error(format!("Redis 链接异常: {}", e)),
         }
     }
 
-    pub fn change_list(
+    pub async fn change_list(
         &self,
         db_index: i32,
         list_key: String,
         old_value: String,
         new_value: String,
     ) -> Response<bool> {
-        let result = self.client.get_connection();
+        let result = self.connection(db_index).await;
 
         match result {
             Ok(mut con) => {
-                // 切换到指定的数据库
-                let _: () = redis::cmd("SELECT")
-                    .arg(db_index)
-                    .query(&mut con)
-                    .expect("Failed to SELECT database");
-
-                // 从List中移除旧值
-                let _removed_count: i32 = redis::cmd("LREM")
+                // 在事务中从List中移除旧值，并将新值添加到List的开头
+                let result: Result<(), redis::RedisError> = redis::pipe()
+                    .atomic()
+                    .cmd("LREM")
                     .arg(list_key.as_str())
                     .arg(0) // Remove all occurrences of the value
                     .arg(old_value.as_str())
-                    .query(&mut con)
-                    .expect("Failed to remove old value from list");
-
-                // 将新值添加到List的开头
-                redis::cmd("LPUSH")
+                    .ignore()
+                    .cmd("LPUSH")
                     .arg(list_key.as_str())
                     .arg(new_value.as_str())
-                    .execute(&mut con);
+                    .ignore()
+                    .query_async(&mut con)
+                    .await;
 
-                Response::new("设置数据成功", Some(true))
+                match result {
+                    Ok(_) => Response::new("设置数据成功", Some(true)),
+                    Err(err) => Response::from_error(format!("Error: {:?}", err)),
+                }
             }
             Err(e) => Response::from_error(format!("Redis 链接异常: {}", e)),
         }
     }
 
-    pub fn remove_list_value(
+    pub async fn remove_list_value(
         &self,
         db_index: i32,
         list_key: String,
         old_value: String,
     ) -> Response<bool> {
-        let result = self.client.get_connection();
+        let result = self.connection(db_index).await;
 
         match result {
             Ok(mut con) => {
-                // 切换到指定的数据库
-                let _: () = redis::cmd("SELECT")
-                    .arg(db_index)
-                    .query(&mut con)
-                    .expect("Failed to SELECT database");
-
                 // 从List中移除旧值
-                let _removed_count: i32 = redis::cmd("LREM")
+                let result: Result<i32, redis::RedisError> = redis::cmd("LREM")
                     .arg(list_key.as_str())
                     .arg(0) // Remove all occurrences of the value
                     .arg(old_value.as_str())
-                    .query(&mut con)
-                    .expect("Failed to remove old value from list");
+                    .query_async(&mut con)
+                    .await;
 
-                Response::new("设置数据成功", Some(true))
+                match result {
+                    Ok(_) => Response::new("设置数据成功", Some(true)),
+                    Err(err) => Response::from_error(format!("Error: {:?}", err)),
+                }
             }
             Err(e) => Response::from_error(format!("Redis 链接异常: {}", e)),
         }
     }
 
-    pub fn delete_redis_key(&self, db_index: i32, key: String) -> Response<bool> {
-        match self.client.get_connection() {
+    pub async fn delete_redis_key(&self, db_index: i32, key: String) -> Response<bool> {
+        match self.connection(db_index).await {
             Ok(mut con) => {
-                // 切换到指定的数据库
-                match redis::cmd("SELECT").arg(db_index).query::<()>(&mut con) {
-                    Ok(_) => {
-                        // 使用 DEL 删除指定的 key
-                        redis::cmd("DEL").arg(key.as_str()).execute(&mut con);
+                // 使用 DEL 删除指定的 key
+                let result: Result<(), redis::RedisError> = redis::cmd("DEL")
+                    .arg(key.as_str())
+                    .query_async(&mut con)
+                    .await;
 
-                        Response::new("删除 key 成功", Some(true))
-                    }
-                    Err(err) => {
-                        Response::from_error(format!("Failed to SELECT database: {:?}", err))
-                    }
+                match result {
+                    Ok(_) => Response::new("删除 key 成功", Some(true)),
+                    Err(err) => Response::from_error(format!("Error: {:?}", err)),
                 }
             }
             Err(e) => Response::from_error(format!("Redis 链接异常: {}", e)),
         }
     }
 
-    pub fn set_redis_key_expire(
+    pub async fn set_redis_key_expire(
         &self,
         db_index: i32,
         key: String,
         expiration_seconds: usize,
     ) -> Response<bool> {
-        match self.client.get_connection() {
+        match self.connection(db_index).await {
             Ok(mut con) => {
-                // 切换到指定的数据库
-                match redis::cmd("SELECT").arg(db_index).query::<()>(&mut con) {
-                    Ok(_) => {
-                        // 使用 EXPIRE 设置 key 的过期时间
-                        redis::cmd("EXPIRE")
-                            .arg(key.as_str())
-                            .arg(expiration_seconds)
-                            .execute(&mut con);
-                        Response::new("设置 key 过期时间成功", Some(true))
-                    }
-                    Err(err) => {
-                        Response::from_error(format!("Failed to SELECT database: {:?}", err))
-                    }
+                // 使用 EXPIRE 设置 key 的过期时间
+                let result: Result<(), redis::RedisError> = redis::cmd("EXPIRE")
+                    .arg(key.as_str())
+                    .arg(expiration_seconds)
+                    .query_async(&mut con)
+                    .await;
+
+                match result {
+                    Ok(_) => Response::new("设置 key 过期时间成功", Some(true)),
+                    Err(err) => Response::from_error(format!("Error: {:?}", err)),
                 }
             }
             Err(e) => Response::from_error(format!("Redis 链接异常: {}", e)),
         }
     }
 
-    pub fn persist_redis_key(&self, db_index: i32, key: &str) -> Response<bool> {
-        match self.client.get_connection() {
+    pub async fn persist_redis_key(&self, db_index: i32, key: &str) -> Response<bool> {
+        match self.connection(db_index).await {
             Ok(mut con) => {
-                // 切换到指定的数据库
-                match redis::cmd("SELECT").arg(db_index).query::<()>(&mut con) {
-                    Ok(_) => {
-                        // 使用 PERSIST 移除 key 的过期时间
-                        redis::cmd("PERSIST").arg(key).execute(&mut con);
-                        Response::new("设置 key 不过期成功", Some(true))
+                // 使用 PERSIST 移除 key 的过期时间
+                let result: Result<(), redis::RedisError> =
+                    redis::cmd("PERSIST").arg(key).query_async(&mut con).await;
+
+                match result {
+                    Ok(_) => Response::new("设置 key 不过期成功", Some(true)),
+                    Err(err) => Response::from_error(format!("Error: {:?}", err)),
+                }
+            }
+            Err(e) => Response::from_error(format!("Redis 链接异常: {}", e)),
+        }
+    }
+
+    /// 在独立连接上订阅频道和模式，收到的消息通过 on_message 回调，连接中断时回调 Closed
+    ///
+    /// # 参数
+    ///
+    /// * `session_id`: 订阅会话 id，会写入每条消息
+    /// * `channels`: SUBSCRIBE 的频道
+    /// * `patterns`: PSUBSCRIBE 的模式
+    ///
+    /// 返回: 后台监听线程句柄
+    pub fn subscribe<F>(
+        &self,
+        session_id: String,
+        channels: Vec<String>,
+        patterns: Vec<String>,
+        on_message: F,
+    ) -> Result<RedisListenHandle, String>
+    where
+        F: Fn(RedisListenEvent<PubSubMessage>) + Send + 'static,
+    {
+        if channels.is_empty() && patterns.is_empty() {
+            return Err("频道和模式不能同时为空".to_string());
+        }
+        let mut con = self
+            .client
+            .get_connection()
+            .map_err(|e| format!("Redis 链接异常: {}", e))?;
+
+        let stop = Arc::new(AtomicBool::new(false));
+        let thread_stop = stop.clone();
+        let (ready_tx, ready_rx) = mpsc::channel::<Result<(), String>>();
+
+        let join = thread::spawn(move || {
+            let mut pubsub = con.as_pubsub();
+            let mut subscribed = pubsub.set_read_timeout(Some(LISTEN_POLL_INTERVAL));
+            for channel in &channels {
+                subscribed = subscribed.and_then(|_| pubsub.subscribe(channel));
+            }
+            for pattern in &patterns {
+                subscribed = subscribed.and_then(|_| pubsub.psubscribe(pattern));
+            }
+            if let Err(err) = subscribed {
+                thread_stop.store(true, Ordering::SeqCst);
+                let _ = ready_tx.send(Err(format!("订阅失败: {:?}", err)));
+                return;
+            }
+            let _ = ready_tx.send(Ok(()));
+
+            while !thread_stop.load(Ordering::SeqCst) {
+                match pubsub.get_message() {
+                    Ok(msg) => {
+                        let pattern = if msg.from_pattern() {
+                            msg.get_pattern::<String>().ok()
+                        } else {
+                            None
+                        };
+                        on_message(RedisListenEvent::Message(PubSubMessage {
+                            session_id: session_id.clone(),
+                            channel: msg.get_channel_name().to_string(),
+                            pattern,
+                            payload: String::from_utf8_lossy(msg.get_payload_bytes()).to_string(),
+                            timestamp: Local::now().timestamp_millis(),
+                        }));
                     }
+                    Err(err) if err.is_timeout() => continue,
                     Err(err) => {
-                        Response::from_error(format!("Failed to SELECT database: {:?}", err))
+                        on_message(RedisListenEvent::Closed {
+                            session_id: session_id.clone(),
+                            error: format!("订阅中断: {:?}", err),
+                        });
+                        break;
                     }
                 }
             }
-            Err(e) => Response::from_error(format!("Redis 链接异常: {}", e)),
+            thread_stop.store(true, Ordering::SeqCst);
+        });
+
+        match ready_rx.recv() {
+            Ok(Ok(())) => Ok(RedisListenHandle::new(stop, join)),
+            Ok(Err(err)) => Err(err),
+            Err(_) => Err("订阅线程异常退出".to_string()),
         }
     }
-}
-
-#[derive(Serialize, Deserialize, Debug)]
-pub struct ZSetData {
-    pub values: Vec<ZSetEnt>,
-    pub total_length: usize,
-}
 
-#[derive(Serialize, Deserialize, Debug)]
-pub struct ZSetEnt {
-    pub member: String,
-    pub score: f64,
-}
+    /// PUBLISH 消息，返回收到消息的订阅者数量
+    pub async fn publish(&self, channel: String, message: String) -> Response<i64> {
+        match self.connection(0).await {
+            Ok(mut con) => {
+                let result: Result<i64, redis::RedisError> = redis::cmd("PUBLISH")
+                    .arg(&channel)
+                    .arg(&message)
+                    .query_async(&mut con)
+                    .await;
 
-impl ZSetData {
-    pub fn new(values: Vec<ZSetEnt>, total_length: usize) -> Self {
-        Self {
-            values,
-            total_length,
+                match result {
+                    Ok(receivers) => Response::new("发布消息成功", Some(receivers)),
+                    Err(err) => Response::from_error(format!("Error: {:?}", err)),
+                }
+            }
+            Err(e) => Response::from_error(format!("Redis 链接异常: {}", e)),
         }
     }
-}
-
-#[derive(Serialize, Deserialize, Debug)]
-pub struct HashData {
-    pub values: Vec<HashEnt>,
-    pub total_length: usize,
-}
 
-#[derive(Serialize, Deserialize, Debug)]
-pub struct HashEnt {
-    pub key: String,
-    pub val: String,
-}
+    /// PUBSUB CHANNELS，pattern 为空时返回全部活跃频道
+    pub async fn pubsub_channels(&self, pattern: &str) -> Response<Vec<String>> {
+        match self.connection(0).await {
+            Ok(mut con) => {
+                let mut cmd = redis::cmd("PUBSUB");
+                cmd.arg("CHANNELS");
+                if !pattern.is_empty() {
+                    cmd.arg(pattern);
+                }
+                let result: Result<Vec<String>, redis::RedisError> =
+                    cmd.query_async(&mut con).await;
 
-impl HashData {
-    pub fn new(values: Vec<HashEnt>, total_length: usize) -> Self {
-        Self {
-            values,
-            total_length,
+                match result {
+                    Ok(channels) => Response::new("操作成功", Some(channels)),
+                    Err(err) => Response::from_error(format!("Error: {:?}", err)),
+                }
+            }
+            Err(e) => Response::from_error(format!("Redis 链接异常: {}", e)),
         }
     }
-}
 
-#[derive(Serialize, Deserialize, Debug)]
-pub struct SetData {
-    pub values: HashSet<String>,
-    pub total_length: usize,
-}
+    /// PUBSUB NUMSUB，返回每个频道的订阅者数量
+    pub async fn pubsub_numsub(&self, channels: Vec<String>) -> Response<Vec<PubSubChannelCount>> {
+        match self.connection(0).await {
+            Ok(mut con) => {
+                let result: Result<Vec<String>, redis::RedisError> = redis::cmd("PUBSUB")
+                    .arg("NUMSUB")
+                    .arg(channels)
+                    .query_async(&mut con)
+                    .await;
 
-impl SetData {
-    pub fn new(values: HashSet<String>, total_length: usize) -> Self {
-        Self {
-            values,
-            total_length,
+                match result {
+                    Ok(values) => {
+                        let counts = values
+                            .chunks(2)
+                            .map(|chunk| PubSubChannelCount {
+                                channel: chunk[0].clone(),
+                                subscribers: chunk[1].parse().unwrap_or_default(),
+                            })
+                            .collect();
+                        Response::new("操作成功", Some(counts))
+                    }
+                    Err(err) => Response::from_error(format!("Error: {:?}", err)),
+                }
+            }
+            Err(e) => Response::from_error(format!("Redis 链接异常: {}", e)),
         }
     }
-}
 
-#[derive(Serialize, Deserialize, Debug)]
-pub struct ListData {
-    pub values: Vec<String>,
-    pub total_length: usize,
-}
+    /// PUBSUB NUMPAT，返回模式订阅总数
+    pub async fn pubsub_numpat(&self) -> Response<i64> {
+        match self.connection(0).await {
+            Ok(mut con) => {
+                let result: Result<i64, redis::RedisError> = redis::cmd("PUBSUB")
+                    .arg("NUMPAT")
+                    .query_async(&mut con)
+                    .await;
 
-impl ListData {
-    pub fn new(values: Vec<String>, total_length: usize) -> Self {
-        Self {
-            values,
-            total_length,
+                match result {
+                    Ok(count) => Response::new("操作成功", Some(count)),
+                    Err(err) => Response::from_error(format!("Error: {:?}", err)),
+                }
+            }
+            Err(e) => Response::from_error(format!("Redis 链接异常: {}", e)),
         }
     }
-}
 
-#[derive(Serialize, Deserialize, Debug)]
-pub struct ScanKeyResult {
-    pub new_cursor: usize,
-    pub keys: Vec<KeyInfo>, // 使用 Vec<KeyInfo> 替代 HashMap<String, KeyType>
-}
+    /// 在独立连接上执行 MONITOR，解析后的命令通过 on_entry 回调，连接中断时回调 Closed
+    ///
+    /// # 参数
+    ///
+    /// * `session_id`: 监控会话 id，会写入每条记录
+    /// * `filter`: 命令名、key 模式、客户端过滤条件
+    /// * `capture`: 可选，录制到本地文件
+    /// * `max_duration_secs`: 自动停止时间，0 表示不自动停止
+    ///
+    /// 返回: 后台监听线程句柄
+    pub fn monitor<F>(
+        &self,
+        session_id: String,
+        filter: MonitorFilter,
+        capture: Option<MonitorCapture>,
+        max_duration_secs: u64,
+        on_entry: F,
+    ) -> Result<RedisListenHandle, String>
+    where
+        F: Fn(RedisListenEvent<MonitorEntry>) + Send + 'static,
+    {
+        let key_regex = if filter.key_pattern.is_empty() {
+            None
+        } else {
+            Some(glob_to_regex(filter.key_pattern.as_str())?)
+        };
+        let commands: HashSet<String> = filter
+            .commands
+            .iter()
+            .map(|command| command.to_uppercase())
+            .collect();
+        let mut con = self
+            .client
+            .get_connection()
+            .map_err(|e| format!("Redis 链接异常: {}", e))?;
+        con.set_read_timeout(Some(LISTEN_POLL_INTERVAL))
+            .and_then(|_| redis::cmd("MONITOR").query::<()>(&mut con))
+            .map_err(|e| format!("MONITOR 执行失败: {:?}", e))?;
+
+        // MONITOR 成功后再创建录制文件，连接失败时不留下空文件
+        let mut capture_file = match &capture {
+            Some(capture) => Some(
+                File::create(&capture.file_path)
+                    .map(BufWriter::new)
+                    .map_err(|e| format!("创建录制文件失败: {}", e))?,
+            ),
+            None => None,
+        };
+        let max_entries = capture.map(|capture| capture.max_entries).unwrap_or(0);
 
-#[derive(Serialize, Deserialize, Debug)]
+        let stop = Arc::new(AtomicBool::new(false));
+        let thread_stop = stop.clone();
+        let join = thread::spawn(move || {
+            let started = Instant::now();
+            let mut captured = 0;
+
+            while !thread_stop.load(Ordering::SeqCst) {
+                if max_duration_secs > 0 && started.elapsed().as_secs() >= max_duration_secs {
+                    break;
+                }
+                let line = match con.recv_response() {
+                    Ok(redis::Value::Status(line)) => line,
+                    Ok(_) => continue,
+                    Err(err) if err.is_timeout() => continue,
+                    Err(err) => {
+                        on_entry(RedisListenEvent::Closed {
+                            session_id: session_id.clone(),
+                            error: format!("MONITOR 中断: {:?}", err),
+                        });
+                        break;
+                    }
+                };
+                let mut entry = match MonitorEntry::parse(line.as_str()) {
+                    Some(entry) => entry,
+                    None => continue,
+                };
+                if !commands.is_empty() && !commands.contains(&entry.command) {
+                    continue;
+                }
+                if let Some(re) = &key_regex {
+                    if !entry.key().is_some_and(|key| re.is_match(key)) {
+                        continue;
+                    }
+                }
+                if !filter.client.is_empty() && !entry.client.contains(filter.client.as_str()) {
+                    continue;
+                }
+
+                if let Some(file) = capture_file.as_mut() {
+                    if captured < max_entries && writeln!(file, "{}", entry.raw).is_ok() {
+                        captured += 1;
+                    }
+                    if captured >= max_entries {
+                        let _ = file.flush();
+                        capture_file = None;
+                    }
+                }
+                entry.session_id = session_id.clone();
+                on_entry(RedisListenEvent::Message(entry));
+            }
+            if let Some(mut file) = capture_file {
+                let _ = file.flush();
+            }
+            thread_stop.store(true, Ordering::SeqCst);
+        });
+
+        Ok(RedisListenHandle::new(stop, join))
+    }
+
+    /// 读取 MONITOR 录制文件，用于回放
+    pub fn read_monitor_capture(file_path: &str) -> Response<Vec<MonitorEntry>> {
+        match fs::read_to_string(file_path) {
+            Ok(content) => {
+                let entries = content.lines().filter_map(MonitorEntry::parse).collect();
+                Response::new("读取录制文件成功", Some(entries))
+            }
+            Err(err) => Response::from_error(format!("读取录制文件失败: {}", err)),
+        }
+    }
+
+    /// 按模式导出 key 到文件
+    ///
+    /// # 参数
+    ///
+    /// * `db_index`: 数据库索引
+    /// * `pattern`: SCAN MATCH 模式
+    /// * `format`: 导出格式
+    /// * `file_path`: 导出文件路径
+    ///
+    /// 返回: 导出数量、被跳过和读取失败的 key
+    pub async fn export_keys(
+        &self,
+        db_index: i32,
+        pattern: &str,
+        format: RedisTransferFormat,
+        file_path: &str,
+    ) -> Response<RedisExportResult> {
+        let mut con = match self.connection(db_index).await {
+            Ok(con) => con,
+            Err(e) => return Response::from_error(format!("Redis 链接异常: {}", e)),
+        };
+        let keys = match scan_match_keys(&mut con, pattern).await {
+            Ok(keys) => keys,
+            Err(err) => return Response::from_error(format!("Error: {:?}", err)),
+        };
+        let mut writer = match File::create(file_path) {
+            Ok(file) => BufWriter::new(file),
+            Err(err) => return Response::from_error(format!("创建导出文件失败: {}", err)),
+        };
+        if format == RedisTransferFormat::Csv {
+            if let Err(err) = writeln!(writer, "key,type,ttl,field,value") {
+                return Response::from_error(format!("写入导出文件失败: {}", err));
+            }
+        }
+
+        let mut result = RedisExportResult {
+            file_path: file_path.to_string(),
+            exported: 0,
+            skipped: vec![],
+            failed: vec![],
+        };
+        for batch in keys.chunks(TRANSFER_BATCH_SIZE) {
+            let values = match read_key_values(&mut con, batch).await {
+                Ok((values, failed)) => {
+                    result.failed.extend(failed);
+                    values
+                }
+                Err(err) => return Response::from_error(format!("Error: {:?}", err)),
+            };
+            for exported in values {
+                let written = match format {
+                    RedisTransferFormat::JsonLines => serde_json::to_string(&exported)
+                        .map_err(|e| e.to_string())
+                        .and_then(|line| writeln!(writer, "{}", line).map_err(|e| e.to_string())),
+                    RedisTransferFormat::Csv => {
+                        if exported.key_type == KeyType::Stream || exported.base64 {
+                            result.skipped.push(exported.key);
+                            continue;
+                        }
+                        csv_rows(&exported).iter().try_for_each(|row| {
+                            writeln!(writer, "{}", row.join(",")).map_err(|e| e.to_string())
+                        })
+                    }
+                    RedisTransferFormat::Resp => {
+                        let mut commands = vec![redis::cmd("DEL").arg(&exported.key).clone()];
+                        match write_commands(&exported, exported.key.as_str()) {
+                            Ok(written) => commands.extend(written),
+                            Err(err) => {
+                                result.failed.push(RedisKeyError {
+                                    key: exported.key,
+                                    error: err,
+                                });
+                                continue;
+                            }
+                        }
+                        commands.iter().try_for_each(|cmd| {
+                            writer
+                                .write_all(&cmd.get_packed_command())
+                                .map_err(|e| e.to_string())
+                        })
+                    }
+                };
+                if let Err(err) = written {
+                    return Response::from_error(format!("写入导出文件失败: {}", err));
+                }
+                result.exported += 1;
+            }
+        }
+        if let Err(err) = writer.flush() {
+            return Response::from_error(format!("写入导出文件失败: {}", err));
+        }
+        Response::new("导出成功", Some(result))
+    }
+
+    /// 从导出文件导入 key，按批次 pipeline 写入
+    ///
+    /// # 参数
+    ///
+    /// * `db_index`: 数据库索引
+    /// * `format`: 文件格式
+    /// * `file_path`: 导入文件路径
+    /// * `policy`: key 已存在时的处理方式
+    /// * `rename_suffix`: Rename 策略追加的后缀
+    ///
+    /// 返回: 导入统计和失败的 key
+    pub async fn import_keys(
+        &self,
+        db_index: i32,
+        format: RedisTransferFormat,
+        file_path: &str,
+        policy: ImportConflictPolicy,
+        rename_suffix: &str,
+    ) -> Response<RedisImportResult> {
+        if policy == ImportConflictPolicy::Rename && rename_suffix.is_empty() {
+            return Response::from_error("重命名后缀不能为空");
+        }
+        let content = match fs::read(file_path) {
+            Ok(content) => content,
+            Err(err) => return Response::from_error(format!("读取导入文件失败: {}", err)),
+        };
+        let (keys, mut failed) = match format {
+            RedisTransferFormat::JsonLines => parse_json_lines(&String::from_utf8_lossy(&content)),
+            RedisTransferFormat::Csv => parse_csv_keys(&String::from_utf8_lossy(&content)),
+            RedisTransferFormat::Resp => match parse_resp_keys(&content) {
+                Ok(parsed) => parsed,
+                Err(err) => return Response::from_error(err),
+            },
+        };
+
+        let mut con = match self.connection(db_index).await {
+            Ok(con) => con,
+            Err(e) => return Response::from_error(format!("Redis 链接异常: {}", e)),
+        };
+
+        let mut result = RedisImportResult {
+            total: keys.len() + failed.len(),
+            ..Default::default()
+        };
+        for batch in keys.chunks(TRANSFER_BATCH_SIZE) {
+            if let Err(err) =
+                write_key_batch(&mut con, batch, &policy, rename_suffix, &mut result).await
+            {
+                return Response::from_error(format!("Error: {:?}", err));
+            }
+        }
+        failed.append(&mut result.failed);
+        result.failed = failed;
+        Response::new("导入完成", Some(result))
+    }
+
+    /// 将当前连接中匹配的 key 迁移到目标连接
+    ///
+    /// 使用 DUMP/RESTORE 批量迁移，RESTORE 失败（例如版本不兼容）时按类型读取后写入
+    ///
+    /// # 参数
+    ///
+    /// * `target`: 目标连接
+    /// * `source_db`: 源数据库索引
+    /// * `target_db`: 目标数据库索引
+    /// * `pattern`: SCAN MATCH 模式
+    /// * `options`: 迁移选项
+    ///
+    /// 返回: 迁移统计和失败的 key
+    pub async fn migrate_keys(
+        &self,
+        target: &RedisOperation,
+        source_db: i32,
+        target_db: i32,
+        pattern: &str,
+        options: RedisMigrateOptions,
+    ) -> Response<RedisMigrateResult> {
+        let mut src = match self.connection(source_db).await {
+            Ok(con) => con,
+            Err(e) => return Response::from_error(format!("源 Redis 链接异常: {}", e)),
+        };
+        let mut dst = match target.connection(target_db).await {
+            Ok(con) => con,
+            Err(e) => return Response::from_error(format!("目标 Redis 链接异常: {}", e)),
+        };
+        let keys = match scan_match_keys(&mut src, pattern).await {
+            Ok(keys) => keys,
+            Err(err) => return Response::from_error(format!("Error: {:?}", err)),
+        };
+
+        let mut result = RedisMigrateResult {
+            total: keys.len(),
+            ..Default::default()
+        };
+        for batch in keys.chunks(TRANSFER_BATCH_SIZE) {
+            if let Err(err) =
+                migrate_key_batch(&mut src, &mut dst, batch, &options, &mut result).await
+            {
+                return Response::from_error(format!("Error: {:?}", err));
+            }
+        }
+        Response::new("迁移完成", Some(result))
+    }
+
+    /// 执行 Lua 脚本
+    ///
+    /// # 参数
+    ///
+    /// * `db_index`: 数据库索引
+    /// * `script`: Lua 脚本内容
+    /// * `keys`: KEYS 参数
+    /// * `args`: ARGV 参数
+    ///
+    /// 返回: 脚本的返回值
+    pub async fn eval_script(
+        &self,
+        db_index: i32,
+        script: &str,
+        keys: Vec<String>,
+        args: Vec<String>,
+    ) -> Response<RedisReply> {
+        let mut cmd = redis::cmd("EVAL");
+        cmd.arg(script).arg(keys.len()).arg(keys).arg(args);
+        self.query_reply(db_index, &cmd).await
+    }
+
+    /// 通过 SHA1 执行已缓存的脚本，脚本不存在时返回 NOSCRIPT 错误
+    pub async fn evalsha(
+        &self,
+        db_index: i32,
+        sha: &str,
+        keys: Vec<String>,
+        args: Vec<String>,
+    ) -> Response<RedisReply> {
+        let mut cmd = redis::cmd("EVALSHA");
+        cmd.arg(sha).arg(keys.len()).arg(keys).arg(args);
+        self.query_reply(db_index, &cmd).await
+    }
+
+    /// SCRIPT LOAD，返回脚本的 SHA1
+    pub async fn script_load(&self, script: &str) -> Response<String> {
+        let result = self.connection(0).await;
+        match result {
+            Ok(mut con) => {
+                let result: Result<String, redis::RedisError> = redis::cmd("SCRIPT")
+                    .arg("LOAD")
+                    .arg(script)
+                    .query_async(&mut con)
+                    .await;
+                match result {
+                    Ok(sha) => Response::new("加载脚本成功", Some(sha)),
+                    Err(err) => Response::from_error(format!("Error: {:?}", err)),
+                }
+            }
+            Err(e) => Response::from_error(format!("Redis 链接异常: {}", e)),
+        }
+    }
+
+    /// SCRIPT EXISTS，检查脚本是否已缓存
+    pub async fn script_exists(&self, shas: Vec<String>) -> Response<Vec<ScriptExistence>> {
+        if shas.is_empty() {
+            return Response::new("success", Some(Vec::new()));
+        }
+        let result = self.connection(0).await;
+        match result {
+            Ok(mut con) => {
+                let result: Result<Vec<bool>, redis::RedisError> = redis::cmd("SCRIPT")
+                    .arg("EXISTS")
+                    .arg(&shas)
+                    .query_async(&mut con)
+                    .await;
+                match result {
+                    Ok(exists) => {
+                        let values = shas
+                            .into_iter()
+                            .zip(exists)
+                            .map(|(sha, exists)| ScriptExistence { sha, exists })
+                            .collect();
+                        Response::new("success", Some(values))
+                    }
+                    Err(err) => Response::from_error(format!("Error: {:?}", err)),
+                }
+            }
+            Err(e) => Response::from_error(format!("Redis 链接异常: {}", e)),
+        }
+    }
+
+    /// SCRIPT FLUSH，清空服务端脚本缓存
+    pub async fn script_flush(&self) -> Response<bool> {
+        let result = self.connection(0).await;
+        match result {
+            Ok(mut con) => {
+                let result: Result<(), redis::RedisError> = redis::cmd("SCRIPT")
+                    .arg("FLUSH")
+                    .query_async(&mut con)
+                    .await;
+                match result {
+                    Ok(_) => Response::new("清空脚本缓存成功", Some(true)),
+                    Err(err) => Response::from_error(format!("Error: {:?}", err)),
+                }
+            }
+            Err(e) => Response::from_error(format!("Redis 链接异常: {}", e)),
+        }
+    }
+
+    /// FUNCTION LIST（Redis 7+）
+    ///
+    /// # 参数
+    ///
+    /// * `library_pattern`: 函数库名称模式，为空时返回全部
+    /// * `with_code`: 是否返回函数库源码
+    pub async fn function_list(
+        &self,
+        library_pattern: &str,
+        with_code: bool,
+    ) -> Response<Vec<RedisFunctionLibrary>> {
+        let result = self.connection(0).await;
+        match result {
+            Ok(mut con) => {
+                let mut cmd = redis::cmd("FUNCTION");
+                cmd.arg("LIST");
+                if !library_pattern.is_empty() {
+                    cmd.arg("LIBRARYNAME").arg(library_pattern);
+                }
+                if with_code {
+                    cmd.arg("WITHCODE");
+                }
+                let result: Result<Vec<redis::Value>, redis::RedisError> =
+                    cmd.query_async(&mut con).await;
+                match result {
+                    Ok(values) => {
+                        let libraries = values
+                            .iter()
+                            .filter_map(RedisFunctionLibrary::from_value)
+                            .collect();
+                        Response::new("success", Some(libraries))
+                    }
+                    Err(err) => Response::from_error(format!("Error: {:?}", err)),
+                }
+            }
+            Err(e) => Response::from_error(format!("Redis 链接异常: {}", e)),
+        }
+    }
+
+    /// FUNCTION LOAD，返回函数库名称
+    ///
+    /// # 参数
+    ///
+    /// * `code`: 以 `#!lua name=<library>` 开头的函数库源码
+    /// * `replace`: 函数库已存在时是否替换
+    pub async fn function_load(&self, code: &str, replace: bool) -> Response<String> {
+        let result = self.connection(0).await;
+        match result {
+            Ok(mut con) => {
+                let mut cmd = redis::cmd("FUNCTION");
+                cmd.arg("LOAD");
+                if replace {
+                    cmd.arg("REPLACE");
+                }
+                let result: Result<String, redis::RedisError> =
+                    cmd.arg(code).query_async(&mut con).await;
+                match result {
+                    Ok(library_name) => Response::new("加载函数库成功", Some(library_name)),
+                    Err(err) => Response::from_error(format!("Error: {:?}", err)),
+                }
+            }
+            Err(e) => Response::from_error(format!("Redis 链接异常: {}", e)),
+        }
+    }
+
+    /// FUNCTION DELETE，删除函数库
+    pub async fn function_delete(&self, library_name: &str) -> Response<bool> {
+        let result = self.connection(0).await;
+        match result {
+            Ok(mut con) => {
+                let result: Result<(), redis::RedisError> = redis::cmd("FUNCTION")
+                    .arg("DELETE")
+                    .arg(library_name)
+                    .query_async(&mut con)
+                    .await;
+                match result {
+                    Ok(_) => Response::new("删除函数库成功", Some(true)),
+                    Err(err) => Response::from_error(format!("Error: {:?}", err)),
+                }
+            }
+            Err(e) => Response::from_error(format!("Redis 链接异常: {}", e)),
+        }
+    }
+
+    /// 调用函数（Redis 7+）
+    ///
+    /// # 参数
+    ///
+    /// * `db_index`: 数据库索引
+    /// * `function`: 函数名
+    /// * `keys`: KEYS 参数
+    /// * `args`: ARGV 参数
+    /// * `read_only`: 使用 FCALL_RO，可在只读副本上执行
+    pub async fn fcall(
+        &self,
+        db_index: i32,
+        function: &str,
+        keys: Vec<String>,
+        args: Vec<String>,
+        read_only: bool,
+    ) -> Response<RedisReply> {
+        let mut cmd = redis::cmd(if read_only { "FCALL_RO" } else { "FCALL" });
+        cmd.arg(function).arg(keys.len()).arg(keys).arg(args);
+        self.query_reply(db_index, &cmd).await
+    }
+
+    // 在指定数据库执行命令并保留返回值类型
+    async fn query_reply(&self, db_index: i32, cmd: &redis::Cmd) -> Response<RedisReply> {
+        let result = self.connection(db_index).await;
+        match result {
+            Ok(mut con) => {
+                let result: Result<redis::Value, redis::RedisError> =
+                    cmd.query_async(&mut con).await;
+                match result {
+                    Ok(value) => Response::new("执行成功", Some(RedisReply::from(&value))),
+                    Err(err) => Response::from_error(format!("Error: {:?}", err)),
+                }
+            }
+            Err(e) => Response::from_error(format!("Redis 链接异常: {}", e)),
+        }
+    }
+
+    /// CONFIG GET，按分类分组并标记与默认值不同的配置
+    ///
+    /// # 参数
+    ///
+    /// * `pattern`: 配置名模式，例如 * 或 maxmemory*
+    pub async fn config_get(&self, pattern: &str) -> Response<Vec<RedisConfigGroup>> {
+        match self.config_items(pattern).await {
+            Ok((items, _)) => {
+                let mut groups: BTreeMap<String, Vec<RedisConfigItem>> = BTreeMap::new();
+                for item in items {
+                    groups.entry(item.category.clone()).or_default().push(item);
+                }
+                let groups = groups
+                    .into_iter()
+                    .map(|(category, items)| RedisConfigGroup { category, items })
+                    .collect();
+                Response::new("success", Some(groups))
+            }
+            Err(err) => Response::from_error(err),
+        }
+    }
+
+    /// 与当前版本默认值不同的配置，未收录该版本默认值时返回错误
+    pub async fn config_diff(&self) -> Response<Vec<RedisConfigItem>> {
+        match self.config_items("*").await {
+            Ok((_, redis_version)) if redis_version.is_empty() => {
+                Response::from_error("无法获取 Redis 版本，不能比较配置默认值")
+            }
+            Ok((_, redis_version)) if !has_config_defaults(redis_version.as_str()) => {
+                Response::from_error(format!(
+                    "未收录 Redis {} 的配置默认值，不能比较",
+                    redis_version
+                ))
+            }
+            Ok((items, _)) => {
+                let items = items.into_iter().filter(|item| item.modified).collect();
+                Response::new("success", Some(items))
+            }
+            Err(err) => Response::from_error(err),
+        }
+    }
+
+    /// CONFIG GET 的配置项及 INFO 中的 redis_version
+    async fn config_items(&self, pattern: &str) -> Result<(Vec<RedisConfigItem>, String), String> {
+        let mut con = self
+            .connection(0)
+            .await
+            .map_err(|e| format!("Redis 链接异常: {}", e))?;
+        let mut values: Vec<(String, String)> = redis::cmd("CONFIG")
+            .arg("GET")
+            .arg(pattern)
+            .query_async(&mut con)
+            .await
+            .map_err(|err| format!("Error: {:?}", err))?;
+        values.sort();
+        // 无法获取版本时不比较默认值
+        let info: Option<InfoDict> = redis::cmd("INFO")
+            .arg("server")
+            .query_async(&mut con)
+            .await
+            .ok();
+        let redis_version = info
+            .and_then(|info| info.get::<String>("redis_version"))
+            .unwrap_or_default();
+        let items = values
+            .into_iter()
+            .map(|(name, value)| RedisConfigItem::new(name, value, redis_version.as_str()))
+            .collect();
+        Ok((items, redis_version))
+    }
+
+    /// CONFIG SET，运行时修改配置
+    pub async fn config_set(&self, name: &str, value: &str) -> Response<bool> {
+        let result = self.connection(0).await;
+        match result {
+            Ok(mut con) => {
+                let result: Result<(), redis::RedisError> = redis::cmd("CONFIG")
+                    .arg("SET")
+                    .arg(name)
+                    .arg(value)
+                    .query_async(&mut con)
+                    .await;
+                match result {
+                    Ok(_) => Response::new("修改配置成功", Some(true)),
+                    Err(err) => Response::from_error(format!("Error: {:?}", err)),
+                }
+            }
+            Err(e) => Response::from_error(format!("Redis 链接异常: {}", e)),
+        }
+    }
+
+    /// CONFIG REWRITE，将运行时配置写回 redis.conf
+    pub async fn config_rewrite(&self) -> Response<bool> {
+        let result = self.connection(0).await;
+        match result {
+            Ok(mut con) => {
+                let result: Result<(), redis::RedisError> = redis::cmd("CONFIG")
+                    .arg("REWRITE")
+                    .query_async(&mut con)
+                    .await;
+                match result {
+                    Ok(_) => Response::new("写入配置文件成功", Some(true)),
+                    Err(err) => Response::from_error(format!("Error: {:?}", err)),
+                }
+            }
+            Err(e) => Response::from_error(format!("Redis 链接异常: {}", e)),
+        }
+    }
+
+    /// ACL LIST，解析为结构化的用户规则
+    pub async fn acl_list(&self) -> Response<Vec<AclUser>> {
+        let result = self.connection(0).await;
+        match result {
+            Ok(mut con) => {
+                let result: Result<Vec<String>, redis::RedisError> =
+                    redis::cmd("ACL").arg("LIST").query_async(&mut con).await;
+                match result {
+                    Ok(lines) => {
+                        let users = lines
+                            .iter()
+                            .filter_map(|line| AclUser::parse(line))
+                            .collect();
+                        Response::new("success", Some(users))
+                    }
+                    Err(err) => Response::from_error(format!("Error: {:?}", err)),
+                }
+            }
+            Err(e) => Response::from_error(format!("Redis 链接异常: {}", e)),
+        }
+    }
+
+    /// ACL GETUSER
+    pub async fn acl_get_user(&self, username: &str) -> Response<AclUser> {
+        let result = self.connection(0).await;
+        match result {
+            Ok(mut con) => {
+                let result: Result<redis::Value, redis::RedisError> = redis::cmd("ACL")
+                    .arg("GETUSER")
+                    .arg(username)
+                    .query_async(&mut con)
+                    .await;
+                match result {
+                    Ok(redis::Value::Nil) => Response::from_error("没有数据"),
+                    Ok(value) => match AclUser::from_getuser(username, &value) {
+                        Some(user) => Response::new("success", Some(user)),
+                        None => Response::from_error("解析 ACL 用户失败"),
+                    },
+                    Err(err) => Response::from_error(format!("Error: {:?}", err)),
+                }
+            }
+            Err(e) => Response::from_error(format!("Redis 链接异常: {}", e)),
+        }
+    }
+
+    /// 创建或修改 ACL 用户，先 reset 再写入全部规则
+    ///
+    /// # 参数
+    ///
+    /// * `user`: 用户规则，已有密码以 SHA256 保留
+    /// * `new_passwords`: 新增的明文密码
+    pub async fn acl_save_user(
+        &self,
+        user: &AclUser,
+        new_passwords: Vec<String>,
+    ) -> Response<bool> {
+        let result = self.connection(0).await;
+        match result {
+            Ok(mut con) => {
+                let result: Result<(), redis::RedisError> = redis::cmd("ACL")
+                    .arg("SETUSER")
+                    .arg(user.name.as_str())
+                    .arg("reset")
+                    .arg(user.to_rules())
+                    .arg(
+                        new_passwords
+                            .iter()
+                            .map(|password| format!(">{}", password))
+                            .collect::<Vec<String>>(),
+                    )
+                    .query_async(&mut con)
+                    .await;
+                match result {
+                    Ok(_) => Response::new("保存用户成功", Some(true)),
+                    Err(err) => Response::from_error(format!("Error: {:?}", err)),
+                }
+            }
+            Err(e) => Response::from_error(format!("Redis 链接异常: {}", e)),
+        }
+    }
+
+    /// ACL DELUSER，返回删除的用户数
+    pub async fn acl_delete_users(&self, usernames: Vec<String>) -> Response<i64> {
+        let result = self.connection(0).await;
+        match result {
+            Ok(mut con) => {
+                let result: Result<i64, redis::RedisError> = redis::cmd("ACL")
+                    .arg("DELUSER")
+                    .arg(usernames)
+                    .query_async(&mut con)
+                    .await;
+                match result {
+                    Ok(count) => Response::new("删除用户成功", Some(count)),
+                    Err(err) => Response::from_error(format!("Error: {:?}", err)),
+                }
+            }
+            Err(e) => Response::from_error(format!("Redis 链接异常: {}", e)),
+        }
+    }
+
+    /// ACL LOG，最近被拒绝的命令和认证失败
+    pub async fn acl_log(&self, count: usize) -> Response<Vec<AclLogEntry>> {
+        let result = self.connection(0).await;
+        match result {
+            Ok(mut con) => {
+                let result: Result<Vec<redis::Value>, redis::RedisError> = redis::cmd("ACL")
+                    .arg("LOG")
+                    .arg(count)
+                    .query_async(&mut con)
+                    .await;
+                match result {
+                    Ok(values) => {
+                        let entries = values.iter().filter_map(AclLogEntry::from_value).collect();
+                        Response::new("success", Some(entries))
+                    }
+                    Err(err) => Response::from_error(format!("Error: {:?}", err)),
+                }
+            }
+            Err(e) => Response::from_error(format!("Redis 链接异常: {}", e)),
+        }
+    }
+
+    /// ACL LOG RESET
+    pub async fn acl_log_reset(&self) -> Response<bool> {
+        let result = self.connection(0).await;
+        match result {
+            Ok(mut con) => {
+                let result: Result<(), redis::RedisError> = redis::cmd("ACL")
+                    .arg("LOG")
+                    .arg("RESET")
+                    .query_async(&mut con)
+                    .await;
+                match result {
+                    Ok(_) => Response::new("清空 ACL 日志成功", Some(true)),
+                    Err(err) => Response::from_error(format!("Error: {:?}", err)),
+                }
+            }
+            Err(e) => Response::from_error(format!("Redis 链接异常: {}", e)),
+        }
+    }
+
+    /// 解析后的复制状态
+    ///
+    /// # 参数
+    ///
+    /// * `lag_threshold`: 副本落后主节点的字节数告警阈值
+    pub async fn get_replication_status(
+        &self,
+        lag_threshold: i64,
+    ) -> Response<RedisReplicationStatus> {
+        match self.query_info("replication").await {
+            Ok(info) => Response::new(
+                "success",
+                Some(RedisReplicationStatus::from_info(&info, lag_threshold)),
+            ),
+            Err(err) => Response::from_error(err),
+        }
+    }
+
+    /// 解析后的持久化状态
+    pub async fn get_persistence_status(&self) -> Response<RedisPersistenceStatus> {
+        match self.query_info("persistence").await {
+            Ok(info) => Response::new("success", Some(RedisPersistenceStatus::from_info(&info))),
+            Err(err) => Response::from_error(err),
+        }
+    }
+
+    async fn query_info(&self, section: &str) -> Result<InfoDict, String> {
+        let mut con = self
+            .connection(0)
+            .await
+            .map_err(|e| format!("Redis 链接异常: {}", e))?;
+        redis::cmd("INFO")
+            .arg(section)
+            .query_async(&mut con)
+            .await
+            .map_err(|err| format!("Error: {:?}", err))
+    }
+
+    /// BGSAVE
+    ///
+    /// # 参数
+    ///
+    /// * `schedule`: 正在 AOF 重写时延后执行，而不是返回错误
+    pub async fn bgsave(&self, schedule: bool) -> Response<String> {
+        let mut cmd = redis::cmd("BGSAVE");
+        if schedule {
+            cmd.arg("SCHEDULE");
+        }
+        self.query_status(&cmd).await
+    }
+
+    /// BGREWRITEAOF
+    pub async fn bgrewriteaof(&self) -> Response<String> {
+        self.query_status(&redis::cmd("BGREWRITEAOF")).await
+    }
+
+    /// REPLICAOF，host 为空时执行 REPLICAOF NO ONE 提升为主节点
+    pub async fn replicaof(&self, host: &str, port: u16) -> Response<String> {
+        let mut cmd = redis::cmd("REPLICAOF");
+        if host.is_empty() {
+            cmd.arg("NO").arg("ONE");
+        } else {
+            cmd.arg(host).arg(port);
+        }
+        self.query_status(&cmd).await
+    }
+
+    /// FAILOVER（Redis 6.2+），在主节点上执行
+    pub async fn failover(&self, options: RedisFailoverOptions) -> Response<String> {
+        if !options.abort && options.force {
+            if options.host.is_empty() {
+                return Response::from_error("FORCE 需要指定目标副本");
+            }
+            if options.timeout_ms.is_none() {
+                return Response::from_error("FORCE 需要指定超时时间");
+            }
+        }
+        let mut cmd = redis::cmd("FAILOVER");
+        if options.abort {
+            cmd.arg("ABORT");
+        } else {
+            if !options.host.is_empty() {
+                cmd.arg("TO").arg(options.host.as_str()).arg(options.port);
+                if options.force {
+                    cmd.arg("FORCE");
+                }
+            }
+            if let Some(timeout_ms) = options.timeout_ms {
+                cmd.arg("TIMEOUT").arg(timeout_ms);
+            }
+        }
+        self.query_status(&cmd).await
+    }
+
+    // 执行返回状态的管理命令
+    async fn query_status(&self, cmd: &redis::Cmd) -> Response<String> {
+        let result = self.connection(0).await;
+        match result {
+            Ok(mut con) => {
+                let result: Result<redis::Value, redis::RedisError> =
+                    cmd.query_async(&mut con).await;
+                match result {
+                    Ok(redis::Value::Status(status)) => Response::new("执行成功", Some(status)),
+                    Ok(_) => Response::new("执行成功", Some("OK".to_string())),
+                    Err(err) => Response::from_error(format!("Error: {:?}", err)),
+                }
+            }
+            Err(e) => Response::from_error(format!("Redis 链接异常: {}", e)),
+        }
+    }
+}
+
+/// 监听线程读取超时时间，决定响应 stop 的延迟
+const LISTEN_POLL_INTERVAL: Duration = Duration::from_millis(500);
+
+/// 将 glob 模式（支持 * 和 ?）转换为正则
+fn glob_to_regex(pattern: &str) -> Result<Regex, String> {
+    let mut re = String::from("^");
+    for c in pattern.chars() {
+        match c {
+            '*' => re.push_str(".*"),
+            '?' => re.push('.'),
+            _ => re.push_str(regex::escape(c.to_string().as_str()).as_str()),
+        }
+    }
+    re.push('$');
+    Regex::new(re.as_str()).map_err(|e| format!("key 模式不合法: {}", e))
+}
+
+/// 导出、导入、迁移每批处理的 key 数量
+const TRANSFER_BATCH_SIZE: usize = 200;
+
+/// 使用 SCAN 遍历所有匹配的 key
+async fn scan_match_keys(
+    con: &mut ConnectionManager,
+    pattern: &str,
+) -> redis::RedisResult<Vec<String>> {
+    let mut keys = Vec::new();
+    let mut cursor: u64 = 0;
+    loop {
+        let (next, batch): (u64, Vec<String>) = redis::cmd("SCAN")
+            .arg(cursor)
+            .arg("MATCH")
+            .arg(pattern)
+            .arg("COUNT")
+            .arg(TRANSFER_BATCH_SIZE)
+            .query_async(con)
+            .await?;
+        keys.extend(batch);
+        if next == 0 {
+            return Ok(keys);
+        }
+        cursor = next;
+    }
+}
+
+/// 按类型读取一批 key 的 TTL 和值，读取时已不存在的 key 会被忽略
+///
+/// 返回: 读取到的 key 和读取失败的 key
+async fn read_key_values(
+    con: &mut ConnectionManager,
+    keys: &[String],
+) -> redis::RedisResult<(Vec<ExportedKey>, Vec<RedisKeyError>)> {
+    let mut meta_pipe = redis::pipe();
+    for key in keys {
+        meta_pipe.cmd("TYPE").arg(key).cmd("TTL").arg(key);
+    }
+    let meta: Vec<(String, i64)> = meta_pipe.query_async(con).await?;
+
+    let mut value_pipe = redis::pipe();
+    let mut found = Vec::new();
+    for (key, (type_str, ttl)) in keys.iter().zip(meta) {
+        let key_type = KeyType::from_string(type_str.as_str());
+        let cmd = match read_command(key, &key_type) {
+            Some(cmd) => cmd,
+            None => continue,
+        };
+        value_pipe.add_command(cmd.clone());
+        found.push((key.clone(), key_type, ttl, cmd));
+    }
+    if found.is_empty() {
+        return Ok((vec![], vec![]));
+    }
+    // pipeline 只返回第一个错误，某个 key 在两次读取之间被改成其他类型时逐个重新读取
+    let values: Vec<redis::RedisResult<redis::Value>> =
+        match value_pipe.query_async::<_, Vec<redis::Value>>(con).await {
+            Ok(values) => values.into_iter().map(Ok).collect(),
+            Err(err) if err.is_io_error() => return Err(err),
+            Err(_) => {
+                let mut values = Vec::with_capacity(found.len());
+                for (_, _, _, cmd) in &found {
+                    values.push(cmd.query_async(con).await);
+                }
+                values
+            }
+        };
+
+    let mut exported = Vec::new();
+    let mut failed = Vec::new();
+    for ((key, key_type, ttl, _), value) in found.into_iter().zip(values) {
+        match value.and_then(|value| to_key_value(&key_type, &value)) {
+            Ok(Some((value, base64))) => exported.push(ExportedKey {
+                key,
+                key_type,
+                ttl,
+                value,
+                base64,
+            }),
+            Ok(None) => {}
+            Err(err) => failed.push(RedisKeyError {
+                key,
+                error: format!("{:?}", err),
+            }),
+        }
+    }
+    Ok((exported, failed))
+}
+
+/// 按类型生成读取 key 值的命令，key 不存在或类型未知时返回 None
+fn read_command(key: &str, key_type: &KeyType) -> Option<redis::Cmd> {
+    let mut cmd = match key_type {
+        KeyType::String => redis::cmd("GET"),
+        KeyType::List => redis::cmd("LRANGE"),
+        KeyType::Set => redis::cmd("SMEMBERS"),
+        KeyType::ZSet => redis::cmd("ZRANGE"),
+        KeyType::Hash => redis::cmd("HGETALL"),
+        KeyType::Stream => redis::cmd("XRANGE"),
+        KeyType::None | KeyType::Unknown => return None,
+    };
+    cmd.arg(key);
+    match key_type {
+        KeyType::List => cmd.arg(0).arg(-1),
+        KeyType::ZSet => cmd.arg(0).arg(-1).arg("WITHSCORES"),
+        KeyType::Stream => cmd.arg("-").arg("+"),
+        _ => &mut cmd,
+    };
+    Some(cmd)
+}
+
+/// 将读取命令的返回值转换为 RedisKeyValue，值不全是 UTF-8 时整个 key 改用 base64 编码
+///
+/// 返回: 值和是否为 base64 编码，读取时 key 已过期则返回 None
+fn to_key_value(
+    key_type: &KeyType,
+    value: &redis::Value,
+) -> redis::RedisResult<Option<(RedisKeyValue, bool)>> {
+    match value {
+        redis::Value::Nil => return Ok(None),
+        // 除 stream 外集合类型不会为空，为空说明读取前 key 已过期
+        redis::Value::Bulk(items) if items.is_empty() && *key_type != KeyType::Stream => {
+            return Ok(None)
+        }
+        _ => {}
+    }
+    if let Some(text) = decode_key_value(key_type, value, |bytes| String::from_utf8(bytes).ok())? {
+        return Ok(Some((text, false)));
+    }
+    let encoded = decode_key_value(key_type, value, |bytes| Some(BASE64.encode(bytes)))?;
+    Ok(encoded.map(|encoded| (encoded, true)))
+}
+
+/// 按类型解析读取命令的返回值，`text` 将原始字节转为文本，任一值无法转换时返回 None
+fn decode_key_value(
+    key_type: &KeyType,
+    value: &redis::Value,
+    text: impl Fn(Vec<u8>) -> Option<String>,
+) -> redis::RedisResult<Option<RedisKeyValue>> {
+    let pair = |(field, value): (Vec<u8>, Vec<u8>)| Some((text(field)?, text(value)?));
+    Ok(match key_type {
+        KeyType::String => text(redis::from_redis_value(value)?).map(RedisKeyValue::String),
+        KeyType::List | KeyType::Set => {
+            let members: Vec<Vec<u8>> = redis::from_redis_value(value)?;
+            let members: Option<Vec<String>> = members.into_iter().map(&text).collect();
+            members.map(RedisKeyValue::Members)
+        }
+        KeyType::ZSet => {
+            let members: Vec<(Vec<u8>, f64)> = redis::from_redis_value(value)?;
+            let members: Option<Vec<ZSetEnt>> = members
+                .into_iter()
+                .map(|(member, score)| {
+                    Some(ZSetEnt {
+                        member: text(member)?,
+                        score,
+                    })
+                })
+                .collect();
+            members.map(RedisKeyValue::ZSet)
+        }
+        KeyType::Hash => {
+            let fields: Vec<(Vec<u8>, Vec<u8>)> = redis::from_redis_value(value)?;
+            let fields: Option<BTreeMap<String, String>> = fields.into_iter().map(pair).collect();
+            fields.map(RedisKeyValue::Hash)
+        }
+        _ => {
+            let entries: Vec<redis::Value> = redis::from_redis_value(value)?;
+            let mut stream = Vec::new();
+            for entry in entries {
+                let (id, fields): (String, redis::Value) = redis::from_redis_value(&entry)?;
+                let fields: Vec<(Vec<u8>, Vec<u8>)> = redis::from_redis_value(&fields)?;
+                match fields.into_iter().map(pair).collect() {
+                    Some(fields) => stream.push(StreamEnt { id, fields }),
+                    None => return Ok(None),
+                }
+            }
+            Some(RedisKeyValue::Stream(stream))
+        }
+    })
+}
+
+/// 将已按 UTF-8 保存的值改为 base64 编码
+fn encode_key_value(value: &mut RedisKeyValue) {
+    let encode = |fields: &BTreeMap<String, String>| {
+        fields
+            .iter()
+            .map(|(field, value)| (BASE64.encode(field), BASE64.encode(value)))
+            .collect()
+    };
+    match value {
+        RedisKeyValue::String(value) => *value = BASE64.encode(&value),
+        RedisKeyValue::Members(values) => values
+            .iter_mut()
+            .for_each(|value| *value = BASE64.encode(&value)),
+        RedisKeyValue::Hash(fields) => *fields = encode(fields),
+        RedisKeyValue::ZSet(values) => values
+            .iter_mut()
+            .for_each(|ent| ent.member = BASE64.encode(&ent.member)),
+        RedisKeyValue::Stream(entries) => entries
+            .iter_mut()
+            .for_each(|entry| entry.fields = encode(&entry.fields)),
+    }
+}
+
+/// 生成写入 key 的命令（不含 DEL），ttl 大于 0 时追加 EXPIRE
+///
+/// 值为 base64 编码时先解码，返回空列表表示类型与值不匹配
+fn write_commands(exported: &ExportedKey, target_key: &str) -> Result<Vec<redis::Cmd>, String> {
+    let raw = |text: &String| -> Result<Vec<u8>, String> {
+        if exported.base64 {
+            BASE64
+                .decode(text)
+                .map_err(|err| format!("base64 解码失败: {}", err))
+        } else {
+            Ok(text.as_bytes().to_vec())
+        }
+    };
+    let raw_fields = |fields: &BTreeMap<String, String>| {
+        fields
+            .iter()
+            .map(|(field, value)| Ok((raw(field)?, raw(value)?)))
+            .collect::<Result<Vec<_>, String>>()
+    };
+    let mut commands = Vec::new();
+    match (&exported.key_type, &exported.value) {
+        (KeyType::String, RedisKeyValue::String(value)) => {
+            commands.push(redis::cmd("SET").arg(target_key).arg(raw(value)?).clone());
+        }
+        (KeyType::List, RedisKeyValue::Members(values)) if !values.is_empty() => {
+            let values = values.iter().map(raw).collect::<Result<Vec<_>, _>>()?;
+            commands.push(redis::cmd("RPUSH").arg(target_key).arg(values).clone());
+        }
+        (KeyType::Set, RedisKeyValue::Members(values)) if !values.is_empty() => {
+            let values = values.iter().map(raw).collect::<Result<Vec<_>, _>>()?;
+            commands.push(redis::cmd("SADD").arg(target_key).arg(values).clone());
+        }
+        (KeyType::ZSet, RedisKeyValue::ZSet(values)) if !values.is_empty() => {
+            let mut cmd = redis::cmd("ZADD");
+            cmd.arg(target_key);
+            for ent in values {
+                cmd.arg(ent.score).arg(raw(&ent.member)?);
+            }
+            commands.push(cmd);
+        }
+        (KeyType::Hash, RedisKeyValue::Hash(fields)) if !fields.is_empty() => {
+            commands.push(
+                redis::cmd("HSET")
+                    .arg(target_key)
+                    .arg(raw_fields(fields)?)
+                    .clone(),
+            );
+        }
+        (KeyType::Stream, RedisKeyValue::Stream(entries)) => {
+            for entry in entries {
+                commands.push(
+                    redis::cmd("XADD")
+                        .arg(target_key)
+                        .arg(&entry.id)
+                        .arg(raw_fields(&entry.fields)?)
+                        .clone(),
+                );
+            }
+        }
+        _ => return Ok(commands),
+    }
+    if exported.ttl > 0 && !commands.is_empty() {
+        commands.push(
+            redis::cmd("EXPIRE")
+                .arg(target_key)
+                .arg(exported.ttl)
+                .clone(),
+        );
+    }
+    Ok(commands)
+}
+
+/// 按冲突策略写入一批 key
+///
+/// 每个 key 的写入都是独立事务，不会因同批其他 key 失败而重复执行
+async fn write_key_batch(
+    con: &mut ConnectionManager,
+    batch: &[ExportedKey],
+    policy: &ImportConflictPolicy,
+    rename_suffix: &str,
+    result: &mut RedisImportResult,
+) -> redis::RedisResult<()> {
+    let targets: Vec<String> = batch.iter().map(|exported| exported.key.clone()).collect();
+    let mut exists_pipe = redis::pipe();
+    for target in &targets {
+        exists_pipe.cmd("EXISTS").arg(target);
+        if *policy == ImportConflictPolicy::Rename {
+            exists_pipe
+                .cmd("EXISTS")
+                .arg(format!("{}{}", target, rename_suffix));
+        }
+    }
+    let exists: Vec<bool> = exists_pipe.query_async(con).await?;
+    let step = if *policy == ImportConflictPolicy::Rename {
+        2
+    } else {
+        1
+    };
+
+    let mut pending = Vec::new();
+    for (index, (exported, target)) in batch.iter().zip(targets).enumerate() {
+        let target_exists = exists[index * step];
+        let target = match policy {
+            ImportConflictPolicy::Skip if target_exists => {
+                result.skipped += 1;
+                continue;
+            }
+            ImportConflictPolicy::Rename if target_exists => {
+                if exists[index * step + 1] {
+                    result.failed.push(RedisKeyError {
+                        key: exported.key.clone(),
+                        error: "重命名后的 key 已存在".to_string(),
+                    });
+                    continue;
+                }
+                format!("{}{}", target, rename_suffix)
+            }
+            _ => target,
+        };
+        let commands = match write_commands(exported, target.as_str()) {
+            Ok(commands) if !commands.is_empty() => commands,
+            outcome => {
+                result.failed.push(RedisKeyError {
+                    key: exported.key.clone(),
+                    error: outcome
+                        .err()
+                        .unwrap_or_else(|| "类型与值不匹配或值为空".to_string()),
+                });
+                continue;
+            }
+        };
+        pending.push(PendingWrite {
+            key: exported.key.clone(),
+            target,
+            existed: target_exists && *policy == ImportConflictPolicy::Overwrite,
+            commands,
+        });
+    }
+
+    // 每个 key 单独用 MULTI/EXEC 先 DEL 再写入，各事务经同一连接并发发送，按各自的回复计数
+    let writes = pending.iter().map(|write| {
+        let mut con = con.clone();
+        async move {
+            // 覆盖已存在的 key 前先 DUMP 备份，写入失败时还原
+            let backup: Option<(Option<Vec<u8>>, i64)> = if write.existed {
+                redis::pipe()
+                    .cmd("DUMP")
+                    .arg(&write.target)
+                    .cmd("PTTL")
+                    .arg(&write.target)
+                    .query_async(&mut con)
+                    .await
+                    .ok()
+            } else {
+                None
+            };
+            let mut pipe = redis::pipe();
+            pipe.atomic().cmd("DEL").arg(&write.target).ignore();
+            for cmd in &write.commands {
+                pipe.add_command(cmd.clone()).ignore();
+            }
+            let err = match pipe.query_async::<_, ()>(&mut con).await {
+                Ok(_) => return Ok(()),
+                Err(err) => err,
+            };
+            // 事务中某条命令执行失败时其余命令仍会生效，原先不存在的 key 删除写了一半的数据
+            if !write.existed {
+                let _: redis::RedisResult<()> = redis::cmd("DEL")
+                    .arg(&write.target)
+                    .query_async(&mut con)
+                    .await;
+                return Err(format!("{:?}", err));
+            }
+            let (dump, pttl) = match backup {
+                Some((Some(dump), pttl)) => (dump, pttl),
+                _ => {
+                    return Err(format!(
+                        "{:?}，写入前备份目标 key 失败，原值可能已丢失",
+                        err
+                    ))
+                }
+            };
+            let restored: redis::RedisResult<()> = redis::cmd("RESTORE")
+                .arg(&write.target)
+                .arg(pttl.max(0))
+                .arg(dump)
+                .arg("REPLACE")
+                .query_async(&mut con)
+                .await;
+            match restored {
+                Ok(_) => Err(format!("{:?}，目标 key 已还原为原值", err)),
+                Err(restore_err) => Err(format!(
+                    "{:?}，还原目标 key 失败，原值可能已丢失: {:?}",
+                    err, restore_err
+                )),
+            }
+        }
+    });
+    for (write, outcome) in pending.iter().zip(join_all(writes).await) {
+        match outcome {
+            Ok(_) => result.imported += 1,
+            Err(error) => result.failed.push(RedisKeyError {
+                key: write.key.clone(),
+                error,
+            }),
+        }
+    }
+    Ok(())
+}
+
+/// 待写入的 key
+struct PendingWrite {
+    key: String,
+    target: String,
+    /// 覆盖写入前目标已存在，写入失败时用 DUMP 备份还原而不是删除
+    existed: bool,
+    commands: Vec<redis::Cmd>,
+}
+
+/// 按迁移选项替换 key 前缀
+fn rewrite_key(key: &str, options: &RedisMigrateOptions) -> String {
+    match key.strip_prefix(options.source_prefix.as_str()) {
+        Some(rest) if !options.source_prefix.is_empty() || !options.target_prefix.is_empty() => {
+            format!("{}{}", options.target_prefix, rest)
+        }
+        _ => key.to_string(),
+    }
+}
+
+/// 迁移一批 key，RESTORE 并发发送并逐个检查回复，失败的 key 回退为按类型复制
+async fn migrate_key_batch(
+    src: &mut ConnectionManager,
+    dst: &mut ConnectionManager,
+    batch: &[String],
+    options: &RedisMigrateOptions,
+    result: &mut RedisMigrateResult,
+) -> redis::RedisResult<()> {
+    let mut dump_pipe = redis::pipe();
+    for key in batch {
+        dump_pipe.cmd("PTTL").arg(key).cmd("DUMP").arg(key);
+    }
+    let dumps: Vec<(i64, Option<Vec<u8>>)> = dump_pipe.query_async(src).await?;
+    let targets: Vec<String> = batch.iter().map(|key| rewrite_key(key, options)).collect();
+
+    let exists: Vec<bool> = if options.replace {
+        vec![false; batch.len()]
+    } else {
+        let mut exists_pipe = redis::pipe();
+        for target in &targets {
+            exists_pipe.cmd("EXISTS").arg(target);
+        }
+        exists_pipe.query_async(dst).await?
+    };
+
+    let mut pending = Vec::new();
+    for (((key, target), (pttl, payload)), exists) in
+        batch.iter().zip(targets).zip(dumps).zip(exists)
+    {
+        let payload = match payload {
+            Some(payload) if !exists => payload,
+            // key 已过期或目标已存在
+            _ => {
+                result.skipped += 1;
+                continue;
+            }
+        };
+        let ttl = if options.keep_ttl && pttl > 0 {
+            pttl
+        } else {
+            0
+        };
+        let mut cmd = redis::cmd("RESTORE");
+        cmd.arg(&target).arg(ttl).arg(payload);
+        if options.replace {
+            cmd.arg("REPLACE");
+        }
+        pending.push((key.clone(), target, cmd));
+    }
+
+    let restores = pending.iter().map(|(_, _, cmd)| {
+        let mut con = dst.clone();
+        async move { cmd.query_async::<_, ()>(&mut con).await }
+    });
+    let outcomes = join_all(restores).await;
+
+    for ((key, target, _), outcome) in pending.into_iter().zip(outcomes) {
+        let restore_err = match outcome {
+            Ok(_) => {
+                result.migrated += 1;
+                continue;
+            }
+            // 检查存在之后目标 key 被其他客户端创建
+            Err(err) if err.code() == Some("BUSYKEY") => {
+                result.skipped += 1;
+                continue;
+            }
+            Err(err) => err,
+        };
+
+        // RESTORE 失败时按类型读取后写入
+        let (mut values, mut read_failed) =
+            match read_key_values(src, std::slice::from_ref(&key)).await {
+                Ok(read) => read,
+                Err(err) => (
+                    vec![],
+                    vec![RedisKeyError {
+                        key: key.clone(),
+                        error: format!("{:?}", err),
+                    }],
+                ),
+            };
+        if let Some(read_err) = read_failed.pop() {
+            result.failed.push(RedisKeyError {
+                key,
+                error: format!(
+                    "RESTORE 失败: {:?}，读取源数据失败: {}",
+                    restore_err, read_err.error
+                ),
+            });
+            continue;
+        }
+        let mut exported = match values.pop() {
+            Some(exported) => exported,
+            None => {
+                result.skipped += 1;
+                continue;
+            }
+        };
+        exported.key = target;
+        if !options.keep_ttl {
+            exported.ttl = -1;
+        }
+        let policy = if options.replace {
+            ImportConflictPolicy::Overwrite
+        } else {
+            ImportConflictPolicy::Skip
+        };
+        let mut copy_result = RedisImportResult::default();
+        write_key_batch(dst, &[exported], &policy, "", &mut copy_result).await?;
+        result.copied += copy_result.imported;
+        result.skipped += copy_result.skipped;
+        result
+            .failed
+            .extend(copy_result.failed.into_iter().map(|failed| RedisKeyError {
+                key: key.clone(),
+                error: format!(
+                    "RESTORE 失败: {:?}，复制失败: {}",
+                    restore_err, failed.error
+                ),
+            }));
+    }
+    Ok(())
+}
+
+/// 生成一个 key 的 CSV 行，列为 key,type,ttl,field,value
+///
+/// list 的 field 为下标，zset 的 field 为分数，hash 的 field 为字段名
+fn csv_rows(exported: &ExportedKey) -> Vec<Vec<String>> {
+    let row = |field: String, value: &str| {
+        vec![
+            csv_escape(exported.key.as_str()),
+            exported.key_type.as_str().to_string(),
+            exported.ttl.to_string(),
+            csv_escape(field.as_str()),
+            csv_escape(value),
+        ]
+    };
+    match &exported.value {
+        RedisKeyValue::String(value) => vec![row(String::new(), value)],
+        RedisKeyValue::Members(values) => values
+            .iter()
+            .enumerate()
+            .map(|(index, value)| match exported.key_type {
+                KeyType::List => row(index.to_string(), value),
+                _ => row(String::new(), value),
+            })
+            .collect(),
+        RedisKeyValue::Hash(fields) => fields
+            .iter()
+            .map(|(field, value)| row(field.clone(), value))
+            .collect(),
+        RedisKeyValue::ZSet(values) => values
+            .iter()
+            .map(|ent| row(ent.score.to_string(), ent.member.as_str()))
+            .collect(),
+        RedisKeyValue::Stream(_) => vec![],
+    }
+}
+
+/// 解析 JSON Lines 导出文件
+fn parse_json_lines(content: &str) -> (Vec<ExportedKey>, Vec<RedisKeyError>) {
+    let mut keys = Vec::new();
+    let mut failed = Vec::new();
+    for (index, line) in content.lines().enumerate() {
+        if line.trim().is_empty() {
+            continue;
+        }
+        match serde_json::from_str::<ExportedKey>(line) {
+            Ok(exported) => keys.push(exported),
+            Err(err) => failed.push(RedisKeyError {
+                key: format!("line {}", index + 1),
+                error: err.to_string(),
+            }),
+        }
+    }
+    (keys, failed)
+}
+
+/// 解析 CSV 导出文件，同一个 key 的多行合并为一个 ExportedKey
+fn parse_csv_keys(content: &str) -> (Vec<ExportedKey>, Vec<RedisKeyError>) {
+    let mut keys: Vec<ExportedKey> = Vec::new();
+    let mut failed = Vec::new();
+    for (index, record) in parse_csv_records(content).into_iter().enumerate().skip(1) {
+        let (key, type_str, ttl, field, value) = match record.as_slice() {
+            [key, type_str, ttl, field, value] => (key, type_str, ttl, field, value),
+            _ => {
+                failed.push(RedisKeyError {
+                    key: format!("line {}", index + 1),
+                    error: "列数不正确".to_string(),
+                });
+                continue;
+            }
+        };
+        let key_type = KeyType::from_string(type_str);
+        let ttl = ttl.parse().unwrap_or(-1);
+        if keys.last().map_or(true, |last| &last.key != key) {
+            let value = match key_type {
+                KeyType::String => RedisKeyValue::String(String::new()),
+                KeyType::List | KeyType::Set => RedisKeyValue::Members(vec![]),
+                KeyType::Hash => RedisKeyValue::Hash(BTreeMap::new()),
+                KeyType::ZSet => RedisKeyValue::ZSet(vec![]),
+                _ => {
+                    failed.push(RedisKeyError {
+                        key: key.clone(),
+                        error: format!("CSV 不支持的类型: {}", type_str),
+                    });
+                    continue;
+                }
+            };
+            keys.push(ExportedKey {
+                key: key.clone(),
+                key_type,
+                ttl,
+                value,
+                base64: false,
+            });
+        }
+        let last = keys.last_mut().unwrap();
+        match &mut last.value {
+            RedisKeyValue::String(current) => *current = value.clone(),
+            RedisKeyValue::Members(values) => values.push(value.clone()),
+            RedisKeyValue::Hash(fields) => {
+                fields.insert(field.clone(), value.clone());
+            }
+            RedisKeyValue::ZSet(values) => values.push(ZSetEnt {
+                member: value.clone(),
+                score: field.parse().unwrap_or_default(),
+            }),
+            RedisKeyValue::Stream(_) => {}
+        }
+    }
+    (keys, failed)
+}
+
+/// 解析 RESP 命令文件为命令参数列表，参数保留原始字节
+fn parse_resp_commands(content: &[u8]) -> Result<Vec<Vec<Vec<u8>>>, String> {
+    fn read_line(content: &[u8], pos: &mut usize) -> Result<String, String> {
+        let start = *pos;
+        while *pos + 1 < content.len() && &content[*pos..*pos + 2] != b"\r\n" {
+            *pos += 1;
+        }
+        if *pos + 1 >= content.len() {
+            return Err(format!("RESP 格式错误，位置 {}", start));
+        }
+        let line = String::from_utf8_lossy(&content[start..*pos]).to_string();
+        *pos += 2;
+        Ok(line)
+    }
+
+    let mut commands = Vec::new();
+    let mut pos = 0;
+    while pos < content.len() {
+        let header = read_line(content, &mut pos)?;
+        let count: usize = header
+            .strip_prefix('*')
+            .and_then(|count| count.parse().ok())
+            .ok_or_else(|| format!("RESP 格式错误: {}", header))?;
+        let mut args = Vec::with_capacity(count);
+        for _ in 0..count {
+            let len_line = read_line(content, &mut pos)?;
+            let len: usize = len_line
+                .strip_prefix('$')
+                .and_then(|len| len.parse().ok())
+                .ok_or_else(|| format!("RESP 格式错误: {}", len_line))?;
+            if pos + len + 2 > content.len() {
+                return Err("RESP 文件不完整".to_string());
+            }
+            args.push(content[pos..pos + len].to_vec());
+            pos += len + 2;
+        }
+        commands.push(args);
+    }
+    Ok(commands)
+}
+
+/// 将导出的 RESP 命令还原为 ExportedKey，DEL 命令会被忽略
+fn parse_resp_keys(content: &[u8]) -> Result<(Vec<ExportedKey>, Vec<RedisKeyError>), String> {
+    let mut keys: Vec<ExportedKey> = Vec::new();
+    let mut failed = Vec::new();
+    for args in parse_resp_commands(content)? {
+        let (name, key, rest) = match args.as_slice() {
+            [name, key, rest @ ..] => (
+                String::from_utf8_lossy(name).to_uppercase(),
+                String::from_utf8_lossy(key).to_string(),
+                rest,
+            ),
+            _ => continue,
+        };
+        if name == "DEL" {
+            continue;
+        }
+        if name == "EXPIRE" {
+            if let Some(last) = keys.iter_mut().rev().find(|last| last.key == key) {
+                last.ttl = rest
+                    .first()
+                    .and_then(|ttl| String::from_utf8_lossy(ttl).parse().ok())
+                    .unwrap_or(-1);
+            }
+            continue;
+        }
+        let key_type = match name.as_str() {
+            "SET" => KeyType::String,
+            "RPUSH" => KeyType::List,
+            "SADD" => KeyType::Set,
+            "ZADD" => KeyType::ZSet,
+            "HSET" => KeyType::Hash,
+            "XADD" => KeyType::Stream,
+            _ => {
+                failed.push(RedisKeyError {
+                    key,
+                    error: format!("不支持的命令: {}", name),
+                });
+                continue;
+            }
+        };
+        if keys
+            .last()
+            .map_or(true, |last| last.key != key || last.key_type != key_type)
+        {
+            let value = match key_type {
+                KeyType::String => RedisKeyValue::String(String::new()),
+                KeyType::List | KeyType::Set => RedisKeyValue::Members(vec![]),
+                KeyType::Hash => RedisKeyValue::Hash(BTreeMap::new()),
+                KeyType::ZSet => RedisKeyValue::ZSet(vec![]),
+                _ => RedisKeyValue::Stream(vec![]),
+            };
+            keys.push(ExportedKey {
+                key,
+                key_type,
+                ttl: -1,
+                value,
+                base64: false,
+            });
+        }
+        let last = keys.last_mut().unwrap();
+        // zset 的分数和 stream 的 ID 不是值，不参与 base64 编码
+        let is_value = |index: usize| match name.as_str() {
+            "ZADD" => index % 2 == 1,
+            "XADD" => index > 0,
+            _ => true,
+        };
+        if !last.base64
+            && rest
+                .iter()
+                .enumerate()
+                .any(|(index, arg)| is_value(index) && std::str::from_utf8(arg).is_err())
+        {
+            encode_key_value(&mut last.value);
+            last.base64 = true;
+        }
+        let rest: Vec<String> = rest
+            .iter()
+            .enumerate()
+            .map(|(index, arg)| {
+                if last.base64 && is_value(index) {
+                    BASE64.encode(arg)
+                } else {
+                    String::from_utf8_lossy(arg).to_string()
+                }
+            })
+            .collect();
+        match &mut last.value {
+            RedisKeyValue::String(value) => *value = rest.first().cloned().unwrap_or_default(),
+            RedisKeyValue::Members(values) => values.extend(rest.iter().cloned()),
+            RedisKeyValue::Hash(fields) => {
+                fields.extend(rest.chunks(2).filter_map(|pair| match pair {
+                    [field, value] => Some((field.clone(), value.clone())),
+                    _ => None,
+                }))
+            }
+            RedisKeyValue::ZSet(values) => {
+                values.extend(rest.chunks(2).filter_map(|pair| match pair {
+                    [score, member] => Some(ZSetEnt {
+                        member: member.clone(),
+                        score: score.parse().unwrap_or_default(),
+                    }),
+                    _ => None,
+                }))
+            }
+            RedisKeyValue::Stream(entries) => {
+                if let Some((id, fields)) = rest.split_first() {
+                    entries.push(StreamEnt {
+                        id: id.clone(),
+                        fields: fields
+                            .chunks(2)
+                            .filter_map(|pair| match pair {
+                                [field, value] => Some((field.clone(), value.clone())),
+                                _ => None,
+                            })
+                            .collect(),
+                    });
+                }
+            }
+        }
+    }
+    Ok((keys, failed))
+}
+
+#[derive(Serialize, Deserialize, Debug)]
+pub struct ZSetData {
+    pub values: Vec<ZSetEnt>,
+    pub total_length: usize,
+}
+
+#[derive(Serialize, Deserialize, Debug, Clone)]
+pub struct ZSetEnt {
+    pub member: String,
+    pub score: f64,
+}
+
+impl ZSetData {
+    pub fn new(values: Vec<ZSetEnt>, total_length: usize) -> Self {
+        Self {
+            values,
+            total_length,
+        }
+    }
+}
+
+#[derive(Serialize, Deserialize, Debug)]
+pub struct HashData {
+    pub values: Vec<HashEnt>,
+    pub total_length: usize,
+}
+
+#[derive(Serialize, Deserialize, Debug)]
+pub struct HashEnt {
+    pub key: String,
+    pub val: String,
+}
+
+impl HashData {
+    pub fn new(values: Vec<HashEnt>, total_length: usize) -> Self {
+        Self {
+            values,
+            total_length,
+        }
+    }
+}
+
+#[derive(Serialize, Deserialize, Debug)]
+pub struct SetData {
+    pub values: HashSet<String>,
+    pub total_length: usize,
+}
+
+impl SetData {
+    pub fn new(values: HashSet<String>, total_length: usize) -> Self {
+        Self {
+            values,
+            total_length,
+        }
+    }
+}
+
+#[derive(Serialize, Deserialize, Debug)]
+pub struct ListData {
+    pub values: Vec<String>,
+    pub total_length: usize,
+}
+
+impl ListData {
+    pub fn new(values: Vec<String>, total_length: usize) -> Self {
+        Self {
+            values,
+            total_length,
+        }
+    }
+}
+
+#[derive(Serialize, Deserialize, Debug)]
+pub struct ScanKeyResult {
+    pub new_cursor: usize,
+    pub keys: Vec<KeyInfo>, // 使用 Vec<KeyInfo> 替代 HashMap<String, KeyType>
+}
+
+#[derive(Serialize, Deserialize, Debug)]
 pub struct KeyInfo {
     pub key_name: String,
     pub key_type: KeyType,
@@ -910,13 +2779,14 @@ impl ScanKeyResult {
     }
 }
 
-#[derive(Serialize, Deserialize, Debug)]
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq)]
 pub enum KeyType {
     String,
     List,
     Set,
     ZSet,
     Hash,
+    Stream,
     None,
     Unknown,
 }
@@ -930,10 +2800,25 @@ impl KeyType {
             "set" => KeyType::Set,
             "zset" => KeyType::ZSet,
             "hash" => KeyType::Hash,
+            "stream" => KeyType::Stream,
             "none" => KeyType::None,
             _ => KeyType::Unknown,
         }
     }
+
+    // 转换为 TYPE 命令返回的名称
+    fn as_str(&self) -> &'static str {
+        match self {
+            KeyType::String => "string",
+            KeyType::List => "list",
+            KeyType::Set => "set",
+            KeyType::ZSet => "zset",
+            KeyType::Hash => "hash",
+            KeyType::Stream => "stream",
+            KeyType::None => "none",
+            KeyType::Unknown => "unknown",
+        }
+    }
 }
 
 impl KeyspaceInfo {
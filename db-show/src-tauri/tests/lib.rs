@@ -2,5 +2,6 @@ pub mod config;
 pub mod op;
 
 pub mod cry;
+pub mod util;
 #[cfg(test)]
 pub mod tests {}
@@ -2,7 +2,14 @@
 mod tests {
     use elastic::prelude::*;
 
-    use db_show::op::es_op::EsOperation;
+    use db_show::config::es_config::{ESUserPassword, EsAuthMode};
+    use db_show::op::es_entity::{
+        EsAliasAction, EsAliasActionType, EsAliasInfo, EsAllocationExplain, EsAnalyzeOptions,
+        EsByQueryOptions, EsFieldCaps, EsIndexInfo, EsIndexMapping, EsResizeOptions, EsResizeType,
+        EsSearchOptions, EsSearchResult, EsShardInfo, EsSnapshotOptions, EsTaskInfo, EsTaskStatus,
+        EsWriteOptions, NodesStats,
+    };
+    use db_show::op::es_op::{es_error_message, parse_bulk_operations, EsOperation};
 
     #[tokio::test]
     async fn main() -> Result<(), Box<dyn std::error::Error>> {
@@ -28,16 +35,14 @@ mod tests {
 
     #[tokio::test]
     async fn test() -> Result<(), Box<dyn std::error::Error>> {
-        // Elasticsearch节点的URL
-        let url = "1";
-        let c = EsOperation::new(url.to_string());
+        let c = get_es_op();
         // 获取集群健康状态
         let x = c.get_cluster_health().await;
 
         dbg!(x);
         let x1 = c.get_nodes_stats().await;
         dbg!(x1);
-        let x12 = c.get_index_stats().await;
+        let x12 = c.list_indices("").await;
         dbg!(x12);
 
         // // 获取索引状态
@@ -50,4 +55,443 @@ mod tests {
 
         Ok(())
     }
+
+    #[tokio::test]
+    async fn test_index_management() {
+        let operation = get_es_op();
+        let body = r#"{
+            "settings": {"number_of_shards": 2, "number_of_replicas": 0},
+            "mappings": {"properties": {"message": {"type": "text"}}}
+        }"#;
+        dbg!(operation.create_index("test-logs", body).await);
+        dbg!(operation.list_indices("test-*").await);
+        dbg!(
+            operation
+                .update_index_settings("test-logs", r#"{"index.refresh_interval": "5s"}"#)
+                .await
+        );
+        dbg!(operation.get_index_settings("test-logs").await);
+        dbg!(operation.refresh_index("test-logs").await);
+        dbg!(operation.force_merge("test-logs", Some(1), false).await);
+        let resize = EsResizeOptions {
+            resize_type: EsResizeType::Shrink,
+            target: "test-logs-shrink".to_string(),
+            body: r#"{"settings": {"index.number_of_shards": 1}}"#.to_string(),
+            block_source_writes: true,
+        };
+        dbg!(operation.resize_index("test-logs", &resize).await);
+        let actions = vec![EsAliasAction {
+            action: EsAliasActionType::Add,
+            index: "test-logs".to_string(),
+            alias: "test-current".to_string(),
+            filter: String::new(),
+            is_write_index: Some(true),
+        }];
+        dbg!(operation.update_aliases(&actions).await);
+        dbg!(operation.list_aliases().await);
+        dbg!(operation.close_index("test-logs").await);
+        dbg!(operation.delete_index("test-logs,test-logs-shrink").await);
+    }
+
+    #[test]
+    fn test_parse_cat_rows() {
+        let row = serde_json::json!({
+            "health": "yellow", "status": "open", "index": "logs-2024.01", "uuid": "abc",
+            "pri": "3", "rep": "1", "docs.count": "1200", "docs.deleted": "4",
+            "store.size": "2048000", "pri.store.size": "1024000"
+        });
+        let index = EsIndexInfo::from_cat_row(&row);
+        assert_eq!(index.primaries, 3);
+        assert_eq!(index.docs_count, 1200);
+        assert_eq!(index.store_size, 2048000);
+
+        let closed = serde_json::json!({
+            "health": "red", "status": "close", "index": "old", "uuid": "def",
+            "pri": "1", "rep": "0", "docs.count": null, "docs.deleted": null,
+            "store.size": null, "pri.store.size": null
+        });
+        let closed = EsIndexInfo::from_cat_row(&closed);
+        assert_eq!(closed.status, "close");
+        assert_eq!(closed.docs_count, 0);
+
+        let alias = serde_json::json!({
+            "alias": "current", "index": "logs-2024.01", "filter": "-",
+            "routing.index": "-", "routing.search": "-", "is_write_index": "true"
+        });
+        let alias = EsAliasInfo::from_cat_row(&alias);
+        assert!(!alias.filtered);
+        assert_eq!(alias.is_write_index, Some(true));
+    }
+
+    #[tokio::test]
+    async fn test_search() {
+        let operation = get_es_op();
+        let indices = vec!["test-logs".to_string()];
+        let body = r#"{
+            "query": {"match": {"message": "error"}},
+            "sort": [{"@timestamp": "desc"}],
+            "highlight": {"fields": {"message": {}}},
+            "aggs": {"levels": {"terms": {"field": "level"}}}
+        }"#;
+        dbg!(operation.validate_query(&indices, body).await);
+        dbg!(operation.count(&indices, body).await);
+
+        let pit_id = operation.open_pit(&indices, "1m").await.data;
+        let mut options = EsSearchOptions {
+            body: body.to_string(),
+            size: Some(10),
+            pit_id: pit_id.clone(),
+            ..Default::default()
+        };
+        let first = operation.search(&options).await;
+        if let Some(result) = first.data {
+            options.pit_id = result.pit_id.clone();
+            options.search_after = result.next_search_after.clone();
+            dbg!(&result);
+            dbg!(operation.search(&options).await);
+        }
+        if let Some(pit_id) = pit_id {
+            dbg!(operation.close_pit(&pit_id).await);
+        }
+    }
+
+    #[test]
+    fn test_parse_search_result() {
+        let response = serde_json::json!({
+            "took": 5, "timed_out": false, "pit_id": "pit-2",
+            "_shards": {"total": 2, "successful": 2, "skipped": 0, "failed": 0},
+            "hits": {
+                "total": {"value": 10000, "relation": "gte"},
+                "max_score": null,
+                "hits": [
+                    {"_index": "logs", "_id": "1", "_score": null,
+                     "_source": {"message": "disk error"},
+                     "highlight": {"message": ["disk <em>error</em>"]},
+                     "sort": [1704067200000_u64, 12]},
+                    {"_index": "logs", "_id": "2", "_score": null,
+                     "_source": {"message": "net error"}, "sort": [1704067100000_u64, 7]}
+                ]
+            },
+            "aggregations": {"levels": {"buckets": [{"key": "error", "doc_count": 2}]}}
+        });
+        let result = EsSearchResult::from_response(&response);
+        assert_eq!(result.total, 10000);
+        assert_eq!(result.total_relation, "gte");
+        assert_eq!(result.hits.len(), 2);
+        assert_eq!(
+            result.hits[0].highlight.as_ref().unwrap()["message"][0],
+            "disk <em>error</em>"
+        );
+        assert_eq!(
+            result.next_search_after,
+            Some(serde_json::json!([1704067100000_u64, 7]))
+        );
+        assert_eq!(result.pit_id.as_deref(), Some("pit-2"));
+        assert_eq!(result.shards.successful, 2);
+        assert!(result.aggregations.is_some());
+
+        let legacy =
+            serde_json::json!({"took": 1, "hits": {"total": 3, "max_score": 1.0, "hits": []}});
+        let legacy = EsSearchResult::from_response(&legacy);
+        assert_eq!(legacy.total, 3);
+        assert_eq!(legacy.total_relation, "eq");
+        assert_eq!(legacy.max_score, Some(1.0));
+    }
+
+    #[tokio::test]
+    async fn test_documents() {
+        let operation = get_es_op();
+        let options = EsWriteOptions {
+            refresh: true,
+            ..Default::default()
+        };
+        let body = r#"{"message": "disk error", "level": "error"}"#;
+        dbg!(
+            operation
+                .index_document("test-logs", "1", body, true, &options)
+                .await
+        );
+        let document = operation.get_document("test-logs", "1").await;
+        dbg!(&document);
+        if let Some(document) = document.data {
+            let options = EsWriteOptions {
+                if_seq_no: document.seq_no,
+                if_primary_term: document.primary_term,
+                ..Default::default()
+            };
+            dbg!(
+                operation
+                    .update_document("test-logs", "1", r#"{"level": "warn"}"#, false, &options)
+                    .await
+            );
+        }
+        let by_query = EsByQueryOptions {
+            indices: vec!["test-logs".to_string()],
+            body: r#"{"query": {"term": {"level": "warn"}}, "script": {"source": "ctx._source.level = 'info'"}}"#
+                .to_string(),
+            conflicts_proceed: true,
+            ..Default::default()
+        };
+        let result = operation.update_by_query(&by_query).await;
+        dbg!(&result);
+        if let Some(task) = result.data.and_then(|result| result.task) {
+            dbg!(operation.get_task(&task).await);
+        }
+        let file_path = std::env::temp_dir().join("test-logs.ndjson");
+        std::fs::write(
+            &file_path,
+            "{\"index\": {\"_id\": \"2\"}}\n{\"message\": \"ok\"}\n{\"delete\": {\"_id\": \"1\"}}\n",
+        )
+        .unwrap();
+        dbg!(
+            operation
+                .bulk_import("test-logs", file_path.to_str().unwrap(), true)
+                .await
+        );
+        dbg!(
+            operation
+                .delete_document("test-logs", "2", &EsWriteOptions::default())
+                .await
+        );
+    }
+
+    #[test]
+    fn test_parse_bulk_operations() {
+        let content = "{\"index\": {\"_index\": \"logs\", \"_id\": \"1\"}}\n\
+            {\"message\": \"a\"}\n\
+            \n\
+            {\"delete\": {\"_index\": \"logs\", \"_id\": \"2\"}}\n\
+            {\"update\": {\"_index\": \"logs\", \"_id\": \"3\"}}\n\
+            {\"doc\": {\"message\": \"c\"}}\n";
+        let operations = parse_bulk_operations(content).unwrap();
+        assert_eq!(operations.len(), 3);
+        assert_eq!(operations[0].action, "index");
+        assert_eq!(operations[0].payload.lines().count(), 2);
+        assert_eq!(operations[1].action, "delete");
+        assert_eq!(operations[1].line, 4);
+        assert!(operations[1].payload.ends_with('\n'));
+        assert_eq!(operations[2].line, 5);
+
+        assert!(parse_bulk_operations("{\"index\": {}}\n").is_err());
+        assert!(parse_bulk_operations("{\"upsert\": {}}\n{}\n").is_err());
+        assert!(parse_bulk_operations("not json\n").is_err());
+
+        let task = serde_json::json!({
+            "completed": true,
+            "task": {"action": "indices:data/write/update/byquery", "description": "update-by-query [logs]",
+                     "running_time_in_nanos": 2_500_000_000_u64,
+                     "status": {"total": 10, "updated": 8, "created": 0, "deleted": 0, "version_conflicts": 2}},
+            "response": {"took": 2500, "timed_out": false, "total": 10, "updated": 8, "version_conflicts": 2,
+                         "failures": [{"index": "logs", "id": "9", "cause": {"type": "version_conflict_engine_exception", "reason": "conflict"}}]}
+        });
+        let task = EsTaskStatus::from_value("node:1", &task);
+        assert!(task.completed);
+        assert_eq!(task.running_time_ms, 2500);
+        assert_eq!(task.version_conflicts, 2);
+        let response = task.result.unwrap();
+        assert_eq!(response.updated, 8);
+        assert_eq!(
+            response.failures,
+            vec!["version_conflict_engine_exception: conflict"]
+        );
+    }
+
+    #[tokio::test]
+    async fn test_mapping_tools() {
+        let operation = get_es_op();
+        dbg!(operation.get_mapping("test-logs").await);
+        dbg!(operation.field_caps("test-*", "", true).await);
+        let options = EsAnalyzeOptions {
+            analyzer: "standard".to_string(),
+            text: "Disk Error on node-1".to_string(),
+            ..Default::default()
+        };
+        dbg!(operation.analyze(&options).await);
+    }
+
+    #[test]
+    fn test_parse_mapping() {
+        let response = serde_json::json!({
+            "mappings": {
+                "dynamic": "strict",
+                "properties": {
+                    "message": {"type": "text", "analyzer": "ik_max_word",
+                                "fields": {"keyword": {"type": "keyword", "ignore_above": 256}}},
+                    "user": {"properties": {"name": {"type": "keyword", "doc_values": false},
+                                            "age": {"type": "integer", "index": false}}},
+                    "@timestamp": {"type": "date", "format": "epoch_millis"}
+                }
+            }
+        });
+        let mapping = EsIndexMapping::from_value("logs", &response);
+        assert_eq!(mapping.dynamic.as_deref(), Some("strict"));
+        let names: Vec<&str> = mapping
+            .fields
+            .iter()
+            .map(|field| field.name.as_str())
+            .collect();
+        assert_eq!(names, vec!["@timestamp", "message", "user"]);
+        let message = &mapping.fields[1];
+        assert_eq!(message.analyzer.as_deref(), Some("ik_max_word"));
+        assert_eq!(message.fields[0].path, "message.keyword");
+        let user = &mapping.fields[2];
+        assert_eq!(user.field_type, "object");
+        assert_eq!(user.properties[0].path, "user.age");
+        assert!(!user.properties[0].index);
+        assert_eq!(user.properties[1].doc_values, Some(false));
+
+        let caps = serde_json::json!({
+            "indices": ["logs-1", "logs-2"],
+            "fields": {
+                "_id": {"_id": {"type": "_id", "searchable": true, "aggregatable": false}},
+                "status": {
+                    "keyword": {"type": "keyword", "searchable": true, "aggregatable": true, "indices": ["logs-1"]},
+                    "long": {"type": "long", "searchable": true, "aggregatable": true, "indices": ["logs-2"]}
+                },
+                "message": {"text": {"type": "text", "searchable": true, "aggregatable": false}}
+            }
+        });
+        let caps = EsFieldCaps::from_response(&caps);
+        assert_eq!(caps.len(), 2);
+        assert!(!caps[0].conflict);
+        assert!(caps[1].conflict);
+        assert_eq!(caps[1].types[1].indices, vec!["logs-2"]);
+    }
+
+    #[tokio::test]
+    async fn test_cluster_operations() {
+        let operation = get_es_op();
+        dbg!(operation.get_nodes_stats().await);
+        dbg!(operation.list_shards("", true).await);
+        dbg!(operation.allocation_explain("", 0, false).await);
+        dbg!(operation.list_tasks("*search*").await);
+        dbg!(operation.get_cluster_settings(false).await);
+        dbg!(operation.list_repositories().await);
+        dbg!(
+            operation
+                .create_repository("backup", "fs", r#"{"location": "/tmp/es-backup"}"#, true)
+                .await
+        );
+        let options = EsSnapshotOptions {
+            indices: vec!["test-logs".to_string()],
+            wait_for_completion: true,
+            ..Default::default()
+        };
+        dbg!(
+            operation
+                .create_snapshot("backup", "snapshot-1", &options)
+                .await
+        );
+        dbg!(operation.list_snapshots("backup").await);
+    }
+
+    #[test]
+    fn test_parse_cluster_stats() {
+        let response = serde_json::json!({
+            "nodes": {
+                "n2": {"name": "node-2", "roles": ["data"], "os": {"mem": {"total_in_bytes": 200}}},
+                "n1": {"name": "node-1", "roles": ["master", "data"],
+                       "jvm": {"mem": {"heap_used_percent": 42},
+                               "gc": {"collectors": {"old": {"collection_count": 3}}}},
+                       "os": {"cpu": {"percent": 7}, "mem": {"total_in_bytes": 100}}}
+            }
+        });
+        let stats = NodesStats::from_response(&response);
+        assert_eq!(stats.node_count, 2);
+        assert_eq!(stats.total_memory, 300);
+        assert_eq!(stats.nodes[0].name, "node-1");
+        assert_eq!(stats.nodes[0].heap_used_percent, 42);
+        assert_eq!(stats.nodes[0].gc_old_count, 3);
+        assert_eq!(stats.nodes[1].roles, vec!["data"]);
+
+        let row = serde_json::json!({
+            "index": "logs", "shard": "1", "prirep": "r", "state": "UNASSIGNED",
+            "docs": null, "store": null, "unassigned.reason": "NODE_LEFT"
+        });
+        let shard = EsShardInfo::from_cat_row(&row);
+        assert_eq!(shard.shard, 1);
+        assert!(!shard.primary);
+        assert_eq!(shard.unassigned_reason.as_deref(), Some("NODE_LEFT"));
+        assert_eq!(shard.unassigned_details, None);
+
+        let explain = serde_json::json!({
+            "index": "logs", "shard": 1, "primary": false, "current_state": "unassigned",
+            "unassigned_info": {"reason": "NODE_LEFT"},
+            "can_allocate": "no",
+            "node_allocation_decisions": [{
+                "node_name": "node-1", "node_decision": "no",
+                "deciders": [{"decider": "same_shard", "decision": "NO", "explanation": "copy exists"}]
+            }]
+        });
+        let explain = EsAllocationExplain::from_response(&explain);
+        assert_eq!(explain.can_allocate.as_deref(), Some("no"));
+        assert_eq!(explain.current_node, None);
+        assert_eq!(explain.node_decisions[0].deciders[0].decider, "same_shard");
+
+        let tasks = serde_json::json!({
+            "nodes": {"n1": {"name": "node-1", "tasks": {
+                "n1:1": {"action": "indices:data/read/search", "running_time_in_nanos": 2_000_000,
+                         "cancellable": true},
+                "n1:2": {"action": "indices:data/write/reindex", "running_time_in_nanos": 9_000_000,
+                         "parent_task_id": "n1:0"}
+            }}}
+        });
+        let tasks = EsTaskInfo::from_response(&tasks);
+        assert_eq!(tasks[0].task_id, "n1:2");
+        assert_eq!(tasks[0].running_time_ms, 9);
+        assert_eq!(tasks[0].parent_task_id.as_deref(), Some("n1:0"));
+        assert!(tasks[1].cancellable);
+    }
+
+    fn get_es_op() -> EsOperation {
+        let es_user_password = ESUserPassword {
+            username: "elastic".to_string(),
+            password: "elastic".to_string(),
+            host: "127.0.0.1".to_string(),
+            port: 9200,
+            nodes: vec!["127.0.0.1:9201".to_string()],
+            ..Default::default()
+        };
+        EsOperation::new(&es_user_password).unwrap()
+    }
+
+    #[test]
+    fn test_node_urls() {
+        let es_user_password = ESUserPassword {
+            host: "es1".to_string(),
+            port: 9200,
+            https: true,
+            nodes: vec![
+                "es2:9200".to_string(),
+                "http://es3:9200/".to_string(),
+                "https://es1:9200".to_string(),
+                " ".to_string(),
+            ],
+            ..Default::default()
+        };
+        assert_eq!(
+            es_user_password.node_urls(),
+            vec!["https://es1:9200", "https://es2:9200", "http://es3:9200"]
+        );
+
+        let api_key = ESUserPassword {
+            auth_mode: EsAuthMode::ApiKey,
+            ..es_user_password
+        };
+        assert!(EsOperation::new(&api_key).is_err());
+        assert!(EsOperation::new(&ESUserPassword::default()).is_err());
+    }
+
+    #[test]
+    fn test_es_error_message() {
+        let text = r#"{"error":{"root_cause":[],"type":"index_not_found_exception","reason":"no such index [logs]"},"status":404}"#;
+        assert_eq!(
+            es_error_message(reqwest::StatusCode::NOT_FOUND, text),
+            "操作失败，HTTP状态码: 404，index_not_found_exception: no such index [logs]"
+        );
+        assert_eq!(
+            es_error_message(reqwest::StatusCode::BAD_GATEWAY, "bad gateway"),
+            "操作失败，HTTP状态码: 502，bad gateway"
+        );
+    }
 }
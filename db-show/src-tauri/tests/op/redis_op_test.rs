@@ -3,36 +3,43 @@ mod tests {
     use std::collections::{BTreeMap, HashMap};
 
     use db_show::config::redis_config::RedisUserPassword;
+    use db_show::op::redis_entity::{
+        has_config_defaults, AclUser, ImportConflictPolicy, MonitorEntry, MonitorFilter,
+        RedisConfigItem, RedisFailoverOptions, RedisFunctionLibrary, RedisMigrateOptions,
+        RedisReplicationStatus, RedisReply, RedisTransferFormat, ReplicaInfo,
+    };
     use db_show::op::redis_op::RedisOperation;
+    use db_show::resp::resp::SUCCESS;
+    use redis::InfoDict;
 
-    #[test]
-    pub fn test_server_info() {
+    #[tokio::test]
+    pub async fn test_server_info() {
         let operation = get_redis_op();
-        let response = operation.get_server_info();
+        let response = operation.get_server_info().await;
         dbg!(response);
     }
 
-    #[test]
-    pub fn c() {
+    #[tokio::test]
+    pub async fn c() {
         let operation = get_redis_op();
-        let response = operation.get_db_size();
+        let response = operation.get_db_size().await;
         dbg!(response);
     }
 
-    #[test]
-    pub fn test_get_keys_page() {
+    #[tokio::test]
+    pub async fn test_get_keys_page() {
         let operation = get_redis_op();
 
         let db_index = 4;
         let page = 0;
         let page_size = 1;
-        let result = operation.get_keys_page(db_index, page, page_size);
+        let result = operation.get_keys_page(db_index, page, page_size).await;
         dbg!(result);
         println!();
     }
 
-    #[test]
-    pub fn test_loop_get_keys_page() {
+    #[tokio::test]
+    pub async fn test_loop_get_keys_page() {
         let operation = get_redis_op();
 
         let db_index = 4;
@@ -41,7 +48,7 @@ mod tests {
 
         loop {
             // 调用 get_keys_page 方法获取一页的键和类型
-            let result = operation.get_keys_page(db_index, page, page_size);
+            let result = operation.get_keys_page(db_index, page, page_size).await;
             dbg!(&result);
             // 打印当前页的键和类型
             match result.data {
@@ -70,72 +77,78 @@ mod tests {
         }
     }
 
-    #[test]
-    pub fn test_get_string_data() {
+    #[tokio::test]
+    pub async fn test_get_string_data() {
         let operation = get_redis_op();
 
         let db_index = 1;
-        let result = operation.get_string_data(db_index, "c".to_string());
+        let result = operation.get_string_data(db_index, "c".to_string()).await;
         dbg!(result);
         println!();
     }
 
-    #[test]
-    pub fn test_get_list_data() {
+    #[tokio::test]
+    pub async fn test_get_list_data() {
         let operation = get_redis_op();
 
         let db_index = 1;
-        let result = operation.get_list_data(db_index, "bac".to_string(), 0, 1);
+        let result = operation
+            .get_list_data(db_index, "bac".to_string(), 0, 1)
+            .await;
         dbg!(result);
         println!();
     }
 
-    #[test]
-    pub fn test_get_set_data() {
+    #[tokio::test]
+    pub async fn test_get_set_data() {
         let operation = get_redis_op();
 
         let db_index = 1;
-        let result = operation.get_set_data(db_index, "bb".to_string());
+        let result = operation.get_set_data(db_index, "bb".to_string()).await;
 
         dbg!(result);
         println!();
     }
 
-    #[test]
-    pub fn test_get_hash_data() {
+    #[tokio::test]
+    pub async fn test_get_hash_data() {
         let operation = get_redis_op();
 
         let db_index = 1;
-        let result = operation.get_hash_data(db_index, "hh".to_string());
+        let result = operation.get_hash_data(db_index, "hh".to_string()).await;
 
         dbg!(result);
         println!();
     }
 
-    #[test]
-    pub fn test_get_zset_data() {
+    #[tokio::test]
+    pub async fn test_get_zset_data() {
         let operation = get_redis_op();
 
         let db_index = 1;
-        let result = operation.get_zset_data(db_index, "zset".to_string(), 0, 2);
+        let result = operation
+            .get_zset_data(db_index, "zset".to_string(), 0, 2)
+            .await;
 
         dbg!(result);
         println!();
     }
 
-    #[test]
-    pub fn test_set_string_data() {
+    #[tokio::test]
+    pub async fn test_set_string_data() {
         let operation = get_redis_op();
 
         let db_index = 1;
-        let result = operation.set_string_data(db_index, "str".to_string(), "2".to_string());
+        let result = operation
+            .set_string_data(db_index, "str".to_string(), "2".to_string())
+            .await;
 
         dbg!(result);
         println!();
     }
 
-    #[test]
-    pub fn test_set_list_data() {
+    #[tokio::test]
+    pub async fn test_set_list_data() {
         let operation = get_redis_op();
 
         let db_index = 1;
@@ -145,14 +158,16 @@ mod tests {
             "value3".to_string(),
         ];
 
-        let result = operation.set_list_data(db_index, "list".to_string(), values);
+        let result = operation
+            .set_list_data(db_index, "list".to_string(), values)
+            .await;
 
         dbg!(result);
         println!();
     }
 
-    #[test]
-    pub fn test_set_set_data() {
+    #[tokio::test]
+    pub async fn test_set_set_data() {
         let operation = get_redis_op();
 
         let db_index = 1;
@@ -162,14 +177,16 @@ mod tests {
             "value3".to_string(),
         ];
 
-        let result = operation.set_set_data(db_index, "set".to_string(), values);
+        let result = operation
+            .set_set_data(db_index, "set".to_string(), values)
+            .await;
 
         dbg!(result);
         println!();
     }
 
-    #[test]
-    pub fn test_set_hash_data() {
+    #[tokio::test]
+    pub async fn test_set_hash_data() {
         let operation = get_redis_op();
 
         let db_index = 1;
@@ -179,14 +196,16 @@ mod tests {
         fields_and_values.insert("field2".to_string(), "value2".to_string());
 
         // 调用 set_hash_data 函数进行设置
-        let result = operation.set_hash_data(db_index, "hash".to_string(), fields_and_values);
+        let result = operation
+            .set_hash_data(db_index, "hash".to_string(), fields_and_values)
+            .await;
 
         dbg!(result);
         println!();
     }
 
-    #[test]
-    pub fn test_set_zset_data() {
+    #[tokio::test]
+    pub async fn test_set_zset_data() {
         let operation = get_redis_op();
 
         let db_index = 1;
@@ -197,69 +216,552 @@ mod tests {
         members_scores.insert("member3".to_string(), 3.0);
 
         // 调用 set_hash_data 函数进行设置
-        let result = operation.set_zset_data(db_index, "zset".to_string(), members_scores);
+        let result = operation
+            .set_zset_data(db_index, "zset".to_string(), members_scores)
+            .await;
 
         dbg!(result);
         println!();
     }
 
-    #[test]
-    pub fn test_get_db_key_count() {
+    #[tokio::test]
+    pub async fn test_get_db_key_count() {
         let operation = get_redis_op();
 
         let db_index = 1;
 
         // 调用 set_hash_data 函数进行设置
-        let result = operation.get_db_key_count(db_index);
+        let result = operation.get_db_key_count(db_index).await;
 
         dbg!(result);
         println!();
     }
 
-    #[test]
-    pub fn test_change_set() {
+    #[tokio::test]
+    pub async fn test_change_set() {
         let operation = get_redis_op();
 
         let db_index = 1;
 
         // 调用 set_hash_data 函数进行设置
-        let result = operation.change_set(
-            db_index,
-            "set".to_string(),
-            "value3111".to_string(),
-            "v2".to_string(),
-        );
+        let result = operation
+            .change_set(
+                db_index,
+                "set".to_string(),
+                "value3111".to_string(),
+                "v2".to_string(),
+            )
+            .await;
 
         dbg!(result);
         println!();
     }
 
-    #[test]
-    pub fn test_delete_redis_key() {
+    #[tokio::test]
+    pub async fn test_delete_redis_key() {
         let operation = get_redis_op();
 
         let db_index = 1;
 
         // 调用 set_hash_data 函数进行设置
-        let result = operation.delete_redis_key(db_index, "del1".to_string());
+        let result = operation
+            .delete_redis_key(db_index, "del1".to_string())
+            .await;
 
         dbg!(result);
         println!();
     }
 
-    #[test]
-    pub fn test_set_redis_key_expire() {
+    #[tokio::test]
+    pub async fn test_set_redis_key_expire() {
         let operation = get_redis_op();
 
         let db_index = 1;
 
         // 调用 set_hash_data 函数进行设置
-        let result = operation.set_redis_key_expire(db_index, "baca".to_string(), 100);
+        let result = operation
+            .set_redis_key_expire(db_index, "baca".to_string(), 100)
+            .await;
 
         dbg!(result);
         println!();
     }
 
+    #[tokio::test]
+    pub async fn test_pubsub() {
+        let operation = get_redis_op();
+
+        let mut handle = operation
+            .subscribe(
+                "test".to_string(),
+                vec!["news".to_string()],
+                vec!["news.*".to_string()],
+                |message| {
+                    dbg!(message);
+                },
+            )
+            .unwrap();
+
+        dbg!(
+            operation
+                .publish("news".to_string(), "hello".to_string())
+                .await
+        );
+        dbg!(
+            operation
+                .publish("news.tech".to_string(), "world".to_string())
+                .await
+        );
+        dbg!(operation.pubsub_channels("news*").await);
+        dbg!(operation.pubsub_numsub(vec!["news".to_string()]).await);
+        dbg!(operation.pubsub_numpat().await);
+
+        std::thread::sleep(std::time::Duration::from_secs(1));
+        handle.stop();
+    }
+
+    #[test]
+    pub fn test_parse_monitor_entry() {
+        let entry =
+            MonitorEntry::parse(r#"1339518083.107412 [0 127.0.0.1:60866] "set" "a\"b" "\x01""#)
+                .unwrap();
+        assert_eq!(entry.db, 0);
+        assert_eq!(entry.client, "127.0.0.1:60866");
+        assert_eq!(entry.command, "SET");
+        assert_eq!(entry.args, vec!["a\"b".to_string(), "\u{1}".to_string()]);
+    }
+
+    #[tokio::test]
+    pub async fn test_monitor() {
+        let operation = get_redis_op();
+
+        let filter = MonitorFilter {
+            commands: vec!["set".to_string()],
+            key_pattern: "user:*".to_string(),
+            client: "".to_string(),
+        };
+        let mut handle = operation
+            .monitor("test".to_string(), filter, None, 10, |entry| {
+                dbg!(entry);
+            })
+            .unwrap();
+
+        operation
+            .set_string_data(1, "user:1".to_string(), "a".to_string())
+            .await;
+        std::thread::sleep(std::time::Duration::from_secs(1));
+        handle.stop();
+    }
+
+    #[tokio::test]
+    pub async fn test_export_import_keys() {
+        let operation = get_redis_op();
+
+        let db_index = 1;
+        for format in [
+            RedisTransferFormat::JsonLines,
+            RedisTransferFormat::Csv,
+            RedisTransferFormat::Resp,
+        ] {
+            let file_path = std::env::temp_dir().join(format!("redis_export_{:?}", format));
+            let file_path = file_path.to_str().unwrap();
+
+            let exported = operation
+                .export_keys(db_index, "*", format.clone(), file_path)
+                .await;
+            dbg!(exported);
+            let imported = operation
+                .import_keys(
+                    db_index,
+                    format,
+                    file_path,
+                    ImportConflictPolicy::Rename,
+                    ":imported",
+                )
+                .await;
+            dbg!(imported);
+        }
+    }
+
+    #[tokio::test]
+    pub async fn test_import_keys_with_failed_key() {
+        let operation = get_redis_op();
+
+        let db_index = 1;
+        let file_path = std::env::temp_dir().join("redis_import_failed_key.jsonl");
+        let file_path = file_path.to_str().unwrap();
+        // XADD 不接受 0-0，stream key 在事务中失败
+        let lines = [
+            r#"{"key":"import:list","key_type":"List","ttl":-1,"value":["a","b"]}"#,
+            r#"{"key":"import:stream","key_type":"Stream","ttl":-1,"value":[{"id":"0-0","fields":{"f":"v"}}]}"#,
+            r#"{"key":"import:string","key_type":"String","ttl":-1,"value":"hello"}"#,
+        ];
+        std::fs::write(file_path, lines.join("\n")).unwrap();
+
+        for _ in 0..2 {
+            let result = operation
+                .import_keys(
+                    db_index,
+                    RedisTransferFormat::JsonLines,
+                    file_path,
+                    ImportConflictPolicy::Overwrite,
+                    "",
+                )
+                .await
+                .data
+                .unwrap();
+            assert_eq!(result.imported, 2);
+            assert_eq!(result.failed.len(), 1);
+            assert_eq!(result.failed[0].key, "import:stream");
+
+            let list = operation
+                .get_list_data(db_index, "import:list".to_string(), 0, -1)
+                .await
+                .data
+                .unwrap();
+            assert_eq!(list.values, vec!["a".to_string(), "b".to_string()]);
+            let string = operation
+                .get_string_data(db_index, "import:string".to_string())
+                .await;
+            assert_eq!(string.data.as_deref(), Some("hello"));
+        }
+    }
+
+    #[tokio::test]
+    pub async fn test_export_binary_value() {
+        let operation = get_redis_op();
+
+        let db_index = 1;
+        let file_path = std::env::temp_dir().join("redis_export_binary.jsonl");
+        let file_path = file_path.to_str().unwrap();
+        // 0xFF 不是合法的 UTF-8，导出时整个 key 使用 base64 编码
+        let line =
+            r#"{"key":"binary:string","key_type":"String","ttl":-1,"value":"/w==","base64":true}"#;
+        std::fs::write(file_path, line).unwrap();
+        let imported = operation
+            .import_keys(
+                db_index,
+                RedisTransferFormat::JsonLines,
+                file_path,
+                ImportConflictPolicy::Overwrite,
+                "",
+            )
+            .await
+            .data
+            .unwrap();
+        assert_eq!(imported.imported, 1);
+
+        let exported = operation
+            .export_keys(
+                db_index,
+                "binary:*",
+                RedisTransferFormat::JsonLines,
+                file_path,
+            )
+            .await
+            .data
+            .unwrap();
+        assert_eq!(exported.exported, 1);
+        assert!(exported.failed.is_empty());
+        let content = std::fs::read_to_string(file_path).unwrap();
+        let key: serde_json::Value = serde_json::from_str(content.trim()).unwrap();
+        assert_eq!(key["base64"], true);
+        assert_eq!(key["value"], "/w==");
+
+        // CSV 没有编码标记列，base64 编码的 key 被跳过
+        let exported = operation
+            .export_keys(db_index, "binary:*", RedisTransferFormat::Csv, file_path)
+            .await
+            .data
+            .unwrap();
+        assert_eq!(exported.skipped, vec!["binary:string".to_string()]);
+    }
+
+    #[tokio::test]
+    pub async fn test_migrate_keys() {
+        let source = get_redis_op();
+        let target = get_redis_op();
+
+        let options = RedisMigrateOptions {
+            keep_ttl: true,
+            replace: false,
+            source_prefix: "user:".to_string(),
+            target_prefix: "migrated:user:".to_string(),
+        };
+        let result = source.migrate_keys(&target, 1, 2, "user:*", options).await;
+
+        dbg!(result);
+    }
+
+    #[tokio::test]
+    pub async fn test_eval_script() {
+        let operation = get_redis_op();
+
+        let script = "return redis.call('INCRBY', KEYS[1], ARGV[1])";
+        let response = operation
+            .eval_script(
+                0,
+                script,
+                vec!["rate:limit".to_string()],
+                vec!["1".to_string()],
+            )
+            .await;
+        dbg!(response);
+
+        let sha = operation.script_load(script).await.data.unwrap();
+        dbg!(
+            operation
+                .script_exists(vec![sha.clone(), "0".repeat(40)])
+                .await
+        );
+        dbg!(
+            operation
+                .evalsha(
+                    0,
+                    sha.as_str(),
+                    vec!["rate:limit".to_string()],
+                    vec!["1".to_string()],
+                )
+                .await
+        );
+        dbg!(operation.script_flush().await);
+    }
+
+    #[tokio::test]
+    pub async fn test_function() {
+        let operation = get_redis_op();
+
+        let code = "#!lua name=mylib\n\
+            redis.register_function('myincr', function(keys, args) \
+            return redis.call('INCRBY', keys[1], args[1]) end)";
+        dbg!(operation.function_load(code, true).await);
+        dbg!(operation.function_list("my*", true).await);
+        dbg!(
+            operation
+                .fcall(
+                    0,
+                    "myincr",
+                    vec!["rate:limit".to_string()],
+                    vec!["2".to_string()],
+                    false,
+                )
+                .await
+        );
+        dbg!(operation.function_delete("mylib").await);
+    }
+
+    #[test]
+    pub fn test_parse_function_library() {
+        let data = |s: &str| redis::Value::Data(s.as_bytes().to_vec());
+        let value = redis::Value::Bulk(vec![
+            data("library_name"),
+            data("mylib"),
+            data("engine"),
+            data("LUA"),
+            data("functions"),
+            redis::Value::Bulk(vec![redis::Value::Bulk(vec![
+                data("name"),
+                data("myincr"),
+                data("description"),
+                redis::Value::Nil,
+                data("flags"),
+                redis::Value::Bulk(vec![redis::Value::Status("no-writes".to_string())]),
+            ])]),
+        ]);
+
+        let library = RedisFunctionLibrary::from_value(&value).unwrap();
+        assert_eq!(library.library_name, "mylib");
+        assert_eq!(library.engine, "LUA");
+        assert_eq!(library.functions[0].name, "myincr");
+        assert_eq!(library.functions[0].description, None);
+        assert_eq!(library.functions[0].flags, vec!["no-writes".to_string()]);
+        assert_eq!(library.library_code, None);
+
+        let reply = RedisReply::from(&redis::Value::Bulk(vec![
+            redis::Value::Int(1),
+            data("a"),
+            redis::Value::Nil,
+        ]));
+        assert_eq!(
+            reply,
+            RedisReply::Array(vec![
+                RedisReply::Integer(1),
+                RedisReply::Bulk("a".to_string()),
+                RedisReply::Nil,
+            ])
+        );
+    }
+
+    #[tokio::test]
+    pub async fn test_config() {
+        let operation = get_redis_op();
+
+        dbg!(operation.config_get("maxmemory*").await);
+        dbg!(operation.config_set("slowlog-max-len", "256").await);
+        dbg!(operation.config_diff().await);
+    }
+
+    #[test]
+    pub fn test_config_item_default() {
+        let item = RedisConfigItem::new("appendonly".to_string(), "yes".to_string(), "7.2.4");
+        assert_eq!(item.default_value.as_deref(), Some("no"));
+        assert!(item.modified);
+
+        let item = RedisConfigItem::new("save".to_string(), "900 1".to_string(), "6.2.14");
+        assert_eq!(item.default_value, None);
+        assert!(!item.modified);
+        assert!(has_config_defaults("7.0.15"));
+        assert!(!has_config_defaults("6.2.14"));
+        assert!(!has_config_defaults(""));
+    }
+
+    #[tokio::test]
+    pub async fn test_acl_user() {
+        let operation = get_redis_op();
+
+        let user = AclUser {
+            name: "reader".to_string(),
+            enabled: true,
+            key_patterns: vec!["%R~cache:*".to_string()],
+            commands: vec!["+@read".to_string()],
+            ..Default::default()
+        };
+        dbg!(
+            operation
+                .acl_save_user(&user, vec!["reader-pass".to_string()])
+                .await
+        );
+        dbg!(operation.acl_get_user("reader").await);
+        dbg!(operation.acl_list().await);
+        dbg!(operation.acl_log(10).await);
+        dbg!(operation.acl_delete_users(vec!["reader".to_string()]).await);
+    }
+
+    #[test]
+    pub fn test_parse_acl_user() {
+        let user = AclUser::parse(
+            "user app on #5e884898da28047151d0e56f8dc6292773603d0d6aabbdd62a11ef721d1542d8 \
+             ~app:* %R~cache:* &* -@all +get +set (~log:* +xadd) sanitize-payload",
+        )
+        .unwrap();
+        assert_eq!(user.name, "app");
+        assert!(user.enabled);
+        assert!(!user.nopass);
+        assert_eq!(user.passwords.len(), 1);
+        assert_eq!(user.key_patterns, vec!["~app:*", "%R~cache:*"]);
+        assert_eq!(user.channel_patterns, vec!["&*"]);
+        assert_eq!(user.commands, vec!["-@all", "+get", "+set"]);
+        assert_eq!(user.selectors, vec!["(~log:* +xadd)"]);
+        assert_eq!(user.flags, vec!["sanitize-payload"]);
+
+        let default_user = AclUser::parse("user default on nopass ~* &* +@all").unwrap();
+        assert!(default_user.nopass);
+        assert_eq!(
+            default_user.to_rules(),
+            vec!["on", "nopass", "~*", "&*", "+@all"]
+        );
+    }
+
+    #[test]
+    pub fn test_parse_acl_getuser_redis6() {
+        use redis::Value;
+
+        let data = |s: &str| Value::Data(s.as_bytes().to_vec());
+        // Redis 6.2 的 ACL GETUSER：keys、channels 为不带前缀的模式列表
+        let reply = Value::Bulk(vec![
+            data("flags"),
+            Value::Bulk(vec![
+                data("on"),
+                data("allkeys"),
+                data("allchannels"),
+                data("allcommands"),
+                data("nopass"),
+            ]),
+            data("passwords"),
+            Value::Bulk(vec![]),
+            data("commands"),
+            data("+@all"),
+            data("keys"),
+            Value::Bulk(vec![data("*")]),
+            data("channels"),
+            Value::Bulk(vec![data("*")]),
+        ]);
+        let user = AclUser::from_getuser("default", &reply).unwrap();
+        assert!(user.flags.is_empty());
+        assert_eq!(user.to_rules(), vec!["on", "nopass", "~*", "&*", "+@all"]);
+
+        let reply = Value::Bulk(vec![
+            data("flags"),
+            Value::Bulk(vec![data("on")]),
+            data("passwords"),
+            Value::Bulk(vec![]),
+            data("commands"),
+            data("-@all +get"),
+            data("keys"),
+            Value::Bulk(vec![data("user:*"), data("cache:*")]),
+            data("channels"),
+            Value::Bulk(vec![data("news.*")]),
+        ]);
+        let user = AclUser::from_getuser("app", &reply).unwrap();
+        assert_eq!(user.key_patterns, vec!["~user:*", "~cache:*"]);
+        assert_eq!(user.channel_patterns, vec!["&news.*"]);
+        assert_eq!(user.commands, vec!["-@all", "+get"]);
+        assert!(user.flags.is_empty());
+    }
+
+    #[tokio::test]
+    pub async fn test_replication_status() {
+        let operation = get_redis_op();
+
+        dbg!(operation.get_replication_status(1024 * 1024).await);
+        dbg!(operation.get_persistence_status().await);
+        dbg!(operation.bgsave(true).await);
+    }
+
+    #[tokio::test]
+    pub async fn test_failover_force_requires_timeout() {
+        let operation = get_redis_op();
+
+        let options = RedisFailoverOptions {
+            host: "10.0.0.2".to_string(),
+            port: 6380,
+            force: true,
+            timeout_ms: None,
+            abort: false,
+        };
+        let response = operation.failover(options).await;
+        assert_ne!(response.status_code, SUCCESS);
+        assert_eq!(response.description, "FORCE 需要指定超时时间");
+    }
+
+    #[test]
+    pub fn test_parse_replica_info() {
+        let replica = ReplicaInfo::parse(
+            "slave0",
+            "ip=10.0.0.2,port=6380,state=online,offset=900,lag=1",
+            1000,
+        )
+        .unwrap();
+        assert_eq!(replica.ip, "10.0.0.2");
+        assert_eq!(replica.port, 6380);
+        assert_eq!(replica.state, "online");
+        assert_eq!(replica.offset, 900);
+        assert_eq!(replica.lag, 1);
+        assert_eq!(replica.offset_behind, 100);
+
+        let info = InfoDict::new(
+            "# Replication\r\nrole:master\r\nconnected_slaves:2\r\n\
+             slave0:ip=10.0.0.2,port=6380,state=online,offset=900,lag=1\r\n\
+             slave1:ip=10.0.0.3,port=6380,state=online,offset=10,lag=30\r\n\
+             master_repl_offset:1000\r\n",
+        );
+        let status = RedisReplicationStatus::from_info(&info, 500);
+        assert_eq!(status.replicas.len(), 2);
+        assert_eq!(status.alerts.len(), 1);
+        assert_eq!(status.alerts[0].replica, "10.0.0.3:6380");
+        assert!(status.master.is_none());
+    }
+
     fn get_redis_op() -> RedisOperation {
         // let redis_config = RedisUserPassword {
         //     username: "root".to_string(),
@@ -0,0 +1,16 @@
+#[cfg(test)]
+mod tests {
+    use db_show::util::csv::{csv_escape, parse_csv_records};
+
+    #[test]
+    fn test_csv_round_trip() {
+        let fields = ["plain", "a,b", "say \"hi\"", "line1\nline2", ""];
+        let line: Vec<String> = fields.iter().map(|field| csv_escape(field)).collect();
+        let content = format!("h1,h2,h3,h4,h5\r\n{}\n", line.join(","));
+
+        let records = parse_csv_records(&content);
+        assert_eq!(records.len(), 2);
+        assert_eq!(records[0], vec!["h1", "h2", "h3", "h4", "h5"]);
+        assert_eq!(records[1], fields);
+    }
+}